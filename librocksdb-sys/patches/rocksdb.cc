@@ -1,12 +1,116 @@
 #include "patches/rocksdb.h"
 
+#include "rocksdb/compaction_filter.h"
+#include "rocksdb/env.h"
+#include "rocksdb/listener.h"
+#include "rocksdb/rate_limiter.h"
+#include "rocksdb/sst_file_manager.h"
+#include "rocksdb/sst_file_reader.h"
+#include "rocksdb/sst_file_writer.h"
+#include "rocksdb/statistics.h"
+#include "rocksdb/table_properties.h"
+#include "rocksdb/utilities/checkpoint.h"
+#include "rocksdb/utilities/backup_engine.h"
 #include "rocksdb/utilities/options_util.h"
+#include "rocksdb/utilities/transaction.h"
+#include "rocksdb/utilities/transaction_db.h"
+#include "rocksdb/utilities/write_batch_with_index.h"
+#include "rocksdb/write_buffer_manager.h"
+#include "db/dbformat.h"
+#include "db/range_tombstone_fragmenter.h"
+#include "file/random_access_file_reader.h"
+#include "options/cf_options.h"
+#include "table/table_reader.h"
+#include "utilities/transactions/pessimistic_transaction_db.h"
 
+using rocksdb::BackupEngine;
+using rocksdb::BackupEngineOptions;
+using rocksdb::BackupInfo;
 using rocksdb::Cache;
+using rocksdb::Checkpoint;
+using rocksdb::ColumnFamilyOptions;
 using rocksdb::ColumnFamilyDescriptor;
+using rocksdb::ColumnFamilyHandle;
+using rocksdb::CompactionFilter;
+using rocksdb::CompactionFilterFactory;
+using rocksdb::CompactRangeOptions;
+using rocksdb::DB;
 using rocksdb::Env;
+using rocksdb::EnvOptions;
+using rocksdb::EventListener;
+using rocksdb::ExportImportFilesMetaData;
+using rocksdb::ExternalSstFileInfo;
+using rocksdb::FlushJobInfo;
+using rocksdb::ImportColumnFamilyOptions;
+using rocksdb::Iterator;
+using rocksdb::InfoLogLevel;
+using rocksdb::LiveFileMetaData;
+using rocksdb::Logger;
+using rocksdb::CompactionJobInfo;
+using rocksdb::WriteStallInfo;
+using rocksdb::TableFileDeletionInfo;
+using rocksdb::BackgroundErrorReason;
 using rocksdb::Options;
+using rocksdb::PessimisticTransactionDB;
+using rocksdb::PinnableSlice;
+using rocksdb::RateLimiter;
+using rocksdb::ReadOptions;
+using rocksdb::Slice;
+using rocksdb::SstFileManager;
+using rocksdb::SstFileMetaData;
+using rocksdb::SstFileReader;
+using rocksdb::SstFileWriter;
 using rocksdb::Status;
+using rocksdb::TableProperties;
+using rocksdb::Transaction;
+using rocksdb::TransactionDB;
+using rocksdb::TransactionDBOptions;
+using rocksdb::TransactionLogIterator;
+using rocksdb::TxnDBWritePolicy;
+using rocksdb::WriteBatch;
+using rocksdb::WriteBatchWithIndex;
+using rocksdb::WriteBufferManager;
+using rocksdb::WriteOptions;
+
+static bool SaveError(char** errptr, const Status& s) {
+    if (s.ok()) {
+        return false;
+    }
+    if (*errptr != nullptr) {
+        free(*errptr);
+    }
+    *errptr = strdup(s.ToString().c_str());
+    return true;
+}
+
+static char* CopyString(const std::string& str) {
+    char* result = reinterpret_cast<char*>(malloc(sizeof(char) * str.size()));
+    memcpy(result, str.data(), sizeof(char) * str.size());
+    return result;
+}
+
+static void SaveMultiGetResults(
+    const std::vector<Status>& statuses,
+    const std::vector<std::string>& values,
+    char** values_list,
+    size_t* values_list_sizes,
+    char** errs) {
+    for (size_t i = 0; i < statuses.size(); i++) {
+        if (statuses[i].ok()) {
+            values_list[i] = CopyString(values[i]);
+            values_list_sizes[i] = values[i].size();
+            errs[i] = nullptr;
+        } else {
+            values_list[i] = nullptr;
+            values_list_sizes[i] = 0;
+            if (!statuses[i].IsNotFound()) {
+                errs[i] = strdup(statuses[i].ToString().c_str());
+            } else {
+                errs[i] = nullptr;
+            }
+        }
+    }
+}
 
 extern "C" {
     // Copy structs from librocksdb-sys/rocksdb/db/c.cc
@@ -20,8 +124,348 @@ extern "C" {
     struct rocksdb_options_t {
         Options rep;
     };
+    struct rocksdb_logger_t {
+        std::shared_ptr<Logger> rep;
+    };
+    struct rocksdb_readoptions_t {
+        ReadOptions rep;
+        // stack variables to set pointers to in ReadOptions
+        Slice upper_bound;
+        Slice lower_bound;
+        Slice timestamp;
+        Slice iter_start_ts;
+    };
+    struct rocksdb_iterator_t {
+        Iterator* rep;
+    };
+    struct rocksdb_envoptions_t {
+        EnvOptions rep;
+    };
+    struct rocksdb_sstfilewriter_t {
+        SstFileWriter* rep;
+    };
+    struct rocksdb_livefiles_t {
+        std::vector<LiveFileMetaData> rep;
+    };
+    struct rocksdb_sst_file_metadata_t {
+        const SstFileMetaData* rep;
+    };
+    struct rocksdb_column_family_handle_t {
+        ColumnFamilyHandle* rep;
+    };
+    struct rocksdb_pinnableslice_t {
+        PinnableSlice rep;
+    };
+    struct rocksdb_transactiondb_options_t {
+        TransactionDBOptions rep;
+    };
+    struct rocksdb_transactiondb_t {
+        TransactionDB* rep;
+    };
+    struct rocksdb_transaction_t {
+        Transaction* rep;
+    };
+    struct rocksdb_writebatch_t {
+        WriteBatch rep;
+    };
+    struct rocksdb_writebatch_wi_t {
+        WriteBatchWithIndex* rep;
+    };
+    struct rocksdb_writeoptions_t {
+        WriteOptions rep;
+    };
+    struct rocksdb_t {
+        DB* rep;
+    };
+    struct rocksdb_backup_engine_t {
+        BackupEngine* rep;
+    };
+    struct rocksdb_backup_engine_info_t {
+        std::vector<BackupInfo> rep;
+    };
+    struct rocksdb_backup_engine_options_t {
+        BackupEngineOptions rep;
+    };
+    struct rocksdb_ratelimiter_t {
+        std::shared_ptr<RateLimiter> rep;
+    };
+    struct rocksdb_checkpoint_t {
+        Checkpoint* rep;
+    };
+    struct rocksdb_compactoptions_t {
+        CompactRangeOptions rep;
+        Slice full_history_ts_low;
+    };
+    struct rocksdb_wal_iterator_t {
+        TransactionLogIterator* rep;
+    };
+    struct rocksdb_wal_readoptions_t {
+        TransactionLogIterator::ReadOptions rep;
+    };
+    struct rocksdb_compactionfilter_t : public CompactionFilter {
+        void* state_;
+        void (*destructor_)(void*);
+        unsigned char (*filter_)(
+            void*,
+            int level,
+            const char* key, size_t key_length,
+            const char* existing_value, size_t value_length,
+            char** new_value, size_t *new_value_length,
+            unsigned char* value_changed);
+        const char* (*name_)(void*);
+        unsigned char ignore_snapshots_;
+
+        ~rocksdb_compactionfilter_t() override { (*destructor_)(state_); }
+
+        bool Filter(int level, const Slice& key, const Slice& existing_value,
+                    std::string* new_value, bool* value_changed) const override {
+            char* c_new_value = nullptr;
+            size_t new_value_length = 0;
+            unsigned char c_value_changed = 0;
+            unsigned char result = (*filter_)(
+                state_,
+                level,
+                key.data(), key.size(),
+                existing_value.data(), existing_value.size(),
+                &c_new_value, &new_value_length, &c_value_changed);
+            if (c_value_changed) {
+                new_value->assign(c_new_value, new_value_length);
+                *value_changed = true;
+            }
+            return result;
+        }
+
+        const char* Name() const override { return (*name_)(state_); }
+
+        bool IgnoreSnapshots() const override { return ignore_snapshots_; }
+    };
+
+    struct rocksdb_compactionfiltercontext_t {
+        CompactionFilter::Context rep;
+    };
+    struct rocksdb_compactionfilterfactory_t : public CompactionFilterFactory {
+        void* state_;
+        void (*destructor_)(void*);
+        rocksdb_compactionfilter_t* (*create_compaction_filter_)(
+            void*, rocksdb_compactionfiltercontext_t* context);
+        const char* (*name_)(void*);
+
+        ~rocksdb_compactionfilterfactory_t() override { (*destructor_)(state_); }
+
+        std::unique_ptr<CompactionFilter> CreateCompactionFilter(
+            const CompactionFilter::Context& context) override {
+            rocksdb_compactionfiltercontext_t ccontext;
+            ccontext.rep = context;
+            CompactionFilter* cf = (*create_compaction_filter_)(state_, &ccontext);
+            return std::unique_ptr<CompactionFilter>(cf);
+        }
+
+        const char* Name() const override { return (*name_)(state_); }
+    };
 
     // New structs
+    struct rocksdb_flushjobinfo_t {
+        FlushJobInfo rep;
+    };
+    struct rocksdb_compactionjobinfo_t {
+        CompactionJobInfo rep;
+    };
+    struct rocksdb_writestallinfo_t {
+        WriteStallInfo rep;
+    };
+    struct rocksdb_tablefiledeletioninfo_t {
+        TableFileDeletionInfo rep;
+    };
+    struct rocksdb_write_buffer_manager_t {
+        std::shared_ptr<WriteBufferManager> rep;
+    };
+    struct rocksdb_sst_file_manager_t {
+        std::shared_ptr<SstFileManager> rep;
+    };
+    struct rocksdb_export_import_files_metadata_t {
+        ExportImportFilesMetaData rep;
+    };
+    struct rocksdb_sstfilereader_t {
+        SstFileReader* rep;
+        // Kept to open the file again with internal keys, which SstFileReader
+        // does not expose.
+        Options options;
+        std::string file_path;
+    };
+    struct rocksdb_table_properties_t {
+        std::shared_ptr<const TableProperties> rep;
+    };
+    struct rocksdb_external_sst_file_info_t {
+        ExternalSstFileInfo rep;
+    };
+    struct rocksdb_eventlistener_t : public EventListener {
+        void* state_;
+        void (*destructor_)(void*);
+        void (*on_flush_begin_)(void*, const rocksdb_flushjobinfo_t*);
+        void (*on_flush_completed_)(void*, const rocksdb_flushjobinfo_t*);
+        void (*on_compaction_begin_)(void*, const rocksdb_compactionjobinfo_t*);
+        void (*on_compaction_completed_)(void*, const rocksdb_compactionjobinfo_t*);
+        void (*on_stall_conditions_changed_)(void*, const rocksdb_writestallinfo_t*);
+        void (*on_background_error_)(void*, int reason, const char* error);
+        void (*on_table_file_deleted_)(void*, const rocksdb_tablefiledeletioninfo_t*);
+
+        ~rocksdb_eventlistener_t() override { (*destructor_)(state_); }
+
+        void OnFlushBegin(DB*, const FlushJobInfo& info) override {
+            (*on_flush_begin_)(state_, reinterpret_cast<const rocksdb_flushjobinfo_t*>(&info));
+        }
+
+        void OnFlushCompleted(DB*, const FlushJobInfo& info) override {
+            (*on_flush_completed_)(
+                state_, reinterpret_cast<const rocksdb_flushjobinfo_t*>(&info));
+        }
+
+        void OnCompactionBegin(DB*, const CompactionJobInfo& info) override {
+            (*on_compaction_begin_)(
+                state_, reinterpret_cast<const rocksdb_compactionjobinfo_t*>(&info));
+        }
+
+        void OnCompactionCompleted(DB*, const CompactionJobInfo& info) override {
+            (*on_compaction_completed_)(
+                state_, reinterpret_cast<const rocksdb_compactionjobinfo_t*>(&info));
+        }
+
+        void OnStallConditionsChanged(const WriteStallInfo& info) override {
+            (*on_stall_conditions_changed_)(
+                state_, reinterpret_cast<const rocksdb_writestallinfo_t*>(&info));
+        }
+
+        void OnBackgroundError(BackgroundErrorReason reason, Status* bg_error) override {
+            std::string error = bg_error->ToString();
+            (*on_background_error_)(state_, static_cast<int>(reason), error.c_str());
+        }
+
+        void OnTableFileDeleted(const TableFileDeletionInfo& info) override {
+            (*on_table_file_deleted_)(
+                state_, reinterpret_cast<const rocksdb_tablefiledeletioninfo_t*>(&info));
+        }
+    };
+    struct rocksdb_callback_logger_t : public Logger {
+        void* state_;
+        void (*destructor_)(void*);
+        void (*log_)(void*, int level, const char* msg, size_t len);
+
+        rocksdb_callback_logger_t(
+            InfoLogLevel log_level,
+            void* state,
+            void (*destructor)(void*),
+            void (*log)(void*, int, const char*, size_t))
+            : Logger(log_level), state_(state), destructor_(destructor), log_(log) {}
+
+        ~rocksdb_callback_logger_t() override { (*destructor_)(state_); }
+
+        using Logger::Logv;
+
+        void Logv(const char* format, va_list ap) override {
+            Logv(rocksdb::INFO_LEVEL, format, ap);
+        }
+
+        void Logv(const InfoLogLevel log_level, const char* format, va_list ap) override {
+            if (log_level < GetInfoLogLevel()) {
+                return;
+            }
+            va_list backup_ap;
+            va_copy(backup_ap, ap);
+            char buffer[512];
+            int len = vsnprintf(buffer, sizeof(buffer), format, backup_ap);
+            va_end(backup_ap);
+            if (len < 0) {
+                return;
+            }
+            if (static_cast<size_t>(len) < sizeof(buffer)) {
+                (*log_)(state_, static_cast<int>(log_level), buffer, len);
+                return;
+            }
+            std::string message(len + 1, '\0');
+            vsnprintf(&message[0], message.size(), format, ap);
+            (*log_)(state_, static_cast<int>(log_level), message.data(), len);
+        }
+    };
+    struct rocksdb_compactionfilter_v2_t : public rocksdb_compactionfilter_t {
+        int (*filter_v2_)(
+            void*,
+            int level,
+            const char* key, size_t key_length,
+            int value_type,
+            const char* existing_value, size_t value_length,
+            void* new_value,
+            void* skip_until);
+
+        Decision FilterV2(int level, const Slice& key, ValueType value_type,
+                          const Slice& existing_value, std::string* new_value,
+                          std::string* skip_until) const override {
+            int c_value_type;
+            switch (value_type) {
+                case ValueType::kMergeOperand:
+                    c_value_type = rocksdb_compactionfilter_merge_operand;
+                    break;
+                case ValueType::kBlobIndex:
+                    c_value_type = rocksdb_compactionfilter_blob_index;
+                    break;
+                default:
+                    c_value_type = rocksdb_compactionfilter_value;
+                    break;
+            }
+            int result = (*filter_v2_)(
+                state_,
+                level,
+                key.data(), key.size(),
+                c_value_type,
+                existing_value.data(), existing_value.size(),
+                new_value,
+                skip_until);
+            switch (result) {
+                case rocksdb_compactionfilter_remove:
+                    return Decision::kRemove;
+                case rocksdb_compactionfilter_change_value:
+                    // A blob reference can't be replaced by a plain value.
+                    return value_type == ValueType::kBlobIndex
+                        ? Decision::kKeep
+                        : Decision::kChangeValue;
+                case rocksdb_compactionfilter_remove_and_skip_until:
+                    return Decision::kRemoveAndSkipUntil;
+                default:
+                    return Decision::kKeep;
+            }
+        }
+    };
+    struct rocksdb_compactionfiltercontext_v2_t : public rocksdb_compactionfiltercontext_t {
+        const std::string* column_family_name;
+    };
+    struct rocksdb_compactionfilterfactory_v2_t : public rocksdb_compactionfilterfactory_t {
+        std::unique_ptr<CompactionFilter> CreateCompactionFilter(
+            const CompactionFilter::Context& context) override {
+            return CreateCompactionFilter(context, nullptr);
+        }
+
+        std::unique_ptr<CompactionFilter> CreateCompactionFilter(
+            const CompactionFilter::Context& context,
+            const std::string* column_family_name) {
+            rocksdb_compactionfiltercontext_v2_t ccontext;
+            ccontext.rep = context;
+            ccontext.column_family_name = column_family_name;
+            CompactionFilter* cf = (*create_compaction_filter_)(state_, &ccontext);
+            return std::unique_ptr<CompactionFilter>(cf);
+        }
+    };
+    // Passes the name of the column family it was set for to the contexts
+    // of a factory which may be shared by several column families.
+    struct ColumnFamilyNamedCompactionFilterFactory : public CompactionFilterFactory {
+        std::shared_ptr<rocksdb_compactionfilterfactory_v2_t> base_;
+        std::string column_family_name_;
+
+        std::unique_ptr<CompactionFilter> CreateCompactionFilter(
+            const CompactionFilter::Context& context) override {
+            return base_->CreateCompactionFilter(context, &column_family_name_);
+        }
+
+        const char* Name() const override { return base_->Name(); }
+    };
     struct rocksdb_column_family_descriptor_t {
         char *name;
         Options options;
@@ -110,4 +554,1420 @@ extern "C" {
         *errptr = strdup(status.ToString().c_str());
         return full_opts;
     }
+
+    static void SavePinnedMultiGetResults(
+        size_t num_keys,
+        PinnableSlice* value_slices,
+        const Status* statuses,
+        rocksdb_pinnableslice_t** values,
+        char** errs) {
+        for (size_t i = 0; i < num_keys; i++) {
+            if (statuses[i].ok()) {
+                values[i] = new rocksdb_pinnableslice_t;
+                values[i]->rep = std::move(value_slices[i]);
+                errs[i] = nullptr;
+            } else {
+                values[i] = nullptr;
+                if (!statuses[i].IsNotFound()) {
+                    errs[i] = strdup(statuses[i].ToString().c_str());
+                } else {
+                    errs[i] = nullptr;
+                }
+            }
+        }
+    }
+
+    void rocksdb_transactiondb_batched_multi_get_cf(
+        rocksdb_transactiondb_t* txn_db,
+        const rocksdb_readoptions_t* options,
+        rocksdb_column_family_handle_t* column_family,
+        size_t num_keys,
+        const char* const* keys_list,
+        const size_t* keys_list_sizes,
+        rocksdb_pinnableslice_t** values,
+        char** errs,
+        const bool sorted_input) {
+
+        std::vector<Slice> key_slices(num_keys);
+        std::vector<PinnableSlice> value_slices(num_keys);
+        std::vector<Status> statuses(num_keys);
+        for (size_t i = 0; i < num_keys; i++) {
+            key_slices[i] = Slice(keys_list[i], keys_list_sizes[i]);
+        }
+        txn_db->rep->MultiGet(options->rep, column_family->rep, num_keys,
+                              key_slices.data(), value_slices.data(),
+                              statuses.data(), sorted_input);
+        SavePinnedMultiGetResults(num_keys, value_slices.data(), statuses.data(), values, errs);
+    }
+
+    void rocksdb_transaction_batched_multi_get_cf(
+        rocksdb_transaction_t* txn,
+        const rocksdb_readoptions_t* options,
+        rocksdb_column_family_handle_t* column_family,
+        size_t num_keys,
+        const char* const* keys_list,
+        const size_t* keys_list_sizes,
+        rocksdb_pinnableslice_t** values,
+        char** errs,
+        const bool sorted_input) {
+
+        std::vector<Slice> key_slices(num_keys);
+        std::vector<PinnableSlice> value_slices(num_keys);
+        std::vector<Status> statuses(num_keys);
+        for (size_t i = 0; i < num_keys; i++) {
+            key_slices[i] = Slice(keys_list[i], keys_list_sizes[i]);
+        }
+        txn->rep->MultiGet(options->rep, column_family->rep, num_keys,
+                           key_slices.data(), value_slices.data(),
+                           statuses.data(), sorted_input);
+        SavePinnedMultiGetResults(num_keys, value_slices.data(), statuses.data(), values, errs);
+    }
+
+    void rocksdb_transaction_multi_get_for_update(
+        rocksdb_transaction_t* txn,
+        const rocksdb_readoptions_t* options,
+        size_t num_keys,
+        const char* const* keys_list,
+        const size_t* keys_list_sizes,
+        char** values_list,
+        size_t* values_list_sizes,
+        char** errs) {
+
+        std::vector<Slice> keys(num_keys);
+        for (size_t i = 0; i < num_keys; i++) {
+            keys[i] = Slice(keys_list[i], keys_list_sizes[i]);
+        }
+        std::vector<std::string> values(num_keys);
+        std::vector<Status> statuses =
+            txn->rep->MultiGetForUpdate(options->rep, keys, &values);
+        SaveMultiGetResults(statuses, values, values_list, values_list_sizes, errs);
+    }
+
+    void rocksdb_transaction_multi_get_for_update_cf(
+        rocksdb_transaction_t* txn,
+        const rocksdb_readoptions_t* options,
+        const rocksdb_column_family_handle_t* const* column_families,
+        size_t num_keys,
+        const char* const* keys_list,
+        const size_t* keys_list_sizes,
+        char** values_list,
+        size_t* values_list_sizes,
+        char** errs) {
+
+        std::vector<Slice> keys(num_keys);
+        std::vector<ColumnFamilyHandle*> cfs(num_keys);
+        for (size_t i = 0; i < num_keys; i++) {
+            keys[i] = Slice(keys_list[i], keys_list_sizes[i]);
+            cfs[i] = column_families[i]->rep;
+        }
+        std::vector<std::string> values(num_keys);
+        std::vector<Status> statuses =
+            txn->rep->MultiGetForUpdate(options->rep, cfs, keys, &values);
+        SaveMultiGetResults(statuses, values, values_list, values_list_sizes, errs);
+    }
+
+    void rocksdb_options_set_allow_2pc(rocksdb_options_t* opt, unsigned char v) {
+        opt->rep.allow_2pc = v;
+    }
+
+    void rocksdb_transactiondb_options_set_write_policy(
+        rocksdb_transactiondb_options_t* opt,
+        int write_policy) {
+        opt->rep.write_policy = static_cast<TxnDBWritePolicy>(write_policy);
+    }
+
+    void rocksdb_transactiondb_options_set_skip_concurrency_control(
+        rocksdb_transactiondb_options_t* opt,
+        unsigned char v) {
+        opt->rep.skip_concurrency_control = v;
+    }
+
+    rocksdb_wal_iterator_t* rocksdb_transactiondb_get_updates_since(
+        rocksdb_transactiondb_t* txn_db,
+        uint64_t seq_number,
+        const rocksdb_wal_readoptions_t* options,
+        char** errptr) {
+
+        std::unique_ptr<TransactionLogIterator> iter;
+        TransactionLogIterator::ReadOptions ro;
+        if (options != nullptr) {
+            ro = options->rep;
+        }
+        if (SaveError(errptr, txn_db->rep->GetUpdatesSince(seq_number, &iter, ro))) {
+            return nullptr;
+        }
+        rocksdb_wal_iterator_t* result = new rocksdb_wal_iterator_t;
+        result->rep = iter.release();
+        return result;
+    }
+
+    uint64_t rocksdb_transactiondb_get_latest_sequence_number(rocksdb_transactiondb_t* txn_db) {
+        return txn_db->rep->GetLatestSequenceNumber();
+    }
+
+    void rocksdb_writebatch_iterate_cf(
+        rocksdb_writebatch_t* b,
+        void* state,
+        void (*put_cf)(void*, uint32_t cf_id, const char* k, size_t klen, const char* v, size_t vlen),
+        void (*deleted_cf)(void*, uint32_t cf_id, const char* k, size_t klen),
+        void (*single_deleted_cf)(void*, uint32_t cf_id, const char* k, size_t klen),
+        void (*merge_cf)(void*, uint32_t cf_id, const char* k, size_t klen, const char* v, size_t vlen),
+        void (*delete_range_cf)(void*, uint32_t cf_id, const char* begin_key, size_t begin_klen, const char* end_key, size_t end_klen),
+        char** errptr) {
+
+        class H : public WriteBatch::Handler {
+        public:
+            void* state_;
+            void (*put_cf_)(void*, uint32_t, const char*, size_t, const char*, size_t);
+            void (*deleted_cf_)(void*, uint32_t, const char*, size_t);
+            void (*single_deleted_cf_)(void*, uint32_t, const char*, size_t);
+            void (*merge_cf_)(void*, uint32_t, const char*, size_t, const char*, size_t);
+            void (*delete_range_cf_)(void*, uint32_t, const char*, size_t, const char*, size_t);
+
+            Status PutCF(uint32_t cf_id, const Slice& key, const Slice& value) override {
+                (*put_cf_)(state_, cf_id, key.data(), key.size(), value.data(), value.size());
+                return Status::OK();
+            }
+            Status DeleteCF(uint32_t cf_id, const Slice& key) override {
+                (*deleted_cf_)(state_, cf_id, key.data(), key.size());
+                return Status::OK();
+            }
+            Status SingleDeleteCF(uint32_t cf_id, const Slice& key) override {
+                (*single_deleted_cf_)(state_, cf_id, key.data(), key.size());
+                return Status::OK();
+            }
+            Status MergeCF(uint32_t cf_id, const Slice& key, const Slice& value) override {
+                (*merge_cf_)(state_, cf_id, key.data(), key.size(), value.data(), value.size());
+                return Status::OK();
+            }
+            Status DeleteRangeCF(uint32_t cf_id, const Slice& begin_key, const Slice& end_key) override {
+                (*delete_range_cf_)(state_, cf_id, begin_key.data(), begin_key.size(),
+                                    end_key.data(), end_key.size());
+                return Status::OK();
+            }
+            // Batches read from the WAL of a TransactionDB carry 2PC markers.
+            Status MarkBeginPrepare(bool) override { return Status::OK(); }
+            Status MarkEndPrepare(const Slice&) override { return Status::OK(); }
+            Status MarkNoop(bool) override { return Status::OK(); }
+            Status MarkRollback(const Slice&) override { return Status::OK(); }
+            Status MarkCommit(const Slice&) override { return Status::OK(); }
+        };
+
+        H handler;
+        handler.state_ = state;
+        handler.put_cf_ = put_cf;
+        handler.deleted_cf_ = deleted_cf;
+        handler.single_deleted_cf_ = single_deleted_cf;
+        handler.merge_cf_ = merge_cf;
+        handler.delete_range_cf_ = delete_range_cf;
+        SaveError(errptr, b->rep.Iterate(&handler));
+    }
+
+    uint32_t rocksdb_column_family_handle_id(rocksdb_column_family_handle_t* handle) {
+        return handle->rep->GetID();
+    }
+
+    void rocksdb_transactiondb_write_writebatch_wi(
+        rocksdb_transactiondb_t* txn_db,
+        const rocksdb_writeoptions_t* options,
+        rocksdb_writebatch_wi_t* wbwi,
+        char** errptr) {
+        SaveError(errptr, txn_db->rep->Write(options->rep, wbwi->rep->GetWriteBatch()));
+    }
+
+    void rocksdb_backup_engine_options_set_share_files_with_checksum(
+        rocksdb_backup_engine_options_t* options,
+        unsigned char val) {
+        options->rep.share_files_with_checksum = val;
+    }
+
+    rocksdb_backup_engine_t* rocksdb_backup_engine_open_with_dir(
+        const rocksdb_backup_engine_options_t* options,
+        const char* backup_dir,
+        char** errptr) {
+        // Open with a copy, leaving the backup directory of `options` untouched.
+        BackupEngineOptions opts = options->rep;
+        opts.backup_dir = backup_dir;
+        BackupEngine* be;
+        if (SaveError(errptr, BackupEngine::Open(opts, Env::Default(), &be))) {
+            return nullptr;
+        }
+        rocksdb_backup_engine_t* result = new rocksdb_backup_engine_t;
+        result->rep = be;
+        return result;
+    }
+
+    void rocksdb_backup_engine_create_new_backup_with_metadata(
+        rocksdb_backup_engine_t* be,
+        rocksdb_t* db,
+        const char* app_metadata,
+        size_t app_metadata_len,
+        unsigned char flush_before_backup,
+        char** errptr) {
+        SaveError(errptr, be->rep->CreateNewBackupWithMetadata(
+            db->rep,
+            std::string(app_metadata, app_metadata_len),
+            flush_before_backup));
+    }
+
+    void rocksdb_backup_engine_delete_backup(
+        rocksdb_backup_engine_t* be,
+        uint32_t backup_id,
+        char** errptr) {
+        SaveError(errptr, be->rep->DeleteBackup(backup_id));
+    }
+
+    const rocksdb_backup_engine_info_t* rocksdb_backup_engine_get_backup_info_with_file_details(
+        rocksdb_backup_engine_t* be) {
+        rocksdb_backup_engine_info_t* result = new rocksdb_backup_engine_info_t;
+        be->rep->GetBackupInfo(&result->rep, true);
+        return result;
+    }
+
+    const char* rocksdb_backup_engine_info_app_metadata(
+        const rocksdb_backup_engine_info_t* info,
+        int index,
+        size_t* len) {
+        const std::string& app_metadata = info->rep[index].app_metadata;
+        *len = app_metadata.size();
+        return app_metadata.data();
+    }
+
+    size_t rocksdb_backup_engine_info_file_details_count(
+        const rocksdb_backup_engine_info_t* info,
+        int index) {
+        return info->rep[index].file_details.size();
+    }
+
+    const char* rocksdb_backup_engine_info_file_details_name(
+        const rocksdb_backup_engine_info_t* info,
+        int index,
+        size_t file_index,
+        size_t* len) {
+        const std::string& name = info->rep[index].file_details[file_index].relative_filename;
+        *len = name.size();
+        return name.data();
+    }
+
+    uint64_t rocksdb_backup_engine_info_file_details_size(
+        const rocksdb_backup_engine_info_t* info,
+        int index,
+        size_t file_index) {
+        return info->rep[index].file_details[file_index].size;
+    }
+
+    rocksdb_t* rocksdb_transactiondb_get_base_db(rocksdb_transactiondb_t* txn_db) {
+        DB* base_db = txn_db->rep->GetBaseDB();
+
+        if (base_db != nullptr) {
+            rocksdb_t* result = new rocksdb_t;
+            result->rep = base_db;
+            return result;
+        }
+
+        return nullptr;
+    }
+
+    void rocksdb_transactiondb_close_base_db(rocksdb_t* base_db) {
+        delete base_db;
+    }
+
+    void rocksdb_compact_range_opt_with_status(
+        rocksdb_t* db,
+        rocksdb_compactoptions_t* opt,
+        const char* start_key,
+        size_t start_key_len,
+        const char* limit_key,
+        size_t limit_key_len,
+        char** errptr) {
+        Slice a, b;
+        SaveError(errptr, db->rep->CompactRange(
+            opt->rep,
+            // Pass nullptr Slice if corresponding "const char*" is nullptr
+            (start_key ? (a = Slice(start_key, start_key_len), &a) : nullptr),
+            (limit_key ? (b = Slice(limit_key, limit_key_len), &b) : nullptr)));
+    }
+
+    void rocksdb_compact_range_cf_opt_with_status(
+        rocksdb_t* db,
+        rocksdb_column_family_handle_t* column_family,
+        rocksdb_compactoptions_t* opt,
+        const char* start_key,
+        size_t start_key_len,
+        const char* limit_key,
+        size_t limit_key_len,
+        char** errptr) {
+        Slice a, b;
+        SaveError(errptr, db->rep->CompactRange(
+            opt->rep, column_family->rep,
+            // Pass nullptr Slice if corresponding "const char*" is nullptr
+            (start_key ? (a = Slice(start_key, start_key_len), &a) : nullptr),
+            (limit_key ? (b = Slice(limit_key, limit_key_len), &b) : nullptr)));
+    }
+
+    void rocksdb_compactoptions_set_canceled(
+        rocksdb_compactoptions_t* opt,
+        unsigned char* canceled) {
+        static_assert(sizeof(std::atomic<bool>) == sizeof(unsigned char),
+                      "std::atomic<bool> must be a single byte");
+        opt->rep.canceled = reinterpret_cast<std::atomic<bool>*>(canceled);
+    }
+
+    rocksdb_compactionfilter_t* rocksdb_compactionfilter_create_v2(
+        void* state,
+        void (*destructor)(void*),
+        int (*filter)(
+            void*,
+            int level,
+            const char* key, size_t key_length,
+            int value_type,
+            const char* existing_value, size_t value_length,
+            void* new_value,
+            void* skip_until),
+        const char* (*name)(void*)) {
+        rocksdb_compactionfilter_v2_t* result = new rocksdb_compactionfilter_v2_t;
+        result->state_ = state;
+        result->destructor_ = destructor;
+        result->filter_ = nullptr;
+        result->filter_v2_ = filter;
+        result->ignore_snapshots_ = true;
+        result->name_ = name;
+        return result;
+    }
+
+    void rocksdb_compactionfilter_assign(void* output, const char* data, size_t len) {
+        reinterpret_cast<std::string*>(output)->assign(data, len);
+    }
+
+    uint32_t rocksdb_compactionfiltercontext_column_family_id(
+        rocksdb_compactionfiltercontext_t* context) {
+        return context->rep.column_family_id;
+    }
+
+    int rocksdb_compactionfiltercontext_reason(rocksdb_compactionfiltercontext_t* context) {
+        switch (context->rep.reason) {
+            case rocksdb::TableFileCreationReason::kFlush:
+                return rocksdb_table_file_creation_reason_flush;
+            case rocksdb::TableFileCreationReason::kCompaction:
+                return rocksdb_table_file_creation_reason_compaction;
+            case rocksdb::TableFileCreationReason::kRecovery:
+                return rocksdb_table_file_creation_reason_recovery;
+            default:
+                return rocksdb_table_file_creation_reason_misc;
+        }
+    }
+
+    const char* rocksdb_compactionfiltercontext_column_family_name(
+        rocksdb_compactionfiltercontext_t* context,
+        size_t* len) {
+        const std::string* name =
+            static_cast<rocksdb_compactionfiltercontext_v2_t*>(context)->column_family_name;
+        if (name == nullptr) {
+            *len = 0;
+            return nullptr;
+        }
+        *len = name->size();
+        return name->data();
+    }
+
+    rocksdb_compactionfilterfactory_t* rocksdb_compactionfilterfactory_create_v2(
+        void* state,
+        void (*destructor)(void*),
+        rocksdb_compactionfilter_t* (*create_compaction_filter)(
+            void*, rocksdb_compactionfiltercontext_t* context),
+        const char* (*name)(void*)) {
+        rocksdb_compactionfilterfactory_v2_t* result = new rocksdb_compactionfilterfactory_v2_t;
+        result->state_ = state;
+        result->destructor_ = destructor;
+        result->create_compaction_filter_ = create_compaction_filter;
+        result->name_ = name;
+        return result;
+    }
+
+    void rocksdb_options_set_compaction_filter_factory_column_family_name(
+        rocksdb_options_t* opt,
+        const char* name) {
+        std::shared_ptr<CompactionFilterFactory>& factory = opt->rep.compaction_filter_factory;
+        std::shared_ptr<rocksdb_compactionfilterfactory_v2_t> base;
+        if (auto named = std::dynamic_pointer_cast<ColumnFamilyNamedCompactionFilterFactory>(factory)) {
+            base = named->base_;
+        } else {
+            base = std::dynamic_pointer_cast<rocksdb_compactionfilterfactory_v2_t>(factory);
+        }
+        if (base) {
+            auto named = std::make_shared<ColumnFamilyNamedCompactionFilterFactory>();
+            named->base_ = base;
+            named->column_family_name_ = name;
+            factory = named;
+        }
+    }
+
+    void rocksdb_options_set_reverse_bytewise_comparator(rocksdb_options_t* opt) {
+        opt->rep.comparator = rocksdb::ReverseBytewiseComparator();
+    }
+
+    void rocksdb_options_ensure_statistics(rocksdb_options_t* opt) {
+        if (!opt->rep.statistics) {
+            opt->rep.statistics = rocksdb::CreateDBStatistics();
+        }
+    }
+
+    void rocksdb_options_set_statistics_level(rocksdb_options_t* opt, int level) {
+        rocksdb_options_ensure_statistics(opt);
+        if (level < rocksdb_statistics_level_disable_all) {
+            level = rocksdb_statistics_level_disable_all;
+        }
+        if (level > rocksdb_statistics_level_all) {
+            level = rocksdb_statistics_level_all;
+        }
+        opt->rep.statistics->set_stats_level(static_cast<rocksdb::StatsLevel>(level));
+    }
+
+    int rocksdb_options_get_statistics_level(rocksdb_options_t* opt) {
+        if (!opt->rep.statistics) {
+            return rocksdb_statistics_level_disable_all;
+        }
+        return static_cast<int>(opt->rep.statistics->get_stats_level());
+    }
+
+    const char* rocksdb_statistics_ticker_name(uint32_t ticker_type) {
+        for (const auto& ticker : rocksdb::TickersNameMap) {
+            if (static_cast<uint32_t>(ticker.first) == ticker_type) {
+                return ticker.second.c_str();
+            }
+        }
+        return nullptr;
+    }
+
+    const char* rocksdb_statistics_histogram_name(uint32_t histogram_type) {
+        for (const auto& histogram : rocksdb::HistogramsNameMap) {
+            if (static_cast<uint32_t>(histogram.first) == histogram_type) {
+                return histogram.second.c_str();
+            }
+        }
+        return nullptr;
+    }
+
+    uint64_t rocksdb_options_statistics_get_ticker_count(
+        rocksdb_options_t* opt,
+        uint32_t ticker_type) {
+        if (!opt->rep.statistics || ticker_type >= rocksdb::TICKER_ENUM_MAX) {
+            return 0;
+        }
+        return opt->rep.statistics->getTickerCount(ticker_type);
+    }
+
+    void rocksdb_options_statistics_get_histogram_data(
+        rocksdb_options_t* opt,
+        uint32_t histogram_type,
+        double* median,
+        double* p95,
+        double* p99,
+        double* average,
+        double* std_dev,
+        double* max,
+        double* min,
+        uint64_t* count,
+        uint64_t* sum) {
+        rocksdb::HistogramData data{};
+        if (opt->rep.statistics && histogram_type < rocksdb::HISTOGRAM_ENUM_MAX) {
+            opt->rep.statistics->histogramData(histogram_type, &data);
+        }
+        *median = data.median;
+        *p95 = data.percentile95;
+        *p99 = data.percentile99;
+        *average = data.average;
+        *std_dev = data.standard_deviation;
+        *max = data.max;
+        *min = data.min;
+        *count = data.count;
+        *sum = data.sum;
+    }
+
+    void rocksdb_options_statistics_reset(rocksdb_options_t* opt, char** errptr) {
+        if (opt->rep.statistics) {
+            SaveError(errptr, opt->rep.statistics->Reset());
+        }
+    }
+
+    rocksdb_eventlistener_t* rocksdb_eventlistener_create(
+        void* state,
+        void (*destructor)(void*),
+        void (*on_flush_begin)(void*, const rocksdb_flushjobinfo_t*),
+        void (*on_flush_completed)(void*, const rocksdb_flushjobinfo_t*),
+        void (*on_compaction_begin)(void*, const rocksdb_compactionjobinfo_t*),
+        void (*on_compaction_completed)(void*, const rocksdb_compactionjobinfo_t*),
+        void (*on_stall_conditions_changed)(void*, const rocksdb_writestallinfo_t*),
+        void (*on_background_error)(void*, int reason, const char* error),
+        void (*on_table_file_deleted)(void*, const rocksdb_tablefiledeletioninfo_t*)) {
+        rocksdb_eventlistener_t* result = new rocksdb_eventlistener_t;
+        result->state_ = state;
+        result->destructor_ = destructor;
+        result->on_flush_begin_ = on_flush_begin;
+        result->on_flush_completed_ = on_flush_completed;
+        result->on_compaction_begin_ = on_compaction_begin;
+        result->on_compaction_completed_ = on_compaction_completed;
+        result->on_stall_conditions_changed_ = on_stall_conditions_changed;
+        result->on_background_error_ = on_background_error;
+        result->on_table_file_deleted_ = on_table_file_deleted;
+        return result;
+    }
+
+    void rocksdb_options_add_eventlistener(
+        rocksdb_options_t* opt,
+        rocksdb_eventlistener_t* listener) {
+        opt->rep.listeners.emplace_back(std::shared_ptr<EventListener>(listener));
+    }
+
+    uint32_t rocksdb_flushjobinfo_cf_id(const rocksdb_flushjobinfo_t* info) {
+        return info->rep.cf_id;
+    }
+
+    const char* rocksdb_flushjobinfo_cf_name(const rocksdb_flushjobinfo_t* info, size_t* len) {
+        *len = info->rep.cf_name.size();
+        return info->rep.cf_name.data();
+    }
+
+    const char* rocksdb_flushjobinfo_file_path(const rocksdb_flushjobinfo_t* info, size_t* len) {
+        *len = info->rep.file_path.size();
+        return info->rep.file_path.data();
+    }
+
+    int rocksdb_flushjobinfo_job_id(const rocksdb_flushjobinfo_t* info) {
+        return info->rep.job_id;
+    }
+
+    unsigned char rocksdb_flushjobinfo_triggered_writes_slowdown(
+        const rocksdb_flushjobinfo_t* info) {
+        return info->rep.triggered_writes_slowdown;
+    }
+
+    unsigned char rocksdb_flushjobinfo_triggered_writes_stop(const rocksdb_flushjobinfo_t* info) {
+        return info->rep.triggered_writes_stop;
+    }
+
+    uint64_t rocksdb_flushjobinfo_smallest_seqno(const rocksdb_flushjobinfo_t* info) {
+        return info->rep.smallest_seqno;
+    }
+
+    uint64_t rocksdb_flushjobinfo_largest_seqno(const rocksdb_flushjobinfo_t* info) {
+        return info->rep.largest_seqno;
+    }
+
+    int rocksdb_flushjobinfo_flush_reason(const rocksdb_flushjobinfo_t* info) {
+        return static_cast<int>(info->rep.flush_reason);
+    }
+
+    uint64_t rocksdb_flushjobinfo_num_entries(const rocksdb_flushjobinfo_t* info) {
+        return info->rep.table_properties.num_entries;
+    }
+
+    uint64_t rocksdb_flushjobinfo_data_size(const rocksdb_flushjobinfo_t* info) {
+        return info->rep.table_properties.data_size;
+    }
+
+    uint32_t rocksdb_compactionjobinfo_cf_id(const rocksdb_compactionjobinfo_t* info) {
+        return info->rep.cf_id;
+    }
+
+    const char* rocksdb_compactionjobinfo_cf_name(
+        const rocksdb_compactionjobinfo_t* info,
+        size_t* len) {
+        *len = info->rep.cf_name.size();
+        return info->rep.cf_name.data();
+    }
+
+    void rocksdb_compactionjobinfo_status(
+        const rocksdb_compactionjobinfo_t* info,
+        char** errptr) {
+        SaveError(errptr, info->rep.status);
+    }
+
+    int rocksdb_compactionjobinfo_job_id(const rocksdb_compactionjobinfo_t* info) {
+        return info->rep.job_id;
+    }
+
+    int rocksdb_compactionjobinfo_base_input_level(const rocksdb_compactionjobinfo_t* info) {
+        return info->rep.base_input_level;
+    }
+
+    int rocksdb_compactionjobinfo_output_level(const rocksdb_compactionjobinfo_t* info) {
+        return info->rep.output_level;
+    }
+
+    size_t rocksdb_compactionjobinfo_input_files_count(const rocksdb_compactionjobinfo_t* info) {
+        return info->rep.input_files.size();
+    }
+
+    const char* rocksdb_compactionjobinfo_input_file_at(
+        const rocksdb_compactionjobinfo_t* info,
+        size_t pos,
+        size_t* len) {
+        const std::string& path = info->rep.input_files[pos];
+        *len = path.size();
+        return path.data();
+    }
+
+    size_t rocksdb_compactionjobinfo_output_files_count(const rocksdb_compactionjobinfo_t* info) {
+        return info->rep.output_files.size();
+    }
+
+    const char* rocksdb_compactionjobinfo_output_file_at(
+        const rocksdb_compactionjobinfo_t* info,
+        size_t pos,
+        size_t* len) {
+        const std::string& path = info->rep.output_files[pos];
+        *len = path.size();
+        return path.data();
+    }
+
+    int rocksdb_compactionjobinfo_compaction_reason(const rocksdb_compactionjobinfo_t* info) {
+        return static_cast<int>(info->rep.compaction_reason);
+    }
+
+    uint64_t rocksdb_compactionjobinfo_elapsed_micros(const rocksdb_compactionjobinfo_t* info) {
+        return info->rep.stats.elapsed_micros;
+    }
+
+    uint64_t rocksdb_compactionjobinfo_num_corrupt_keys(const rocksdb_compactionjobinfo_t* info) {
+        return info->rep.stats.num_corrupt_keys;
+    }
+
+    uint64_t rocksdb_compactionjobinfo_input_records(const rocksdb_compactionjobinfo_t* info) {
+        return info->rep.stats.num_input_records;
+    }
+
+    uint64_t rocksdb_compactionjobinfo_output_records(const rocksdb_compactionjobinfo_t* info) {
+        return info->rep.stats.num_output_records;
+    }
+
+    uint64_t rocksdb_compactionjobinfo_total_input_bytes(const rocksdb_compactionjobinfo_t* info) {
+        return info->rep.stats.total_input_bytes;
+    }
+
+    uint64_t rocksdb_compactionjobinfo_total_output_bytes(
+        const rocksdb_compactionjobinfo_t* info) {
+        return info->rep.stats.total_output_bytes;
+    }
+
+    const char* rocksdb_writestallinfo_cf_name(const rocksdb_writestallinfo_t* info, size_t* len) {
+        *len = info->rep.cf_name.size();
+        return info->rep.cf_name.data();
+    }
+
+    int rocksdb_writestallinfo_cur(const rocksdb_writestallinfo_t* info) {
+        return static_cast<int>(info->rep.condition.cur);
+    }
+
+    int rocksdb_writestallinfo_prev(const rocksdb_writestallinfo_t* info) {
+        return static_cast<int>(info->rep.condition.prev);
+    }
+
+    const char* rocksdb_tablefiledeletioninfo_db_name(
+        const rocksdb_tablefiledeletioninfo_t* info,
+        size_t* len) {
+        *len = info->rep.db_name.size();
+        return info->rep.db_name.data();
+    }
+
+    const char* rocksdb_tablefiledeletioninfo_file_path(
+        const rocksdb_tablefiledeletioninfo_t* info,
+        size_t* len) {
+        *len = info->rep.file_path.size();
+        return info->rep.file_path.data();
+    }
+
+    int rocksdb_tablefiledeletioninfo_job_id(const rocksdb_tablefiledeletioninfo_t* info) {
+        return info->rep.job_id;
+    }
+
+    void rocksdb_tablefiledeletioninfo_status(
+        const rocksdb_tablefiledeletioninfo_t* info,
+        char** errptr) {
+        SaveError(errptr, info->rep.status);
+    }
+
+    rocksdb_logger_t* rocksdb_logger_create_callback_logger(
+        int log_level,
+        void* state,
+        void (*destructor)(void*),
+        void (*log)(void*, int level, const char* msg, size_t len)) {
+        rocksdb_logger_t* logger = new rocksdb_logger_t;
+        logger->rep = std::make_shared<rocksdb_callback_logger_t>(
+            static_cast<InfoLogLevel>(log_level), state, destructor, log);
+        return logger;
+    }
+
+    void rocksdb_logger_destroy(rocksdb_logger_t* logger) {
+        delete logger;
+    }
+
+    rocksdb_ratelimiter_t* rocksdb_ratelimiter_create_auto_tuned(
+        int64_t rate_bytes_per_sec,
+        int64_t refill_period_us,
+        int32_t fairness) {
+        rocksdb_ratelimiter_t* limiter = new rocksdb_ratelimiter_t;
+        limiter->rep.reset(rocksdb::NewGenericRateLimiter(
+            rate_bytes_per_sec, refill_period_us, fairness,
+            RateLimiter::Mode::kWritesOnly, true));
+        return limiter;
+    }
+
+    void rocksdb_ratelimiter_set_bytes_per_second(
+        rocksdb_ratelimiter_t* limiter,
+        int64_t bytes_per_second) {
+        limiter->rep->SetBytesPerSecond(bytes_per_second);
+    }
+
+    int64_t rocksdb_ratelimiter_get_bytes_per_second(rocksdb_ratelimiter_t* limiter) {
+        return limiter->rep->GetBytesPerSecond();
+    }
+
+    int64_t rocksdb_ratelimiter_get_total_bytes_through(rocksdb_ratelimiter_t* limiter) {
+        return limiter->rep->GetTotalBytesThrough();
+    }
+
+    void rocksdb_backup_engine_options_set_backup_ratelimiter(
+        rocksdb_backup_engine_options_t* options,
+        rocksdb_ratelimiter_t* limiter) {
+        options->rep.backup_rate_limiter = limiter->rep;
+    }
+
+    void rocksdb_backup_engine_options_set_restore_ratelimiter(
+        rocksdb_backup_engine_options_t* options,
+        rocksdb_ratelimiter_t* limiter) {
+        options->rep.restore_rate_limiter = limiter->rep;
+    }
+
+    rocksdb_write_buffer_manager_t* rocksdb_write_buffer_manager_create(
+        size_t buffer_size,
+        unsigned char allow_stall) {
+        rocksdb_write_buffer_manager_t* wbm = new rocksdb_write_buffer_manager_t;
+        wbm->rep = std::make_shared<WriteBufferManager>(buffer_size, nullptr, allow_stall);
+        return wbm;
+    }
+
+    rocksdb_write_buffer_manager_t* rocksdb_write_buffer_manager_create_with_cache(
+        size_t buffer_size,
+        rocksdb_cache_t* cache,
+        unsigned char allow_stall) {
+        rocksdb_write_buffer_manager_t* wbm = new rocksdb_write_buffer_manager_t;
+        wbm->rep = std::make_shared<WriteBufferManager>(buffer_size, cache->rep, allow_stall);
+        return wbm;
+    }
+
+    void rocksdb_write_buffer_manager_destroy(rocksdb_write_buffer_manager_t* wbm) {
+        delete wbm;
+    }
+
+    unsigned char rocksdb_write_buffer_manager_enabled(rocksdb_write_buffer_manager_t* wbm) {
+        return wbm->rep->enabled();
+    }
+
+    unsigned char rocksdb_write_buffer_manager_cost_to_cache(
+        rocksdb_write_buffer_manager_t* wbm) {
+        return wbm->rep->cost_to_cache();
+    }
+
+    size_t rocksdb_write_buffer_manager_memory_usage(rocksdb_write_buffer_manager_t* wbm) {
+        return wbm->rep->memory_usage();
+    }
+
+    size_t rocksdb_write_buffer_manager_mutable_memtable_memory_usage(
+        rocksdb_write_buffer_manager_t* wbm) {
+        return wbm->rep->mutable_memtable_memory_usage();
+    }
+
+    size_t rocksdb_write_buffer_manager_dummy_entries_in_cache_usage(
+        rocksdb_write_buffer_manager_t* wbm) {
+        return wbm->rep->dummy_entries_in_cache_usage();
+    }
+
+    size_t rocksdb_write_buffer_manager_buffer_size(rocksdb_write_buffer_manager_t* wbm) {
+        return wbm->rep->buffer_size();
+    }
+
+    void rocksdb_write_buffer_manager_set_buffer_size(
+        rocksdb_write_buffer_manager_t* wbm,
+        size_t new_size) {
+        wbm->rep->SetBufferSize(new_size);
+    }
+
+    void rocksdb_options_set_write_buffer_manager(
+        rocksdb_options_t* opt,
+        rocksdb_write_buffer_manager_t* wbm) {
+        opt->rep.write_buffer_manager = wbm->rep;
+    }
+
+    rocksdb_sst_file_manager_t* rocksdb_sst_file_manager_create(
+        rocksdb_env_t* env,
+        char** errptr) {
+        Status status;
+        std::shared_ptr<SstFileManager> rep(rocksdb::NewSstFileManager(
+            env->rep, nullptr, "", 0, true, &status));
+        if (SaveError(errptr, status)) {
+            return nullptr;
+        }
+        rocksdb_sst_file_manager_t* sfm = new rocksdb_sst_file_manager_t;
+        sfm->rep = rep;
+        return sfm;
+    }
+
+    void rocksdb_sst_file_manager_destroy(rocksdb_sst_file_manager_t* sfm) {
+        delete sfm;
+    }
+
+    void rocksdb_sst_file_manager_set_max_allowed_space_usage(
+        rocksdb_sst_file_manager_t* sfm,
+        uint64_t max_allowed_space) {
+        sfm->rep->SetMaxAllowedSpaceUsage(max_allowed_space);
+    }
+
+    void rocksdb_sst_file_manager_set_compaction_buffer_size(
+        rocksdb_sst_file_manager_t* sfm,
+        uint64_t compaction_buffer_size) {
+        sfm->rep->SetCompactionBufferSize(compaction_buffer_size);
+    }
+
+    unsigned char rocksdb_sst_file_manager_is_max_allowed_space_reached(
+        rocksdb_sst_file_manager_t* sfm) {
+        return sfm->rep->IsMaxAllowedSpaceReached();
+    }
+
+    unsigned char rocksdb_sst_file_manager_is_max_allowed_space_reached_including_compactions(
+        rocksdb_sst_file_manager_t* sfm) {
+        return sfm->rep->IsMaxAllowedSpaceReachedIncludingCompactions();
+    }
+
+    uint64_t rocksdb_sst_file_manager_get_total_size(rocksdb_sst_file_manager_t* sfm) {
+        return sfm->rep->GetTotalSize();
+    }
+
+    int64_t rocksdb_sst_file_manager_get_delete_rate_bytes_per_second(
+        rocksdb_sst_file_manager_t* sfm) {
+        return sfm->rep->GetDeleteRateBytesPerSecond();
+    }
+
+    void rocksdb_sst_file_manager_set_delete_rate_bytes_per_second(
+        rocksdb_sst_file_manager_t* sfm,
+        int64_t delete_rate) {
+        sfm->rep->SetDeleteRateBytesPerSecond(delete_rate);
+    }
+
+    double rocksdb_sst_file_manager_get_max_trash_db_ratio(rocksdb_sst_file_manager_t* sfm) {
+        return sfm->rep->GetMaxTrashDBRatio();
+    }
+
+    void rocksdb_sst_file_manager_set_max_trash_db_ratio(
+        rocksdb_sst_file_manager_t* sfm,
+        double ratio) {
+        sfm->rep->SetMaxTrashDBRatio(ratio);
+    }
+
+    uint64_t rocksdb_sst_file_manager_get_total_trash_size(rocksdb_sst_file_manager_t* sfm) {
+        return sfm->rep->GetTotalTrashSize();
+    }
+
+    void rocksdb_options_set_sst_file_manager(
+        rocksdb_options_t* opt,
+        rocksdb_sst_file_manager_t* sfm) {
+        opt->rep.sst_file_manager = sfm->rep;
+    }
+
+    rocksdb_export_import_files_metadata_t* rocksdb_checkpoint_export_column_family(
+        rocksdb_checkpoint_t* checkpoint,
+        rocksdb_column_family_handle_t* column_family,
+        const char* export_dir,
+        char** errptr) {
+        ExportImportFilesMetaData* rep = nullptr;
+        if (SaveError(errptr, checkpoint->rep->ExportColumnFamily(
+                                  column_family->rep, std::string(export_dir), &rep))) {
+            return nullptr;
+        }
+        rocksdb_export_import_files_metadata_t* metadata =
+            new rocksdb_export_import_files_metadata_t;
+        metadata->rep = std::move(*rep);
+        delete rep;
+        return metadata;
+    }
+
+    rocksdb_export_import_files_metadata_t* rocksdb_export_import_files_metadata_create(
+        const char* db_comparator_name) {
+        rocksdb_export_import_files_metadata_t* metadata =
+            new rocksdb_export_import_files_metadata_t;
+        metadata->rep.db_comparator_name = db_comparator_name;
+        return metadata;
+    }
+
+    void rocksdb_export_import_files_metadata_destroy(
+        rocksdb_export_import_files_metadata_t* metadata) {
+        delete metadata;
+    }
+
+    const char* rocksdb_export_import_files_metadata_db_comparator_name(
+        const rocksdb_export_import_files_metadata_t* metadata,
+        size_t* len) {
+        *len = metadata->rep.db_comparator_name.size();
+        return metadata->rep.db_comparator_name.data();
+    }
+
+    size_t rocksdb_export_import_files_metadata_file_count(
+        const rocksdb_export_import_files_metadata_t* metadata) {
+        return metadata->rep.files.size();
+    }
+
+    const char* rocksdb_export_import_files_metadata_file_name(
+        const rocksdb_export_import_files_metadata_t* metadata,
+        size_t index,
+        size_t* len) {
+        const LiveFileMetaData& file = metadata->rep.files[index];
+        *len = file.name.size();
+        return file.name.data();
+    }
+
+    const char* rocksdb_export_import_files_metadata_file_db_path(
+        const rocksdb_export_import_files_metadata_t* metadata,
+        size_t index,
+        size_t* len) {
+        const LiveFileMetaData& file = metadata->rep.files[index];
+        *len = file.db_path.size();
+        return file.db_path.data();
+    }
+
+    int rocksdb_export_import_files_metadata_file_level(
+        const rocksdb_export_import_files_metadata_t* metadata,
+        size_t index) {
+        return metadata->rep.files[index].level;
+    }
+
+    uint64_t rocksdb_export_import_files_metadata_file_size(
+        const rocksdb_export_import_files_metadata_t* metadata,
+        size_t index) {
+        return metadata->rep.files[index].size;
+    }
+
+    const char* rocksdb_export_import_files_metadata_file_smallest_key(
+        const rocksdb_export_import_files_metadata_t* metadata,
+        size_t index,
+        size_t* len) {
+        const LiveFileMetaData& file = metadata->rep.files[index];
+        *len = file.smallestkey.size();
+        return file.smallestkey.data();
+    }
+
+    const char* rocksdb_export_import_files_metadata_file_largest_key(
+        const rocksdb_export_import_files_metadata_t* metadata,
+        size_t index,
+        size_t* len) {
+        const LiveFileMetaData& file = metadata->rep.files[index];
+        *len = file.largestkey.size();
+        return file.largestkey.data();
+    }
+
+    uint64_t rocksdb_export_import_files_metadata_file_smallest_seqno(
+        const rocksdb_export_import_files_metadata_t* metadata,
+        size_t index) {
+        return metadata->rep.files[index].smallest_seqno;
+    }
+
+    uint64_t rocksdb_export_import_files_metadata_file_largest_seqno(
+        const rocksdb_export_import_files_metadata_t* metadata,
+        size_t index) {
+        return metadata->rep.files[index].largest_seqno;
+    }
+
+    void rocksdb_export_import_files_metadata_add_file(
+        rocksdb_export_import_files_metadata_t* metadata,
+        const char* name,
+        const char* db_path,
+        int level,
+        uint64_t size,
+        const char* smallest_key,
+        size_t smallest_key_len,
+        const char* largest_key,
+        size_t largest_key_len,
+        uint64_t smallest_seqno,
+        uint64_t largest_seqno) {
+        LiveFileMetaData file;
+        file.name = name;
+        file.db_path = db_path;
+        file.level = level;
+        file.size = size;
+        file.smallestkey = std::string(smallest_key, smallest_key_len);
+        file.largestkey = std::string(largest_key, largest_key_len);
+        file.smallest_seqno = smallest_seqno;
+        file.largest_seqno = largest_seqno;
+        metadata->rep.files.push_back(std::move(file));
+    }
+
+    rocksdb_column_family_handle_t* rocksdb_create_column_family_with_import(
+        rocksdb_t* db,
+        const rocksdb_options_t* column_family_options,
+        const char* column_family_name,
+        unsigned char move_files,
+        const rocksdb_export_import_files_metadata_t* metadata,
+        char** errptr) {
+        ImportColumnFamilyOptions import_options;
+        import_options.move_files = move_files;
+        ColumnFamilyHandle* handle = nullptr;
+        if (SaveError(errptr, db->rep->CreateColumnFamilyWithImport(
+                                  ColumnFamilyOptions(column_family_options->rep),
+                                  std::string(column_family_name), import_options,
+                                  metadata->rep, &handle))) {
+            return nullptr;
+        }
+        rocksdb_column_family_handle_t* result = new rocksdb_column_family_handle_t;
+        result->rep = handle;
+        return result;
+    }
+
+    rocksdb_column_family_handle_t* rocksdb_transactiondb_create_column_family_with_import(
+        rocksdb_transactiondb_t* txn_db,
+        const rocksdb_options_t* column_family_options,
+        const char* column_family_name,
+        unsigned char move_files,
+        const rocksdb_export_import_files_metadata_t* metadata,
+        char** errptr) {
+        ImportColumnFamilyOptions import_options;
+        import_options.move_files = move_files;
+        ColumnFamilyHandle* handle = nullptr;
+        if (SaveError(errptr, txn_db->rep->CreateColumnFamilyWithImport(
+                                  ColumnFamilyOptions(column_family_options->rep),
+                                  std::string(column_family_name), import_options,
+                                  metadata->rep, &handle))) {
+            return nullptr;
+        }
+        // Unlike CreateColumnFamily, the transaction DB does not register
+        // imported column families with its lock manager.
+        auto pessimistic_txn_db = dynamic_cast<PessimisticTransactionDB*>(txn_db->rep);
+        if (pessimistic_txn_db != nullptr) {
+            pessimistic_txn_db->AddColumnFamily(handle);
+        }
+        rocksdb_column_family_handle_t* result = new rocksdb_column_family_handle_t;
+        result->rep = handle;
+        return result;
+    }
+
+    const char* rocksdb_livefiles_directory(
+        const rocksdb_livefiles_t* lf,
+        int index) {
+        return lf->rep[index].directory.c_str();
+    }
+
+    uint64_t rocksdb_livefiles_smallest_seqno(
+        const rocksdb_livefiles_t* lf,
+        int index) {
+        return lf->rep[index].smallest_seqno;
+    }
+
+    uint64_t rocksdb_livefiles_largest_seqno(
+        const rocksdb_livefiles_t* lf,
+        int index) {
+        return lf->rep[index].largest_seqno;
+    }
+
+    char* rocksdb_sst_file_metadata_get_directory(
+        rocksdb_sst_file_metadata_t* file_meta) {
+        return strdup(file_meta->rep->directory.c_str());
+    }
+
+    uint64_t rocksdb_sst_file_metadata_get_smallest_seqno(
+        rocksdb_sst_file_metadata_t* file_meta) {
+        return file_meta->rep->smallest_seqno;
+    }
+
+    uint64_t rocksdb_sst_file_metadata_get_largest_seqno(
+        rocksdb_sst_file_metadata_t* file_meta) {
+        return file_meta->rep->largest_seqno;
+    }
+
+    uint64_t rocksdb_sst_file_metadata_get_num_entries(
+        rocksdb_sst_file_metadata_t* file_meta) {
+        return file_meta->rep->num_entries;
+    }
+
+    uint64_t rocksdb_sst_file_metadata_get_num_deletions(
+        rocksdb_sst_file_metadata_t* file_meta) {
+        return file_meta->rep->num_deletions;
+    }
+
+    rocksdb_sstfilereader_t* rocksdb_sstfilereader_create(
+        const rocksdb_options_t* options) {
+        rocksdb_sstfilereader_t* reader = new rocksdb_sstfilereader_t;
+        reader->rep = new SstFileReader(options->rep);
+        reader->options = options->rep;
+        return reader;
+    }
+
+    void rocksdb_sstfilereader_open(
+        rocksdb_sstfilereader_t* reader,
+        const char* file_path,
+        char** errptr) {
+        reader->file_path = file_path;
+        SaveError(errptr, reader->rep->Open(reader->file_path));
+    }
+
+    void rocksdb_sstfilereader_get_key_range(
+        rocksdb_sstfilereader_t* reader,
+        unsigned char* has_range,
+        char** smallest_key,
+        size_t* smallest_key_len,
+        char** largest_key,
+        size_t* largest_key_len,
+        char** errptr) {
+        *has_range = 0;
+
+        // Open a table reader the way SstFileReader::Open does.
+        const Options& options = reader->options;
+        EnvOptions soptions(options);
+        rocksdb::ImmutableOptions ioptions(options);
+        ColumnFamilyOptions cf_options(options);
+        rocksdb::MutableCFOptions moptions(cf_options);
+        rocksdb::FileOptions fopts(soptions);
+        const auto& fs = options.env->GetFileSystem();
+        uint64_t file_size = 0;
+        std::unique_ptr<rocksdb::FSRandomAccessFile> file;
+        std::unique_ptr<rocksdb::TableReader> table_reader;
+        Status s = fs->GetFileSize(reader->file_path, fopts.io_options, &file_size, nullptr);
+        if (s.ok()) {
+            s = fs->NewRandomAccessFile(reader->file_path, fopts, &file, nullptr);
+        }
+        if (s.ok()) {
+            std::unique_ptr<rocksdb::RandomAccessFileReader> file_reader(
+                new rocksdb::RandomAccessFileReader(std::move(file), reader->file_path));
+            rocksdb::TableReaderOptions t_opt(
+                ioptions, moptions.prefix_extractor, soptions, ioptions.internal_comparator);
+            t_opt.largest_seqno = rocksdb::kMaxSequenceNumber;
+            s = options.table_factory->NewTableReader(
+                t_opt, std::move(file_reader), file_size, &table_reader);
+        }
+        if (SaveError(errptr, s)) {
+            return;
+        }
+
+        // Bound the point entries, deletions included, and the range
+        // tombstones, as ingestion does.
+        ReadOptions ro;
+        ro.fill_cache = false;
+        std::unique_ptr<rocksdb::InternalIterator> iter(table_reader->NewIterator(
+            ro, moptions.prefix_extractor.get(), nullptr, false,
+            rocksdb::TableReaderCaller::kSSTFileReader));
+        const rocksdb::Comparator* ucmp = options.comparator;
+        std::string smallest;
+        std::string largest;
+        bool bounds_set = false;
+        iter->SeekToFirst();
+        if (iter->Valid()) {
+            smallest = rocksdb::ExtractUserKey(iter->key()).ToString();
+            iter->SeekToLast();
+            if (iter->Valid()) {
+                largest = rocksdb::ExtractUserKey(iter->key()).ToString();
+                bounds_set = true;
+            }
+        }
+        if (SaveError(errptr, iter->status())) {
+            return;
+        }
+
+        std::unique_ptr<rocksdb::FragmentedRangeTombstoneIterator> range_del_iter(
+            table_reader->NewRangeTombstoneIterator(ro));
+        if (range_del_iter != nullptr) {
+            for (range_del_iter->SeekToFirst(); range_del_iter->Valid();
+                 range_del_iter->Next()) {
+                Slice start = rocksdb::ExtractUserKey(range_del_iter->key());
+                Slice end = range_del_iter->value();
+                if (!bounds_set || ucmp->Compare(start, smallest) < 0) {
+                    smallest = start.ToString();
+                }
+                if (!bounds_set || ucmp->Compare(end, largest) > 0) {
+                    largest = end.ToString();
+                }
+                bounds_set = true;
+            }
+            if (SaveError(errptr, range_del_iter->status())) {
+                return;
+            }
+        }
+
+        if (bounds_set) {
+            *has_range = 1;
+            *smallest_key = CopyString(smallest);
+            *smallest_key_len = smallest.size();
+            *largest_key = CopyString(largest);
+            *largest_key_len = largest.size();
+        }
+    }
+
+    rocksdb_iterator_t* rocksdb_sstfilereader_new_iterator(
+        rocksdb_sstfilereader_t* reader,
+        const rocksdb_readoptions_t* options) {
+        rocksdb_iterator_t* result = new rocksdb_iterator_t;
+        result->rep = reader->rep->NewIterator(options->rep);
+        return result;
+    }
+
+    void rocksdb_sstfilereader_verify_checksum(
+        rocksdb_sstfilereader_t* reader,
+        char** errptr) {
+        SaveError(errptr, reader->rep->VerifyChecksum());
+    }
+
+    rocksdb_table_properties_t* rocksdb_sstfilereader_get_table_properties(
+        rocksdb_sstfilereader_t* reader) {
+        rocksdb_table_properties_t* props = new rocksdb_table_properties_t;
+        props->rep = reader->rep->GetTableProperties();
+        return props;
+    }
+
+    void rocksdb_sstfilereader_destroy(
+        rocksdb_sstfilereader_t* reader) {
+        delete reader->rep;
+        delete reader;
+    }
+
+    void rocksdb_table_properties_destroy(
+        rocksdb_table_properties_t* props) {
+        delete props;
+    }
+
+    uint64_t rocksdb_table_properties_get_num_entries(
+        const rocksdb_table_properties_t* props) {
+        return props->rep->num_entries;
+    }
+
+    uint64_t rocksdb_table_properties_get_num_deletions(
+        const rocksdb_table_properties_t* props) {
+        return props->rep->num_deletions;
+    }
+
+    uint64_t rocksdb_table_properties_get_raw_key_size(
+        const rocksdb_table_properties_t* props) {
+        return props->rep->raw_key_size;
+    }
+
+    uint64_t rocksdb_table_properties_get_raw_value_size(
+        const rocksdb_table_properties_t* props) {
+        return props->rep->raw_value_size;
+    }
+
+    uint64_t rocksdb_table_properties_get_data_size(
+        const rocksdb_table_properties_t* props) {
+        return props->rep->data_size;
+    }
+
+    uint64_t rocksdb_table_properties_get_creation_time(
+        const rocksdb_table_properties_t* props) {
+        return props->rep->creation_time;
+    }
+
+    uint64_t rocksdb_table_properties_get_file_creation_time(
+        const rocksdb_table_properties_t* props) {
+        return props->rep->file_creation_time;
+    }
+
+    const char* rocksdb_table_properties_get_comparator_name(
+        const rocksdb_table_properties_t* props,
+        size_t* len) {
+        *len = props->rep->comparator_name.size();
+        return props->rep->comparator_name.data();
+    }
+
+    rocksdb_sstfilewriter_t* rocksdb_sstfilewriter_create_cf(
+        rocksdb_t* db,
+        rocksdb_column_family_handle_t* column_family,
+        const rocksdb_envoptions_t* env) {
+        rocksdb_sstfilewriter_t* writer = new rocksdb_sstfilewriter_t;
+        writer->rep = new SstFileWriter(
+            env->rep, db->rep->GetOptions(column_family->rep), column_family->rep);
+        return writer;
+    }
+
+    void rocksdb_sstfilewriter_delete_range(
+        rocksdb_sstfilewriter_t* writer,
+        const char* begin_key,
+        size_t begin_keylen,
+        const char* end_key,
+        size_t end_keylen,
+        char** errptr) {
+        SaveError(errptr, writer->rep->DeleteRange(Slice(begin_key, begin_keylen),
+                                                   Slice(end_key, end_keylen)));
+    }
+
+    rocksdb_external_sst_file_info_t* rocksdb_sstfilewriter_finish_with_info(
+        rocksdb_sstfilewriter_t* writer,
+        char** errptr) {
+        ExternalSstFileInfo file_info;
+        if (SaveError(errptr, writer->rep->Finish(&file_info))) {
+            return nullptr;
+        }
+        rocksdb_external_sst_file_info_t* info = new rocksdb_external_sst_file_info_t;
+        info->rep = std::move(file_info);
+        return info;
+    }
+
+    void rocksdb_external_sst_file_info_destroy(
+        rocksdb_external_sst_file_info_t* info) {
+        delete info;
+    }
+
+    const char* rocksdb_external_sst_file_info_file_path(
+        const rocksdb_external_sst_file_info_t* info,
+        size_t* len) {
+        *len = info->rep.file_path.size();
+        return info->rep.file_path.data();
+    }
+
+    const char* rocksdb_external_sst_file_info_smallest_key(
+        const rocksdb_external_sst_file_info_t* info,
+        size_t* len) {
+        *len = info->rep.smallest_key.size();
+        return info->rep.smallest_key.data();
+    }
+
+    const char* rocksdb_external_sst_file_info_largest_key(
+        const rocksdb_external_sst_file_info_t* info,
+        size_t* len) {
+        *len = info->rep.largest_key.size();
+        return info->rep.largest_key.data();
+    }
+
+    const char* rocksdb_external_sst_file_info_smallest_range_del_key(
+        const rocksdb_external_sst_file_info_t* info,
+        size_t* len) {
+        *len = info->rep.smallest_range_del_key.size();
+        return info->rep.smallest_range_del_key.data();
+    }
+
+    const char* rocksdb_external_sst_file_info_largest_range_del_key(
+        const rocksdb_external_sst_file_info_t* info,
+        size_t* len) {
+        *len = info->rep.largest_range_del_key.size();
+        return info->rep.largest_range_del_key.data();
+    }
+
+    uint64_t rocksdb_external_sst_file_info_sequence_number(
+        const rocksdb_external_sst_file_info_t* info) {
+        return info->rep.sequence_number;
+    }
+
+    uint64_t rocksdb_external_sst_file_info_file_size(
+        const rocksdb_external_sst_file_info_t* info) {
+        return info->rep.file_size;
+    }
+
+    uint64_t rocksdb_external_sst_file_info_num_entries(
+        const rocksdb_external_sst_file_info_t* info) {
+        return info->rep.num_entries;
+    }
+
+    uint64_t rocksdb_external_sst_file_info_num_range_del_entries(
+        const rocksdb_external_sst_file_info_t* info) {
+        return info->rep.num_range_del_entries;
+    }
+
+    rocksdb_pinnableslice_t* rocksdb_pinnableslice_create_from(const char* v, size_t vlen) {
+        rocksdb_pinnableslice_t* result = new rocksdb_pinnableslice_t;
+        result->rep.PinSelf(Slice(v, vlen));
+        return result;
+    }
 }
@@ -8,6 +8,17 @@ extern "C" {
 
 typedef struct rocksdb_column_family_descriptor_t   rocksdb_column_family_descriptor_t;
 typedef struct rocksdb_column_family_descriptors_t  rocksdb_column_family_descriptors_t;
+typedef struct rocksdb_eventlistener_t              rocksdb_eventlistener_t;
+typedef struct rocksdb_flushjobinfo_t               rocksdb_flushjobinfo_t;
+typedef struct rocksdb_compactionjobinfo_t          rocksdb_compactionjobinfo_t;
+typedef struct rocksdb_writestallinfo_t             rocksdb_writestallinfo_t;
+typedef struct rocksdb_tablefiledeletioninfo_t      rocksdb_tablefiledeletioninfo_t;
+typedef struct rocksdb_write_buffer_manager_t       rocksdb_write_buffer_manager_t;
+typedef struct rocksdb_sst_file_manager_t           rocksdb_sst_file_manager_t;
+typedef struct rocksdb_export_import_files_metadata_t rocksdb_export_import_files_metadata_t;
+typedef struct rocksdb_sstfilereader_t              rocksdb_sstfilereader_t;
+typedef struct rocksdb_table_properties_t           rocksdb_table_properties_t;
+typedef struct rocksdb_external_sst_file_info_t     rocksdb_external_sst_file_info_t;
 typedef struct {
     rocksdb_options_t* db_opts;
     rocksdb_column_family_descriptors_t* cf_descs;
@@ -42,6 +53,922 @@ extern ROCKSDB_LIBRARY_API
         rocksdb_cache_t* cache,
         char** errptr);
 
+extern ROCKSDB_LIBRARY_API
+    void rocksdb_transactiondb_batched_multi_get_cf(
+        rocksdb_transactiondb_t* txn_db,
+        const rocksdb_readoptions_t* options,
+        rocksdb_column_family_handle_t* column_family,
+        size_t num_keys,
+        const char* const* keys_list,
+        const size_t* keys_list_sizes,
+        rocksdb_pinnableslice_t** values,
+        char** errs,
+        const bool sorted_input);
+
+extern ROCKSDB_LIBRARY_API
+    void rocksdb_transaction_batched_multi_get_cf(
+        rocksdb_transaction_t* txn,
+        const rocksdb_readoptions_t* options,
+        rocksdb_column_family_handle_t* column_family,
+        size_t num_keys,
+        const char* const* keys_list,
+        const size_t* keys_list_sizes,
+        rocksdb_pinnableslice_t** values,
+        char** errs,
+        const bool sorted_input);
+
+extern ROCKSDB_LIBRARY_API
+    void rocksdb_transaction_multi_get_for_update(
+        rocksdb_transaction_t* txn,
+        const rocksdb_readoptions_t* options,
+        size_t num_keys,
+        const char* const* keys_list,
+        const size_t* keys_list_sizes,
+        char** values_list,
+        size_t* values_list_sizes,
+        char** errs);
+
+extern ROCKSDB_LIBRARY_API
+    void rocksdb_transaction_multi_get_for_update_cf(
+        rocksdb_transaction_t* txn,
+        const rocksdb_readoptions_t* options,
+        const rocksdb_column_family_handle_t* const* column_families,
+        size_t num_keys,
+        const char* const* keys_list,
+        const size_t* keys_list_sizes,
+        char** values_list,
+        size_t* values_list_sizes,
+        char** errs);
+
+extern ROCKSDB_LIBRARY_API
+    void rocksdb_options_set_allow_2pc(rocksdb_options_t* opt, unsigned char v);
+
+enum {
+    rocksdb_txndb_write_committed = 0,
+    rocksdb_txndb_write_prepared = 1,
+    rocksdb_txndb_write_unprepared = 2
+};
+
+extern ROCKSDB_LIBRARY_API
+    void rocksdb_transactiondb_options_set_write_policy(
+        rocksdb_transactiondb_options_t* opt,
+        int write_policy);
+
+extern ROCKSDB_LIBRARY_API
+    void rocksdb_transactiondb_options_set_skip_concurrency_control(
+        rocksdb_transactiondb_options_t* opt,
+        unsigned char v);
+
+extern ROCKSDB_LIBRARY_API
+    rocksdb_wal_iterator_t* rocksdb_transactiondb_get_updates_since(
+        rocksdb_transactiondb_t* txn_db,
+        uint64_t seq_number,
+        const rocksdb_wal_readoptions_t* options,
+        char** errptr);
+
+extern ROCKSDB_LIBRARY_API
+    uint64_t rocksdb_transactiondb_get_latest_sequence_number(rocksdb_transactiondb_t* txn_db);
+
+extern ROCKSDB_LIBRARY_API
+    void rocksdb_writebatch_iterate_cf(
+        rocksdb_writebatch_t* b,
+        void* state,
+        void (*put_cf)(void*, uint32_t cf_id, const char* k, size_t klen, const char* v, size_t vlen),
+        void (*deleted_cf)(void*, uint32_t cf_id, const char* k, size_t klen),
+        void (*single_deleted_cf)(void*, uint32_t cf_id, const char* k, size_t klen),
+        void (*merge_cf)(void*, uint32_t cf_id, const char* k, size_t klen, const char* v, size_t vlen),
+        void (*delete_range_cf)(void*, uint32_t cf_id, const char* begin_key, size_t begin_klen, const char* end_key, size_t end_klen),
+        char** errptr);
+
+extern ROCKSDB_LIBRARY_API
+    uint32_t rocksdb_column_family_handle_id(rocksdb_column_family_handle_t* handle);
+
+extern ROCKSDB_LIBRARY_API
+    void rocksdb_transactiondb_write_writebatch_wi(
+        rocksdb_transactiondb_t* txn_db,
+        const rocksdb_writeoptions_t* options,
+        rocksdb_writebatch_wi_t* wbwi,
+        char** errptr);
+
+extern ROCKSDB_LIBRARY_API
+    void rocksdb_backup_engine_options_set_share_files_with_checksum(
+        rocksdb_backup_engine_options_t* options,
+        unsigned char val);
+
+extern ROCKSDB_LIBRARY_API
+    rocksdb_backup_engine_t* rocksdb_backup_engine_open_with_dir(
+        const rocksdb_backup_engine_options_t* options,
+        const char* backup_dir,
+        char** errptr);
+
+extern ROCKSDB_LIBRARY_API
+    void rocksdb_backup_engine_create_new_backup_with_metadata(
+        rocksdb_backup_engine_t* be,
+        rocksdb_t* db,
+        const char* app_metadata,
+        size_t app_metadata_len,
+        unsigned char flush_before_backup,
+        char** errptr);
+
+extern ROCKSDB_LIBRARY_API
+    void rocksdb_backup_engine_delete_backup(
+        rocksdb_backup_engine_t* be,
+        uint32_t backup_id,
+        char** errptr);
+
+extern ROCKSDB_LIBRARY_API
+    const rocksdb_backup_engine_info_t* rocksdb_backup_engine_get_backup_info_with_file_details(
+        rocksdb_backup_engine_t* be);
+
+extern ROCKSDB_LIBRARY_API
+    const char* rocksdb_backup_engine_info_app_metadata(
+        const rocksdb_backup_engine_info_t* info,
+        int index,
+        size_t* len);
+
+extern ROCKSDB_LIBRARY_API
+    size_t rocksdb_backup_engine_info_file_details_count(
+        const rocksdb_backup_engine_info_t* info,
+        int index);
+
+extern ROCKSDB_LIBRARY_API
+    const char* rocksdb_backup_engine_info_file_details_name(
+        const rocksdb_backup_engine_info_t* info,
+        int index,
+        size_t file_index,
+        size_t* len);
+
+extern ROCKSDB_LIBRARY_API
+    uint64_t rocksdb_backup_engine_info_file_details_size(
+        const rocksdb_backup_engine_info_t* info,
+        int index,
+        size_t file_index);
+
+extern ROCKSDB_LIBRARY_API
+    rocksdb_t* rocksdb_transactiondb_get_base_db(rocksdb_transactiondb_t* txn_db);
+
+extern ROCKSDB_LIBRARY_API
+    void rocksdb_transactiondb_close_base_db(rocksdb_t* base_db);
+
+extern ROCKSDB_LIBRARY_API
+    void rocksdb_compact_range_opt_with_status(
+        rocksdb_t* db,
+        rocksdb_compactoptions_t* opt,
+        const char* start_key,
+        size_t start_key_len,
+        const char* limit_key,
+        size_t limit_key_len,
+        char** errptr);
+
+extern ROCKSDB_LIBRARY_API
+    void rocksdb_compact_range_cf_opt_with_status(
+        rocksdb_t* db,
+        rocksdb_column_family_handle_t* column_family,
+        rocksdb_compactoptions_t* opt,
+        const char* start_key,
+        size_t start_key_len,
+        const char* limit_key,
+        size_t limit_key_len,
+        char** errptr);
+
+/* `canceled` must point to a single byte flag which is only accessed
+   atomically, it may be null to clear it. */
+extern ROCKSDB_LIBRARY_API
+    void rocksdb_compactoptions_set_canceled(
+        rocksdb_compactoptions_t* opt,
+        unsigned char* canceled);
+
+/* Decisions returned by the filter function of
+   rocksdb_compactionfilter_create_v2 */
+enum {
+    rocksdb_compactionfilter_keep = 0,
+    rocksdb_compactionfilter_remove = 1,
+    rocksdb_compactionfilter_change_value = 2,
+    rocksdb_compactionfilter_remove_and_skip_until = 3
+};
+
+/* Types of the entries passed to the filter function of
+   rocksdb_compactionfilter_create_v2 */
+enum {
+    rocksdb_compactionfilter_value = 0,
+    rocksdb_compactionfilter_merge_operand = 1,
+    rocksdb_compactionfilter_blob_index = 2
+};
+
+/* Like rocksdb_compactionfilter_create, but the filter function is given the
+   type of the entry and returns one of the decisions above. The new value
+   (for change_value) or the key to skip until (for remove_and_skip_until) is
+   stored with rocksdb_compactionfilter_assign into `new_value` or
+   `skip_until`. */
+extern ROCKSDB_LIBRARY_API
+    rocksdb_compactionfilter_t* rocksdb_compactionfilter_create_v2(
+        void* state,
+        void (*destructor)(void*),
+        int (*filter)(
+            void*,
+            int level,
+            const char* key, size_t key_length,
+            int value_type,
+            const char* existing_value, size_t value_length,
+            void* new_value,
+            void* skip_until),
+        const char* (*name)(void*));
+
+extern ROCKSDB_LIBRARY_API
+    void rocksdb_compactionfilter_assign(void* output, const char* data, size_t len);
+
+enum {
+    rocksdb_table_file_creation_reason_flush = 0,
+    rocksdb_table_file_creation_reason_compaction = 1,
+    rocksdb_table_file_creation_reason_recovery = 2,
+    rocksdb_table_file_creation_reason_misc = 3
+};
+
+extern ROCKSDB_LIBRARY_API
+    uint32_t rocksdb_compactionfiltercontext_column_family_id(
+        rocksdb_compactionfiltercontext_t* context);
+
+extern ROCKSDB_LIBRARY_API
+    int rocksdb_compactionfiltercontext_reason(rocksdb_compactionfiltercontext_t* context);
+
+/* Only valid for contexts passed by a factory from
+   rocksdb_compactionfilterfactory_create_v2. Returns null unless the factory
+   was named with
+   rocksdb_options_set_compaction_filter_factory_column_family_name. */
+extern ROCKSDB_LIBRARY_API
+    const char* rocksdb_compactionfiltercontext_column_family_name(
+        rocksdb_compactionfiltercontext_t* context,
+        size_t* len);
+
+extern ROCKSDB_LIBRARY_API
+    rocksdb_compactionfilterfactory_t* rocksdb_compactionfilterfactory_create_v2(
+        void* state,
+        void (*destructor)(void*),
+        rocksdb_compactionfilter_t* (*create_compaction_filter)(
+            void*, rocksdb_compactionfiltercontext_t* context),
+        const char* (*name)(void*));
+
+/* Makes the compaction filter factory of `opt`, if it was created by
+   rocksdb_compactionfilterfactory_create_v2, report `name` as the column
+   family name of its contexts. Other options sharing the factory are not
+   affected. */
+extern ROCKSDB_LIBRARY_API
+    void rocksdb_options_set_compaction_filter_factory_column_family_name(
+        rocksdb_options_t* opt,
+        const char* name);
+
+extern ROCKSDB_LIBRARY_API
+    void rocksdb_options_set_reverse_bytewise_comparator(rocksdb_options_t* opt);
+
+/* Enables statistics unless they already are, keeping their level. */
+extern ROCKSDB_LIBRARY_API
+    void rocksdb_options_ensure_statistics(rocksdb_options_t* opt);
+
+/* The statistics functions below do nothing, or return zeros, unless
+   statistics were enabled with rocksdb_options_enable_statistics,
+   rocksdb_options_ensure_statistics or rocksdb_options_set_statistics_level,
+   which enables them. */
+enum {
+    rocksdb_statistics_level_disable_all = 0,
+    rocksdb_statistics_level_except_tickers = rocksdb_statistics_level_disable_all,
+    rocksdb_statistics_level_except_histogram_or_timers = 1,
+    rocksdb_statistics_level_except_timers = 2,
+    rocksdb_statistics_level_except_detailed_timers = 3,
+    rocksdb_statistics_level_except_time_for_mutex = 4,
+    rocksdb_statistics_level_all = 5
+};
+
+extern ROCKSDB_LIBRARY_API
+    void rocksdb_options_set_statistics_level(rocksdb_options_t* opt, int level);
+
+extern ROCKSDB_LIBRARY_API
+    int rocksdb_options_get_statistics_level(rocksdb_options_t* opt);
+
+/* The name of a ticker or histogram in RocksDB's TickersNameMap or
+   HistogramsNameMap, NULL if there is no such statistic. */
+extern ROCKSDB_LIBRARY_API
+    const char* rocksdb_statistics_ticker_name(uint32_t ticker_type);
+
+extern ROCKSDB_LIBRARY_API
+    const char* rocksdb_statistics_histogram_name(uint32_t histogram_type);
+
+extern ROCKSDB_LIBRARY_API
+    uint64_t rocksdb_options_statistics_get_ticker_count(
+        rocksdb_options_t* opt,
+        uint32_t ticker_type);
+
+extern ROCKSDB_LIBRARY_API
+    void rocksdb_options_statistics_get_histogram_data(
+        rocksdb_options_t* opt,
+        uint32_t histogram_type,
+        double* median,
+        double* p95,
+        double* p99,
+        double* average,
+        double* std_dev,
+        double* max,
+        double* min,
+        uint64_t* count,
+        uint64_t* sum);
+
+extern ROCKSDB_LIBRARY_API
+    void rocksdb_options_statistics_reset(rocksdb_options_t* opt, char** errptr);
+
+/* Event listener */
+
+extern ROCKSDB_LIBRARY_API
+    rocksdb_eventlistener_t* rocksdb_eventlistener_create(
+        void* state,
+        void (*destructor)(void*),
+        void (*on_flush_begin)(void*, const rocksdb_flushjobinfo_t*),
+        void (*on_flush_completed)(void*, const rocksdb_flushjobinfo_t*),
+        void (*on_compaction_begin)(void*, const rocksdb_compactionjobinfo_t*),
+        void (*on_compaction_completed)(void*, const rocksdb_compactionjobinfo_t*),
+        void (*on_stall_conditions_changed)(void*, const rocksdb_writestallinfo_t*),
+        void (*on_background_error)(void*, int reason, const char* error),
+        void (*on_table_file_deleted)(void*, const rocksdb_tablefiledeletioninfo_t*));
+
+/* Takes ownership of the listener. */
+extern ROCKSDB_LIBRARY_API
+    void rocksdb_options_add_eventlistener(
+        rocksdb_options_t* opt,
+        rocksdb_eventlistener_t* listener);
+
+enum {
+    rocksdb_background_error_reason_flush = 0,
+    rocksdb_background_error_reason_compaction = 1,
+    rocksdb_background_error_reason_write_callback = 2,
+    rocksdb_background_error_reason_memtable = 3,
+    rocksdb_background_error_reason_manifest_write = 4,
+    rocksdb_background_error_reason_flush_no_wal = 5,
+    rocksdb_background_error_reason_manifest_write_no_wal = 6
+};
+
+enum {
+    rocksdb_write_stall_condition_normal = 0,
+    rocksdb_write_stall_condition_delayed = 1,
+    rocksdb_write_stall_condition_stopped = 2
+};
+
+extern ROCKSDB_LIBRARY_API
+    uint32_t rocksdb_flushjobinfo_cf_id(const rocksdb_flushjobinfo_t* info);
+
+extern ROCKSDB_LIBRARY_API
+    const char* rocksdb_flushjobinfo_cf_name(const rocksdb_flushjobinfo_t* info, size_t* len);
+
+extern ROCKSDB_LIBRARY_API
+    const char* rocksdb_flushjobinfo_file_path(const rocksdb_flushjobinfo_t* info, size_t* len);
+
+extern ROCKSDB_LIBRARY_API
+    int rocksdb_flushjobinfo_job_id(const rocksdb_flushjobinfo_t* info);
+
+extern ROCKSDB_LIBRARY_API
+    unsigned char rocksdb_flushjobinfo_triggered_writes_slowdown(
+        const rocksdb_flushjobinfo_t* info);
+
+extern ROCKSDB_LIBRARY_API
+    unsigned char rocksdb_flushjobinfo_triggered_writes_stop(const rocksdb_flushjobinfo_t* info);
+
+extern ROCKSDB_LIBRARY_API
+    uint64_t rocksdb_flushjobinfo_smallest_seqno(const rocksdb_flushjobinfo_t* info);
+
+extern ROCKSDB_LIBRARY_API
+    uint64_t rocksdb_flushjobinfo_largest_seqno(const rocksdb_flushjobinfo_t* info);
+
+/* One of the rocksdb::FlushReason values */
+extern ROCKSDB_LIBRARY_API
+    int rocksdb_flushjobinfo_flush_reason(const rocksdb_flushjobinfo_t* info);
+
+extern ROCKSDB_LIBRARY_API
+    uint64_t rocksdb_flushjobinfo_num_entries(const rocksdb_flushjobinfo_t* info);
+
+extern ROCKSDB_LIBRARY_API
+    uint64_t rocksdb_flushjobinfo_data_size(const rocksdb_flushjobinfo_t* info);
+
+extern ROCKSDB_LIBRARY_API
+    uint32_t rocksdb_compactionjobinfo_cf_id(const rocksdb_compactionjobinfo_t* info);
+
+extern ROCKSDB_LIBRARY_API
+    const char* rocksdb_compactionjobinfo_cf_name(
+        const rocksdb_compactionjobinfo_t* info,
+        size_t* len);
+
+extern ROCKSDB_LIBRARY_API
+    void rocksdb_compactionjobinfo_status(
+        const rocksdb_compactionjobinfo_t* info,
+        char** errptr);
+
+extern ROCKSDB_LIBRARY_API
+    int rocksdb_compactionjobinfo_job_id(const rocksdb_compactionjobinfo_t* info);
+
+extern ROCKSDB_LIBRARY_API
+    int rocksdb_compactionjobinfo_base_input_level(const rocksdb_compactionjobinfo_t* info);
+
+extern ROCKSDB_LIBRARY_API
+    int rocksdb_compactionjobinfo_output_level(const rocksdb_compactionjobinfo_t* info);
+
+extern ROCKSDB_LIBRARY_API
+    size_t rocksdb_compactionjobinfo_input_files_count(const rocksdb_compactionjobinfo_t* info);
+
+extern ROCKSDB_LIBRARY_API
+    const char* rocksdb_compactionjobinfo_input_file_at(
+        const rocksdb_compactionjobinfo_t* info,
+        size_t pos,
+        size_t* len);
+
+extern ROCKSDB_LIBRARY_API
+    size_t rocksdb_compactionjobinfo_output_files_count(const rocksdb_compactionjobinfo_t* info);
+
+extern ROCKSDB_LIBRARY_API
+    const char* rocksdb_compactionjobinfo_output_file_at(
+        const rocksdb_compactionjobinfo_t* info,
+        size_t pos,
+        size_t* len);
+
+/* One of the rocksdb::CompactionReason values */
+extern ROCKSDB_LIBRARY_API
+    int rocksdb_compactionjobinfo_compaction_reason(const rocksdb_compactionjobinfo_t* info);
+
+extern ROCKSDB_LIBRARY_API
+    uint64_t rocksdb_compactionjobinfo_elapsed_micros(const rocksdb_compactionjobinfo_t* info);
+
+extern ROCKSDB_LIBRARY_API
+    uint64_t rocksdb_compactionjobinfo_num_corrupt_keys(const rocksdb_compactionjobinfo_t* info);
+
+extern ROCKSDB_LIBRARY_API
+    uint64_t rocksdb_compactionjobinfo_input_records(const rocksdb_compactionjobinfo_t* info);
+
+extern ROCKSDB_LIBRARY_API
+    uint64_t rocksdb_compactionjobinfo_output_records(const rocksdb_compactionjobinfo_t* info);
+
+extern ROCKSDB_LIBRARY_API
+    uint64_t rocksdb_compactionjobinfo_total_input_bytes(const rocksdb_compactionjobinfo_t* info);
+
+extern ROCKSDB_LIBRARY_API
+    uint64_t rocksdb_compactionjobinfo_total_output_bytes(
+        const rocksdb_compactionjobinfo_t* info);
+
+extern ROCKSDB_LIBRARY_API
+    const char* rocksdb_writestallinfo_cf_name(const rocksdb_writestallinfo_t* info, size_t* len);
+
+extern ROCKSDB_LIBRARY_API
+    int rocksdb_writestallinfo_cur(const rocksdb_writestallinfo_t* info);
+
+extern ROCKSDB_LIBRARY_API
+    int rocksdb_writestallinfo_prev(const rocksdb_writestallinfo_t* info);
+
+extern ROCKSDB_LIBRARY_API
+    const char* rocksdb_tablefiledeletioninfo_db_name(
+        const rocksdb_tablefiledeletioninfo_t* info,
+        size_t* len);
+
+extern ROCKSDB_LIBRARY_API
+    const char* rocksdb_tablefiledeletioninfo_file_path(
+        const rocksdb_tablefiledeletioninfo_t* info,
+        size_t* len);
+
+extern ROCKSDB_LIBRARY_API
+    int rocksdb_tablefiledeletioninfo_job_id(const rocksdb_tablefiledeletioninfo_t* info);
+
+extern ROCKSDB_LIBRARY_API
+    void rocksdb_tablefiledeletioninfo_status(
+        const rocksdb_tablefiledeletioninfo_t* info,
+        char** errptr);
+
+/* Logger */
+
+extern ROCKSDB_LIBRARY_API
+    rocksdb_logger_t* rocksdb_logger_create_callback_logger(
+        int log_level,
+        void* state,
+        void (*destructor)(void*),
+        void (*log)(void*, int level, const char* msg, size_t len));
+
+extern ROCKSDB_LIBRARY_API
+    void rocksdb_logger_destroy(rocksdb_logger_t* logger);
+
+/* Rate limiter */
+
+extern ROCKSDB_LIBRARY_API
+    rocksdb_ratelimiter_t* rocksdb_ratelimiter_create_auto_tuned(
+        int64_t rate_bytes_per_sec,
+        int64_t refill_period_us,
+        int32_t fairness);
+
+extern ROCKSDB_LIBRARY_API
+    void rocksdb_ratelimiter_set_bytes_per_second(
+        rocksdb_ratelimiter_t* limiter,
+        int64_t bytes_per_second);
+
+extern ROCKSDB_LIBRARY_API
+    int64_t rocksdb_ratelimiter_get_bytes_per_second(rocksdb_ratelimiter_t* limiter);
+
+extern ROCKSDB_LIBRARY_API
+    int64_t rocksdb_ratelimiter_get_total_bytes_through(rocksdb_ratelimiter_t* limiter);
+
+extern ROCKSDB_LIBRARY_API
+    void rocksdb_backup_engine_options_set_backup_ratelimiter(
+        rocksdb_backup_engine_options_t* options,
+        rocksdb_ratelimiter_t* limiter);
+
+extern ROCKSDB_LIBRARY_API
+    void rocksdb_backup_engine_options_set_restore_ratelimiter(
+        rocksdb_backup_engine_options_t* options,
+        rocksdb_ratelimiter_t* limiter);
+
+/* Write buffer manager */
+
+extern ROCKSDB_LIBRARY_API
+    rocksdb_write_buffer_manager_t* rocksdb_write_buffer_manager_create(
+        size_t buffer_size,
+        unsigned char allow_stall);
+
+extern ROCKSDB_LIBRARY_API
+    rocksdb_write_buffer_manager_t* rocksdb_write_buffer_manager_create_with_cache(
+        size_t buffer_size,
+        rocksdb_cache_t* cache,
+        unsigned char allow_stall);
+
+extern ROCKSDB_LIBRARY_API
+    void rocksdb_write_buffer_manager_destroy(rocksdb_write_buffer_manager_t* wbm);
+
+extern ROCKSDB_LIBRARY_API
+    unsigned char rocksdb_write_buffer_manager_enabled(rocksdb_write_buffer_manager_t* wbm);
+
+extern ROCKSDB_LIBRARY_API
+    unsigned char rocksdb_write_buffer_manager_cost_to_cache(
+        rocksdb_write_buffer_manager_t* wbm);
+
+extern ROCKSDB_LIBRARY_API
+    size_t rocksdb_write_buffer_manager_memory_usage(rocksdb_write_buffer_manager_t* wbm);
+
+extern ROCKSDB_LIBRARY_API
+    size_t rocksdb_write_buffer_manager_mutable_memtable_memory_usage(
+        rocksdb_write_buffer_manager_t* wbm);
+
+extern ROCKSDB_LIBRARY_API
+    size_t rocksdb_write_buffer_manager_dummy_entries_in_cache_usage(
+        rocksdb_write_buffer_manager_t* wbm);
+
+extern ROCKSDB_LIBRARY_API
+    size_t rocksdb_write_buffer_manager_buffer_size(rocksdb_write_buffer_manager_t* wbm);
+
+extern ROCKSDB_LIBRARY_API
+    void rocksdb_write_buffer_manager_set_buffer_size(
+        rocksdb_write_buffer_manager_t* wbm,
+        size_t new_size);
+
+extern ROCKSDB_LIBRARY_API
+    void rocksdb_options_set_write_buffer_manager(
+        rocksdb_options_t* opt,
+        rocksdb_write_buffer_manager_t* wbm);
+
+/* SST file manager */
+
+extern ROCKSDB_LIBRARY_API
+    rocksdb_sst_file_manager_t* rocksdb_sst_file_manager_create(
+        rocksdb_env_t* env,
+        char** errptr);
+
+extern ROCKSDB_LIBRARY_API
+    void rocksdb_sst_file_manager_destroy(rocksdb_sst_file_manager_t* sfm);
+
+extern ROCKSDB_LIBRARY_API
+    void rocksdb_sst_file_manager_set_max_allowed_space_usage(
+        rocksdb_sst_file_manager_t* sfm,
+        uint64_t max_allowed_space);
+
+extern ROCKSDB_LIBRARY_API
+    void rocksdb_sst_file_manager_set_compaction_buffer_size(
+        rocksdb_sst_file_manager_t* sfm,
+        uint64_t compaction_buffer_size);
+
+extern ROCKSDB_LIBRARY_API
+    unsigned char rocksdb_sst_file_manager_is_max_allowed_space_reached(
+        rocksdb_sst_file_manager_t* sfm);
+
+extern ROCKSDB_LIBRARY_API
+    unsigned char rocksdb_sst_file_manager_is_max_allowed_space_reached_including_compactions(
+        rocksdb_sst_file_manager_t* sfm);
+
+extern ROCKSDB_LIBRARY_API
+    uint64_t rocksdb_sst_file_manager_get_total_size(rocksdb_sst_file_manager_t* sfm);
+
+extern ROCKSDB_LIBRARY_API
+    int64_t rocksdb_sst_file_manager_get_delete_rate_bytes_per_second(
+        rocksdb_sst_file_manager_t* sfm);
+
+extern ROCKSDB_LIBRARY_API
+    void rocksdb_sst_file_manager_set_delete_rate_bytes_per_second(
+        rocksdb_sst_file_manager_t* sfm,
+        int64_t delete_rate);
+
+extern ROCKSDB_LIBRARY_API
+    double rocksdb_sst_file_manager_get_max_trash_db_ratio(rocksdb_sst_file_manager_t* sfm);
+
+extern ROCKSDB_LIBRARY_API
+    void rocksdb_sst_file_manager_set_max_trash_db_ratio(
+        rocksdb_sst_file_manager_t* sfm,
+        double ratio);
+
+extern ROCKSDB_LIBRARY_API
+    uint64_t rocksdb_sst_file_manager_get_total_trash_size(rocksdb_sst_file_manager_t* sfm);
+
+extern ROCKSDB_LIBRARY_API
+    void rocksdb_options_set_sst_file_manager(
+        rocksdb_options_t* opt,
+        rocksdb_sst_file_manager_t* sfm);
+
+/* Column family export and import */
+
+extern ROCKSDB_LIBRARY_API
+    rocksdb_export_import_files_metadata_t* rocksdb_checkpoint_export_column_family(
+        rocksdb_checkpoint_t* checkpoint,
+        rocksdb_column_family_handle_t* column_family,
+        const char* export_dir,
+        char** errptr);
+
+extern ROCKSDB_LIBRARY_API
+    rocksdb_export_import_files_metadata_t* rocksdb_export_import_files_metadata_create(
+        const char* db_comparator_name);
+
+extern ROCKSDB_LIBRARY_API
+    void rocksdb_export_import_files_metadata_destroy(
+        rocksdb_export_import_files_metadata_t* metadata);
+
+extern ROCKSDB_LIBRARY_API
+    const char* rocksdb_export_import_files_metadata_db_comparator_name(
+        const rocksdb_export_import_files_metadata_t* metadata,
+        size_t* len);
+
+extern ROCKSDB_LIBRARY_API
+    size_t rocksdb_export_import_files_metadata_file_count(
+        const rocksdb_export_import_files_metadata_t* metadata);
+
+extern ROCKSDB_LIBRARY_API
+    const char* rocksdb_export_import_files_metadata_file_name(
+        const rocksdb_export_import_files_metadata_t* metadata,
+        size_t index,
+        size_t* len);
+
+extern ROCKSDB_LIBRARY_API
+    const char* rocksdb_export_import_files_metadata_file_db_path(
+        const rocksdb_export_import_files_metadata_t* metadata,
+        size_t index,
+        size_t* len);
+
+extern ROCKSDB_LIBRARY_API
+    int rocksdb_export_import_files_metadata_file_level(
+        const rocksdb_export_import_files_metadata_t* metadata,
+        size_t index);
+
+extern ROCKSDB_LIBRARY_API
+    uint64_t rocksdb_export_import_files_metadata_file_size(
+        const rocksdb_export_import_files_metadata_t* metadata,
+        size_t index);
+
+extern ROCKSDB_LIBRARY_API
+    const char* rocksdb_export_import_files_metadata_file_smallest_key(
+        const rocksdb_export_import_files_metadata_t* metadata,
+        size_t index,
+        size_t* len);
+
+extern ROCKSDB_LIBRARY_API
+    const char* rocksdb_export_import_files_metadata_file_largest_key(
+        const rocksdb_export_import_files_metadata_t* metadata,
+        size_t index,
+        size_t* len);
+
+extern ROCKSDB_LIBRARY_API
+    uint64_t rocksdb_export_import_files_metadata_file_smallest_seqno(
+        const rocksdb_export_import_files_metadata_t* metadata,
+        size_t index);
+
+extern ROCKSDB_LIBRARY_API
+    uint64_t rocksdb_export_import_files_metadata_file_largest_seqno(
+        const rocksdb_export_import_files_metadata_t* metadata,
+        size_t index);
+
+extern ROCKSDB_LIBRARY_API
+    void rocksdb_export_import_files_metadata_add_file(
+        rocksdb_export_import_files_metadata_t* metadata,
+        const char* name,
+        const char* db_path,
+        int level,
+        uint64_t size,
+        const char* smallest_key,
+        size_t smallest_key_len,
+        const char* largest_key,
+        size_t largest_key_len,
+        uint64_t smallest_seqno,
+        uint64_t largest_seqno);
+
+extern ROCKSDB_LIBRARY_API
+    rocksdb_column_family_handle_t* rocksdb_create_column_family_with_import(
+        rocksdb_t* db,
+        const rocksdb_options_t* column_family_options,
+        const char* column_family_name,
+        unsigned char move_files,
+        const rocksdb_export_import_files_metadata_t* metadata,
+        char** errptr);
+
+extern ROCKSDB_LIBRARY_API
+    rocksdb_column_family_handle_t* rocksdb_transactiondb_create_column_family_with_import(
+        rocksdb_transactiondb_t* txn_db,
+        const rocksdb_options_t* column_family_options,
+        const char* column_family_name,
+        unsigned char move_files,
+        const rocksdb_export_import_files_metadata_t* metadata,
+        char** errptr);
+
+/* Live files and column family metadata */
+
+extern ROCKSDB_LIBRARY_API
+    const char* rocksdb_livefiles_directory(
+        const rocksdb_livefiles_t* lf,
+        int index);
+
+extern ROCKSDB_LIBRARY_API
+    uint64_t rocksdb_livefiles_smallest_seqno(
+        const rocksdb_livefiles_t* lf,
+        int index);
+
+extern ROCKSDB_LIBRARY_API
+    uint64_t rocksdb_livefiles_largest_seqno(
+        const rocksdb_livefiles_t* lf,
+        int index);
+
+extern ROCKSDB_LIBRARY_API
+    char* rocksdb_sst_file_metadata_get_directory(
+        rocksdb_sst_file_metadata_t* file_meta);
+
+extern ROCKSDB_LIBRARY_API
+    uint64_t rocksdb_sst_file_metadata_get_smallest_seqno(
+        rocksdb_sst_file_metadata_t* file_meta);
+
+extern ROCKSDB_LIBRARY_API
+    uint64_t rocksdb_sst_file_metadata_get_largest_seqno(
+        rocksdb_sst_file_metadata_t* file_meta);
+
+extern ROCKSDB_LIBRARY_API
+    uint64_t rocksdb_sst_file_metadata_get_num_entries(
+        rocksdb_sst_file_metadata_t* file_meta);
+
+extern ROCKSDB_LIBRARY_API
+    uint64_t rocksdb_sst_file_metadata_get_num_deletions(
+        rocksdb_sst_file_metadata_t* file_meta);
+
+/* SstFileReader */
+
+extern ROCKSDB_LIBRARY_API
+    rocksdb_sstfilereader_t* rocksdb_sstfilereader_create(
+        const rocksdb_options_t* options);
+
+extern ROCKSDB_LIBRARY_API
+    void rocksdb_sstfilereader_open(
+        rocksdb_sstfilereader_t* reader,
+        const char* file_path,
+        char** errptr);
+
+/* Sets *has_range and the smallest and largest user keys of the file,
+   counting deletions and range tombstones as ingestion does; a range
+   tombstone contributes its exclusive end key. *has_range is 0 for a file
+   without entries. The keys are allocated with malloc. */
+extern ROCKSDB_LIBRARY_API
+    void rocksdb_sstfilereader_get_key_range(
+        rocksdb_sstfilereader_t* reader,
+        unsigned char* has_range,
+        char** smallest_key,
+        size_t* smallest_key_len,
+        char** largest_key,
+        size_t* largest_key_len,
+        char** errptr);
+
+extern ROCKSDB_LIBRARY_API
+    rocksdb_iterator_t* rocksdb_sstfilereader_new_iterator(
+        rocksdb_sstfilereader_t* reader,
+        const rocksdb_readoptions_t* options);
+
+extern ROCKSDB_LIBRARY_API
+    void rocksdb_sstfilereader_verify_checksum(
+        rocksdb_sstfilereader_t* reader,
+        char** errptr);
+
+extern ROCKSDB_LIBRARY_API
+    rocksdb_table_properties_t* rocksdb_sstfilereader_get_table_properties(
+        rocksdb_sstfilereader_t* reader);
+
+extern ROCKSDB_LIBRARY_API
+    void rocksdb_sstfilereader_destroy(
+        rocksdb_sstfilereader_t* reader);
+
+extern ROCKSDB_LIBRARY_API
+    void rocksdb_table_properties_destroy(
+        rocksdb_table_properties_t* props);
+
+extern ROCKSDB_LIBRARY_API
+    uint64_t rocksdb_table_properties_get_num_entries(
+        const rocksdb_table_properties_t* props);
+
+extern ROCKSDB_LIBRARY_API
+    uint64_t rocksdb_table_properties_get_num_deletions(
+        const rocksdb_table_properties_t* props);
+
+extern ROCKSDB_LIBRARY_API
+    uint64_t rocksdb_table_properties_get_raw_key_size(
+        const rocksdb_table_properties_t* props);
+
+extern ROCKSDB_LIBRARY_API
+    uint64_t rocksdb_table_properties_get_raw_value_size(
+        const rocksdb_table_properties_t* props);
+
+extern ROCKSDB_LIBRARY_API
+    uint64_t rocksdb_table_properties_get_data_size(
+        const rocksdb_table_properties_t* props);
+
+extern ROCKSDB_LIBRARY_API
+    uint64_t rocksdb_table_properties_get_creation_time(
+        const rocksdb_table_properties_t* props);
+
+extern ROCKSDB_LIBRARY_API
+    uint64_t rocksdb_table_properties_get_file_creation_time(
+        const rocksdb_table_properties_t* props);
+
+extern ROCKSDB_LIBRARY_API
+    const char* rocksdb_table_properties_get_comparator_name(
+        const rocksdb_table_properties_t* props,
+        size_t* len);
+
+/* SstFileWriter */
+
+extern ROCKSDB_LIBRARY_API
+    rocksdb_sstfilewriter_t* rocksdb_sstfilewriter_create_cf(
+        rocksdb_t* db,
+        rocksdb_column_family_handle_t* column_family,
+        const rocksdb_envoptions_t* env);
+
+extern ROCKSDB_LIBRARY_API
+    void rocksdb_sstfilewriter_delete_range(
+        rocksdb_sstfilewriter_t* writer,
+        const char* begin_key,
+        size_t begin_keylen,
+        const char* end_key,
+        size_t end_keylen,
+        char** errptr);
+
+extern ROCKSDB_LIBRARY_API
+    rocksdb_external_sst_file_info_t* rocksdb_sstfilewriter_finish_with_info(
+        rocksdb_sstfilewriter_t* writer,
+        char** errptr);
+
+extern ROCKSDB_LIBRARY_API
+    void rocksdb_external_sst_file_info_destroy(
+        rocksdb_external_sst_file_info_t* info);
+
+extern ROCKSDB_LIBRARY_API
+    const char* rocksdb_external_sst_file_info_file_path(
+        const rocksdb_external_sst_file_info_t* info,
+        size_t* len);
+
+extern ROCKSDB_LIBRARY_API
+    const char* rocksdb_external_sst_file_info_smallest_key(
+        const rocksdb_external_sst_file_info_t* info,
+        size_t* len);
+
+extern ROCKSDB_LIBRARY_API
+    const char* rocksdb_external_sst_file_info_largest_key(
+        const rocksdb_external_sst_file_info_t* info,
+        size_t* len);
+
+extern ROCKSDB_LIBRARY_API
+    const char* rocksdb_external_sst_file_info_smallest_range_del_key(
+        const rocksdb_external_sst_file_info_t* info,
+        size_t* len);
+
+extern ROCKSDB_LIBRARY_API
+    const char* rocksdb_external_sst_file_info_largest_range_del_key(
+        const rocksdb_external_sst_file_info_t* info,
+        size_t* len);
+
+extern ROCKSDB_LIBRARY_API
+    uint64_t rocksdb_external_sst_file_info_sequence_number(
+        const rocksdb_external_sst_file_info_t* info);
+
+extern ROCKSDB_LIBRARY_API
+    uint64_t rocksdb_external_sst_file_info_file_size(
+        const rocksdb_external_sst_file_info_t* info);
+
+extern ROCKSDB_LIBRARY_API
+    uint64_t rocksdb_external_sst_file_info_num_entries(
+        const rocksdb_external_sst_file_info_t* info);
+
+extern ROCKSDB_LIBRARY_API
+    uint64_t rocksdb_external_sst_file_info_num_range_del_entries(
+        const rocksdb_external_sst_file_info_t* info);
+
+extern ROCKSDB_LIBRARY_API
+    rocksdb_pinnableslice_t* rocksdb_pinnableslice_create_from(const char* v, size_t vlen);
+
 #ifdef __cplusplus
 }  /* end extern "C" */
 #endif
@@ -13,16 +13,18 @@
 // limitations under the License.
 //
 
-use crate::{ffi, Error, DB};
+use crate::{ffi, handle::Handle, Error, RateLimiter, TransactionDB};
 
-use libc::{c_int, c_uchar};
+use libc::{c_char, c_int, c_uchar, size_t};
 use std::ffi::CString;
 use std::path::Path;
+use std::slice;
 
 /// Represents information of a backup including timestamp of the backup
 /// and the size (please note that sum of all backups' sizes is bigger than the actual
 /// size of the backup directory because some data is shared by multiple backups).
 /// Backups are identified by their always-increasing IDs.
+#[non_exhaustive]
 pub struct BackupEngineInfo {
     /// Timestamp of the backup
     pub timestamp: i64,
@@ -32,6 +34,44 @@ pub struct BackupEngineInfo {
     pub size: u64,
     /// Number of files related to the backup
     pub num_files: u32,
+    /// Application metadata stored with the backup, see
+    /// `BackupEngine::create_new_backup_with_metadata`
+    pub app_metadata: String,
+    /// Files of the backup, some of which might be shared with other backups
+    pub files: Vec<BackupFileInfo>,
+}
+
+/// A file which belongs to a backup.
+#[non_exhaustive]
+pub struct BackupFileInfo {
+    /// Path of the file relative to the backup directory
+    pub relative_filename: String,
+    /// Size of the file in bytes
+    pub size: u64,
+}
+
+/// A database which can be backed up by a `BackupEngine`, or lend the options
+/// of a column family to `SstFileWriter::create_cf`.
+///
+/// Implemented by every database type wrapping a `rocksdb_t`, and by
+/// `TransactionDB` through its base database.
+pub trait BackupSource {
+    fn base_db_ptr(&self) -> *mut ffi::rocksdb_t;
+}
+
+impl<T> BackupSource for T
+where
+    T: Handle<ffi::rocksdb_t>,
+{
+    fn base_db_ptr(&self) -> *mut ffi::rocksdb_t {
+        self.handle()
+    }
+}
+
+impl BackupSource for TransactionDB {
+    fn base_db_ptr(&self) -> *mut ffi::rocksdb_t {
+        TransactionDB::base_db_ptr(self)
+    }
 }
 
 pub struct BackupEngine {
@@ -39,7 +79,7 @@ pub struct BackupEngine {
 }
 
 pub struct BackupEngineOptions {
-    inner: *mut ffi::rocksdb_options_t,
+    inner: *mut ffi::rocksdb_backup_engine_options_t,
 }
 
 pub struct RestoreOptions {
@@ -62,7 +102,10 @@ impl BackupEngine {
 
         let be: *mut ffi::rocksdb_backup_engine_t;
         unsafe {
-            be = ffi_try!(ffi::rocksdb_backup_engine_open(opts.inner, cpath.as_ptr()));
+            be = ffi_try!(ffi::rocksdb_backup_engine_open_with_dir(
+                opts.inner,
+                cpath.as_ptr()
+            ));
         }
 
         if be.is_null() {
@@ -76,7 +119,7 @@ impl BackupEngine {
     ///
     /// Note: no flush before backup is performed. User might want to
     /// use `create_new_backup_flush` instead.
-    pub fn create_new_backup(&mut self, db: &DB) -> Result<(), Error> {
+    pub fn create_new_backup<D: BackupSource>(&mut self, db: &D) -> Result<(), Error> {
         self.create_new_backup_flush(db, false)
     }
 
@@ -84,21 +127,55 @@ impl BackupEngine {
     ///
     /// Set flush_before_backup=true to avoid losing unflushed key/value
     /// pairs from the memtable.
-    pub fn create_new_backup_flush(
+    pub fn create_new_backup_flush<D: BackupSource>(
         &mut self,
-        db: &DB,
+        db: &D,
         flush_before_backup: bool,
     ) -> Result<(), Error> {
         unsafe {
             ffi_try!(ffi::rocksdb_backup_engine_create_new_backup_flush(
                 self.inner,
-                db.inner,
+                db.base_db_ptr(),
+                flush_before_backup as c_uchar,
+            ));
+            Ok(())
+        }
+    }
+
+    /// Captures the state of the database in the latest backup, storing
+    /// `app_metadata` with it.
+    ///
+    /// The metadata is reported by `get_backup_info`.
+    pub fn create_new_backup_with_metadata<D: BackupSource>(
+        &mut self,
+        db: &D,
+        app_metadata: &str,
+        flush_before_backup: bool,
+    ) -> Result<(), Error> {
+        unsafe {
+            ffi_try!(ffi::rocksdb_backup_engine_create_new_backup_with_metadata(
+                self.inner,
+                db.base_db_ptr(),
+                app_metadata.as_ptr() as *const c_char,
+                app_metadata.len() as size_t,
                 flush_before_backup as c_uchar,
             ));
             Ok(())
         }
     }
 
+    /// Deletes the backup with the given id.
+    ///
+    /// Files shared with other backups are kept.
+    pub fn delete_backup(&mut self, backup_id: u32) -> Result<(), Error> {
+        unsafe {
+            ffi_try!(ffi::rocksdb_backup_engine_delete_backup(
+                self.inner, backup_id,
+            ));
+            Ok(())
+        }
+    }
+
     pub fn purge_old_backups(&mut self, num_backups_to_keep: usize) -> Result<(), Error> {
         unsafe {
             ffi_try!(ffi::rocksdb_backup_engine_purge_old_backups(
@@ -239,17 +316,37 @@ impl BackupEngine {
     /// backups on the same directory
     pub fn get_backup_info(&self) -> Vec<BackupEngineInfo> {
         unsafe {
-            let i = ffi::rocksdb_backup_engine_get_backup_info(self.inner);
+            let i = ffi::rocksdb_backup_engine_get_backup_info_with_file_details(self.inner);
 
             let n = ffi::rocksdb_backup_engine_info_count(i);
 
             let mut info = Vec::with_capacity(n as usize);
             for index in 0..n {
+                let mut len: size_t = 0;
+                let app_metadata = ffi::rocksdb_backup_engine_info_app_metadata(i, index, &mut len);
+                let app_metadata = from_raw_string(app_metadata, len);
+
+                let num_details = ffi::rocksdb_backup_engine_info_file_details_count(i, index);
+                let mut files = Vec::with_capacity(num_details);
+                for file_index in 0..num_details {
+                    let name = ffi::rocksdb_backup_engine_info_file_details_name(
+                        i, index, file_index, &mut len,
+                    );
+                    files.push(BackupFileInfo {
+                        relative_filename: from_raw_string(name, len),
+                        size: ffi::rocksdb_backup_engine_info_file_details_size(
+                            i, index, file_index,
+                        ),
+                    });
+                }
+
                 info.push(BackupEngineInfo {
                     timestamp: ffi::rocksdb_backup_engine_info_timestamp(i, index),
                     backup_id: ffi::rocksdb_backup_engine_info_backup_id(i, index),
                     size: ffi::rocksdb_backup_engine_info_size(i, index),
                     num_files: ffi::rocksdb_backup_engine_info_number_files(i, index),
+                    app_metadata,
+                    files,
                 });
             }
 
@@ -261,8 +358,125 @@ impl BackupEngine {
     }
 }
 
+unsafe fn from_raw_string(ptr: *const c_char, len: size_t) -> String {
+    String::from_utf8_lossy(slice::from_raw_parts(ptr as *const u8, len)).into_owned()
+}
+
 impl BackupEngineOptions {
-    //
+    /// If true, backups share table files with each other: a table file
+    /// which is already in the backup directory is not copied again.
+    ///
+    /// Default: true
+    pub fn set_share_table_files(&mut self, share_table_files: bool) {
+        unsafe {
+            ffi::rocksdb_backup_engine_options_set_share_table_files(
+                self.inner,
+                share_table_files as c_uchar,
+            );
+        }
+    }
+
+    /// If true, shared table files are identified by their checksum and size
+    /// as well as their name, so that backups of different databases can
+    /// share a backup directory. Only takes effect if `share_table_files` is
+    /// set.
+    ///
+    /// Default: true
+    pub fn set_share_files_with_checksum(&mut self, share_files_with_checksum: bool) {
+        unsafe {
+            ffi::rocksdb_backup_engine_options_set_share_files_with_checksum(
+                self.inner,
+                share_files_with_checksum as c_uchar,
+            );
+        }
+    }
+
+    /// If true, the backup is synced to disk after every file write, which
+    /// guarantees a consistent backup after a machine crash.
+    ///
+    /// Default: true
+    pub fn set_sync(&mut self, sync: bool) {
+        unsafe {
+            ffi::rocksdb_backup_engine_options_set_sync(self.inner, sync as c_uchar);
+        }
+    }
+
+    /// If true, all existing backups are deleted when the backup engine is
+    /// opened.
+    ///
+    /// Default: false
+    pub fn set_destroy_old_data(&mut self, destroy_old_data: bool) {
+        unsafe {
+            ffi::rocksdb_backup_engine_options_set_destroy_old_data(
+                self.inner,
+                destroy_old_data as c_uchar,
+            );
+        }
+    }
+
+    /// If false, log files are not backed up. Use together with
+    /// `RestoreOptions::set_keep_log_files` to back up in-memory databases.
+    ///
+    /// Default: true
+    pub fn set_backup_log_files(&mut self, backup_log_files: bool) {
+        unsafe {
+            ffi::rocksdb_backup_engine_options_set_backup_log_files(
+                self.inner,
+                backup_log_files as c_uchar,
+            );
+        }
+    }
+
+    /// Maximum number of bytes written per second while creating a backup,
+    /// 0 means unlimited.
+    ///
+    /// Default: 0
+    pub fn set_backup_rate_limit(&mut self, limit: u64) {
+        unsafe {
+            ffi::rocksdb_backup_engine_options_set_backup_rate_limit(self.inner, limit);
+        }
+    }
+
+    /// Maximum number of bytes written per second while restoring a backup,
+    /// 0 means unlimited.
+    ///
+    /// Default: 0
+    pub fn set_restore_rate_limit(&mut self, limit: u64) {
+        unsafe {
+            ffi::rocksdb_backup_engine_options_set_restore_rate_limit(self.inner, limit);
+        }
+    }
+
+    /// Limits the rate at which a backup is written with `limiter`, which may
+    /// be shared with databases. Takes precedence over
+    /// [`set_backup_rate_limit`](#method.set_backup_rate_limit).
+    pub fn set_backup_rate_limiter(&mut self, limiter: &RateLimiter) {
+        unsafe {
+            ffi::rocksdb_backup_engine_options_set_backup_ratelimiter(self.inner, limiter.0.inner);
+        }
+    }
+
+    /// Limits the rate at which a backup is restored with `limiter`, which may
+    /// be shared with databases. Takes precedence over
+    /// [`set_restore_rate_limit`](#method.set_restore_rate_limit).
+    pub fn set_restore_rate_limiter(&mut self, limiter: &RateLimiter) {
+        unsafe {
+            ffi::rocksdb_backup_engine_options_set_restore_ratelimiter(self.inner, limiter.0.inner);
+        }
+    }
+
+    /// Number of threads used to copy files while creating or restoring a
+    /// backup.
+    ///
+    /// Default: 1
+    pub fn set_max_background_operations(&mut self, max_background_operations: i32) {
+        unsafe {
+            ffi::rocksdb_backup_engine_options_set_max_background_operations(
+                self.inner,
+                max_background_operations as c_int,
+            );
+        }
+    }
 }
 
 impl RestoreOptions {
@@ -276,7 +490,8 @@ impl RestoreOptions {
 impl Default for BackupEngineOptions {
     fn default() -> Self {
         unsafe {
-            let opts = ffi::rocksdb_options_create();
+            // The backup directory is passed to `BackupEngine::open`.
+            let opts = ffi::rocksdb_backup_engine_options_create(b"\0".as_ptr() as *const c_char);
             if opts.is_null() {
                 panic!("Could not create RocksDB backup options");
             }
@@ -308,7 +523,7 @@ impl Drop for BackupEngine {
 impl Drop for BackupEngineOptions {
     fn drop(&mut self) {
         unsafe {
-            ffi::rocksdb_options_destroy(self.inner);
+            ffi::rocksdb_backup_engine_options_destroy(self.inner);
         }
     }
 }
@@ -324,7 +539,7 @@ impl Drop for RestoreOptions {
 #[test]
 fn restore_from_latest() {
     use crate::ops::{Get, Open, Put};
-    use crate::TemporaryDBPath;
+    use crate::{TemporaryDBPath, DB};
 
     // create backup
     let path = TemporaryDBPath::new();
@@ -367,7 +582,7 @@ fn restore_from_latest() {
 #[test]
 fn restore_from_backup() {
     use crate::ops::{Get, Open, Put};
-    use crate::TemporaryDBPath;
+    use crate::{TemporaryDBPath, DB};
 
     // create backup
     let path = TemporaryDBPath::new();
@@ -408,3 +623,58 @@ fn restore_from_backup() {
         }
     }
 }
+
+#[test]
+fn backup_with_metadata_and_delete() {
+    use crate::ops::{Open, Put};
+    use crate::{TemporaryDBPath, DB};
+
+    let path = TemporaryDBPath::new();
+    let backup_path = TemporaryDBPath::new();
+    let db = DB::open_default(&path).unwrap();
+
+    let mut backup_opts = BackupEngineOptions::default();
+    backup_opts.set_share_table_files(true);
+    backup_opts.set_share_files_with_checksum(true);
+    backup_opts.set_sync(false);
+    backup_opts.set_destroy_old_data(true);
+    backup_opts.set_backup_log_files(true);
+    backup_opts.set_backup_rate_limit(0);
+    backup_opts.set_restore_rate_limit(0);
+    backup_opts.set_max_background_operations(2);
+    let mut backup_engine = BackupEngine::open(&backup_opts, &backup_path).unwrap();
+
+    db.put(b"k1", b"v1").unwrap();
+    backup_engine
+        .create_new_backup_with_metadata(&db, "first", true)
+        .unwrap();
+    db.put(b"k2", b"v2").unwrap();
+    backup_engine
+        .create_new_backup_with_metadata(&db, "second", true)
+        .unwrap();
+
+    let info = backup_engine.get_backup_info();
+    assert_eq!(info.len(), 2);
+    assert_eq!(info[0].app_metadata, "first");
+    assert_eq!(info[1].app_metadata, "second");
+    for i in &info {
+        assert_eq!(i.files.len(), i.num_files as usize);
+        assert!(i
+            .files
+            .iter()
+            .any(|f| f.relative_filename.ends_with(".sst")));
+        assert_eq!(i.files.iter().map(|f| f.size).sum::<u64>(), i.size);
+    }
+
+    backup_engine.delete_backup(info[0].backup_id).unwrap();
+    let remaining = backup_engine.get_backup_info();
+    assert_eq!(remaining.len(), 1);
+    assert_eq!(remaining[0].backup_id, info[1].backup_id);
+    assert!(backup_engine.verify_backup(info[0].backup_id).is_err());
+    assert!(backup_engine.verify_backup(info[1].backup_id).is_ok());
+    drop(backup_engine);
+
+    // destroy_old_data removes the existing backups on open
+    let backup_engine = BackupEngine::open(&backup_opts, &backup_path).unwrap();
+    assert!(backup_engine.get_backup_info().is_empty());
+}
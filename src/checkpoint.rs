@@ -18,10 +18,12 @@ use crate::ops::*;
 ///! Implementation of bindings to RocksDB Checkpoint[1] API
 ///
 /// [1]: https://github.com/facebook/rocksdb/wiki/Checkpoints
-use crate::{Error, DB};
+use crate::{ffi_util::to_cstring, handle::Handle, ColumnFamily, Error, DB};
+use libc::{c_char, size_t};
 use std::ffi::CString;
 use std::marker::PhantomData;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::slice;
 
 /// Undocumented parameter for `ffi::rocksdb_checkpoint_create` function. Zero by default.
 const LOG_SIZE_FOR_FLUSH: u64 = 0_u64;
@@ -64,6 +66,171 @@ impl<'db> Checkpoint<'db> {
             Ok(())
         }
     }
+
+    /// Exports all live SST files of the column family `cf` into `export_dir`.
+    ///
+    /// `export_dir` must not exist yet, it is created by RocksDB. The files are
+    /// hard-linked when possible and copied otherwise. The returned metadata can be
+    /// passed to `CreateCF::create_cf_with_import` to recreate the column family in
+    /// another database.
+    pub fn export_column_family<P: AsRef<Path>>(
+        &self,
+        cf: &ColumnFamily,
+        export_dir: P,
+    ) -> Result<ExportImportFilesMetaData, Error> {
+        let path = export_dir.as_ref();
+        let cpath = match CString::new(path.to_string_lossy().as_bytes()) {
+            Ok(c) => c,
+            Err(_) => {
+                return Err(Error::new(
+                    "Failed to convert path to CString when exporting column family".to_owned(),
+                ));
+            }
+        };
+
+        unsafe {
+            let inner = ffi_try!(ffi::rocksdb_checkpoint_export_column_family(
+                self.inner,
+                cf.handle(),
+                cpath.as_ptr(),
+            ));
+            Ok(RawExportImportFilesMetaData { inner }.to_metadata())
+        }
+    }
+}
+
+/// Describes the files of a column family exported by
+/// `Checkpoint::export_column_family`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExportImportFilesMetaData {
+    /// Name of the comparator the column family was written with. The importing
+    /// column family must use the same comparator.
+    pub db_comparator_name: String,
+    pub files: Vec<ExportedFile>,
+}
+
+/// An SST file of an exported column family.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExportedFile {
+    /// File name, relative to `directory`.
+    pub name: String,
+    /// Directory the file was exported to.
+    pub directory: PathBuf,
+    /// LSM level the file belongs to.
+    pub level: i32,
+    /// File size in bytes.
+    pub size: u64,
+    /// Smallest user key in the file.
+    pub smallest_key: Vec<u8>,
+    /// Largest user key in the file.
+    pub largest_key: Vec<u8>,
+    pub smallest_seqno: u64,
+    pub largest_seqno: u64,
+}
+
+pub(crate) struct RawExportImportFilesMetaData {
+    pub(crate) inner: *mut ffi::rocksdb_export_import_files_metadata_t,
+}
+
+impl RawExportImportFilesMetaData {
+    pub(crate) fn from_metadata(
+        metadata: &ExportImportFilesMetaData,
+    ) -> Result<RawExportImportFilesMetaData, Error> {
+        const ERR: &str = "Failed to convert export metadata to CString when importing";
+        let comparator_name = to_cstring(&metadata.db_comparator_name, ERR)?;
+        let raw = RawExportImportFilesMetaData {
+            inner: unsafe {
+                ffi::rocksdb_export_import_files_metadata_create(comparator_name.as_ptr())
+            },
+        };
+        for file in &metadata.files {
+            let name = to_cstring(&file.name, ERR)?;
+            let directory = to_cstring(file.directory.to_string_lossy(), ERR)?;
+            unsafe {
+                ffi::rocksdb_export_import_files_metadata_add_file(
+                    raw.inner,
+                    name.as_ptr(),
+                    directory.as_ptr(),
+                    file.level,
+                    file.size,
+                    file.smallest_key.as_ptr() as *const c_char,
+                    file.smallest_key.len() as size_t,
+                    file.largest_key.as_ptr() as *const c_char,
+                    file.largest_key.len() as size_t,
+                    file.smallest_seqno,
+                    file.largest_seqno,
+                );
+            }
+        }
+        Ok(raw)
+    }
+
+    fn to_metadata(&self) -> ExportImportFilesMetaData {
+        unsafe {
+            let mut len: size_t = 0;
+            let ptr =
+                ffi::rocksdb_export_import_files_metadata_db_comparator_name(self.inner, &mut len);
+            let db_comparator_name = string_from_raw(ptr, len);
+
+            let count = ffi::rocksdb_export_import_files_metadata_file_count(self.inner);
+            let files = (0..count)
+                .map(|i| {
+                    let ptr = ffi::rocksdb_export_import_files_metadata_file_name(
+                        self.inner, i, &mut len,
+                    );
+                    let name = string_from_raw(ptr, len);
+                    let ptr = ffi::rocksdb_export_import_files_metadata_file_db_path(
+                        self.inner, i, &mut len,
+                    );
+                    let directory = PathBuf::from(string_from_raw(ptr, len));
+                    let ptr = ffi::rocksdb_export_import_files_metadata_file_smallest_key(
+                        self.inner, i, &mut len,
+                    );
+                    let smallest_key = bytes_from_raw(ptr, len);
+                    let ptr = ffi::rocksdb_export_import_files_metadata_file_largest_key(
+                        self.inner, i, &mut len,
+                    );
+                    let largest_key = bytes_from_raw(ptr, len);
+                    ExportedFile {
+                        name,
+                        directory,
+                        level: ffi::rocksdb_export_import_files_metadata_file_level(self.inner, i),
+                        size: ffi::rocksdb_export_import_files_metadata_file_size(self.inner, i),
+                        smallest_key,
+                        largest_key,
+                        smallest_seqno:
+                            ffi::rocksdb_export_import_files_metadata_file_smallest_seqno(
+                                self.inner, i,
+                            ),
+                        largest_seqno: ffi::rocksdb_export_import_files_metadata_file_largest_seqno(
+                            self.inner, i,
+                        ),
+                    }
+                })
+                .collect();
+
+            ExportImportFilesMetaData {
+                db_comparator_name,
+                files,
+            }
+        }
+    }
+}
+
+impl Drop for RawExportImportFilesMetaData {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::rocksdb_export_import_files_metadata_destroy(self.inner);
+        }
+    }
+}
+
+unsafe fn bytes_from_raw(ptr: *const c_char, len: size_t) -> Vec<u8> {
+    slice::from_raw_parts(ptr as *const u8, len).to_vec()
+}
+
+unsafe fn string_from_raw(ptr: *const c_char, len: size_t) -> String {
+    String::from_utf8_lossy(slice::from_raw_parts(ptr as *const u8, len)).into_owned()
 }
 
 impl<'db> Drop for Checkpoint<'db> {
@@ -13,10 +13,14 @@
 // limitations under the License.
 //
 
-use libc::{c_char, c_int, c_uchar, c_void, size_t};
+use crate::ffi;
+
+use libc::{c_char, c_int, c_uint, c_void, size_t};
 use std::ffi::{CStr, CString};
 use std::slice;
 
+use crate::panic_policy::{abort_on_panic, catch_panic};
+
 /// Decision about how to handle compacting an object
 ///
 /// This is returned by a compaction filter callback. Depending
@@ -29,6 +33,28 @@ pub enum Decision {
     Remove,
     /// Change the value for the key
     Change(&'static [u8]),
+    /// Change the value for the key to a newly computed value
+    ChangeOwned(Vec<u8>),
+    /// Remove the object and all objects up to, but not including, the given
+    /// key, without passing them to the filter.
+    ///
+    /// This is much cheaper than removing the objects one by one, but the
+    /// skipped objects are not deleted from older levels, so older versions of
+    /// their keys may reappear. The key must be greater than the current key,
+    /// otherwise the decision is treated as `Keep`.
+    RemoveAndSkipUntil(Vec<u8>),
+}
+
+/// Type of an entry passed to [`CompactionFilter::filter_entry`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValueType {
+    /// A plain value written by a put.
+    Value,
+    /// An operand written by a merge.
+    MergeOperand,
+    /// A reference to a value stored in a blob file. Such entries can be kept
+    /// or removed but not changed; a change decision is treated as `Keep`.
+    BlobIndex,
 }
 
 /// CompactionFilter allows an application to modify/delete a key-value at
@@ -59,6 +85,24 @@ pub trait CompactionFilter {
     /// in existence and operating concurrently.
     fn filter(&mut self, level: u32, key: &[u8], value: &[u8]) -> Decision;
 
+    /// The compaction process invokes this method for every entry that is being
+    /// compacted, including merge operands and blob references.
+    ///
+    /// The default implementation passes plain values to
+    /// [`filter`](CompactionFilter::filter) and keeps all other entries.
+    fn filter_entry(
+        &mut self,
+        level: u32,
+        key: &[u8],
+        value_type: ValueType,
+        value: &[u8],
+    ) -> Decision {
+        match value_type {
+            ValueType::Value => self.filter(level, key, value),
+            ValueType::MergeOperand | ValueType::BlobIndex => Decision::Keep,
+        }
+    }
+
     /// Returns a name that identifies this compaction filter.
     /// The name will be printed to LOG file on start up for diagnosis.
     fn name(&self) -> &CStr;
@@ -100,7 +144,9 @@ pub unsafe extern "C" fn destructor_callback<F>(raw_cb: *mut c_void)
 where
     F: CompactionFilter,
 {
-    Box::from_raw(raw_cb as *mut F);
+    abort_on_panic(|| {
+        Box::from_raw(raw_cb as *mut F);
+    })
 }
 
 pub unsafe extern "C" fn name_callback<F>(raw_cb: *mut c_void) -> *const c_char
@@ -108,7 +154,7 @@ where
     F: CompactionFilter,
 {
     let cb = &*(raw_cb as *mut F);
-    cb.name().as_ptr()
+    abort_on_panic(|| cb.name().as_ptr())
 }
 
 pub unsafe extern "C" fn filter_callback<F>(
@@ -116,40 +162,66 @@ pub unsafe extern "C" fn filter_callback<F>(
     level: c_int,
     raw_key: *const c_char,
     key_length: size_t,
+    value_type: c_int,
     existing_value: *const c_char,
     value_length: size_t,
-    new_value: *mut *mut c_char,
-    new_value_length: *mut size_t,
-    value_changed: *mut c_uchar,
-) -> c_uchar
+    new_value: *mut c_void,
+    skip_until: *mut c_void,
+) -> c_int
 where
     F: CompactionFilter,
 {
-    use self::Decision::{Change, Keep, Remove};
+    use self::Decision::{Change, ChangeOwned, Keep, Remove, RemoveAndSkipUntil};
 
     let cb = &mut *(raw_cb as *mut F);
     let key = slice::from_raw_parts(raw_key as *const u8, key_length as usize);
     let oldval = slice::from_raw_parts(existing_value as *const u8, value_length as usize);
-    let result = cb.filter(level as u32, key, oldval);
-    match result {
-        Keep => 0,
-        Remove => 1,
+    let value_type = match value_type as c_uint {
+        ffi::rocksdb_compactionfilter_merge_operand => ValueType::MergeOperand,
+        ffi::rocksdb_compactionfilter_blob_index => ValueType::BlobIndex,
+        _ => ValueType::Value,
+    };
+    let result = catch_panic(
+        || cb.filter_entry(level as u32, key, value_type, oldval),
+        || Keep,
+    );
+    let decision = match result {
+        Keep => ffi::rocksdb_compactionfilter_keep,
+        Remove => ffi::rocksdb_compactionfilter_remove,
         Change(newval) => {
-            *new_value = newval.as_ptr() as *mut c_char;
-            *new_value_length = newval.len() as size_t;
-            *value_changed = 1_u8;
-            0
+            assign(new_value, newval);
+            ffi::rocksdb_compactionfilter_change_value
         }
-    }
+        ChangeOwned(newval) => {
+            assign(new_value, &newval);
+            ffi::rocksdb_compactionfilter_change_value
+        }
+        RemoveAndSkipUntil(until) => {
+            assign(skip_until, &until);
+            ffi::rocksdb_compactionfilter_remove_and_skip_until
+        }
+    };
+    decision as c_int
+}
+
+/// Copy `value` into the output string passed to `filter_callback`.
+unsafe fn assign(output: *mut c_void, value: &[u8]) {
+    ffi::rocksdb_compactionfilter_assign(
+        output,
+        value.as_ptr() as *const c_char,
+        value.len() as size_t,
+    );
 }
 
 #[cfg(test)]
 #[allow(unused_variables)]
 fn test_filter(level: u32, key: &[u8], value: &[u8]) -> Decision {
-    use self::Decision::{Change, Keep, Remove};
+    use self::Decision::{Change, ChangeOwned, Keep, Remove, RemoveAndSkipUntil};
     match key.first() {
         Some(&b'_') => Remove,
         Some(&b'%') => Change(b"secret"),
+        Some(&b'#') => ChangeOwned([value, b"-changed"].concat()),
+        Some(&b'a') => RemoveAndSkipUntil(b"c".to_vec()),
         _ => Keep,
     }
 }
@@ -167,10 +239,18 @@ fn compaction_filter_test() {
         let _r = db.put(b"k1", b"a");
         let _r = db.put(b"_k", b"b");
         let _r = db.put(b"%k", b"c");
+        let _r = db.put(b"#k", b"d");
+        let _r = db.put(b"a1", b"e");
+        let _r = db.put(b"b1", b"f");
+        let _r = db.put(b"c1", b"g");
         db.compact_range(None::<&[u8]>, None::<&[u8]>);
         assert_eq!(&*db.get(b"k1").unwrap().unwrap(), b"a");
         assert!(db.get(b"_k").unwrap().is_none());
         assert_eq!(&*db.get(b"%k").unwrap().unwrap(), b"secret");
+        assert_eq!(&*db.get(b"#k").unwrap().unwrap(), b"d-changed");
+        assert!(db.get(b"a1").unwrap().is_none());
+        assert!(db.get(b"b1").unwrap().is_none());
+        assert_eq!(&*db.get(b"c1").unwrap().unwrap(), b"g");
     }
     let result = DB::destroy(&opts, path);
     assert!(result.is_ok());
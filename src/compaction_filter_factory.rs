@@ -1,10 +1,13 @@
 use std::ffi::CStr;
+use std::ptr;
+use std::slice;
 
-use libc::{self, c_char, c_void};
+use libc::{self, c_char, c_uint, c_void};
 
 use crate::{
     compaction_filter::{self, CompactionFilter},
     ffi,
+    panic_policy::{abort_on_panic, catch_panic},
 };
 
 /// Each compaction will create a new CompactionFilter allowing the
@@ -30,7 +33,9 @@ pub unsafe extern "C" fn destructor_callback<F>(raw_self: *mut c_void)
 where
     F: CompactionFilterFactory,
 {
-    Box::from_raw(raw_self as *mut F);
+    abort_on_panic(|| {
+        Box::from_raw(raw_self as *mut F);
+    })
 }
 
 pub unsafe extern "C" fn name_callback<F>(raw_self: *mut c_void) -> *const c_char
@@ -38,7 +43,16 @@ where
     F: CompactionFilterFactory,
 {
     let self_ = &*(raw_self as *const c_void as *const F);
-    self_.name().as_ptr()
+    abort_on_panic(|| self_.name().as_ptr())
+}
+
+/// The reason a table file is being created
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TableFileCreationReason {
+    Flush,
+    Compaction,
+    Recovery,
+    Misc,
 }
 
 /// Context information of a compaction run
@@ -48,6 +62,12 @@ pub struct CompactionFilterContext {
     /// Is this compaction requested by the client (true),
     /// or is it occurring as an automatic compaction process
     pub is_manual_compaction: bool,
+    /// The id of the column family being compacted
+    pub column_family_id: u32,
+    /// The name of the column family being compacted
+    pub column_family_name: String,
+    /// Why the output files of this run are created
+    pub reason: TableFileCreationReason,
 }
 
 impl CompactionFilterContext {
@@ -55,10 +75,32 @@ impl CompactionFilterContext {
         let is_full_compaction = ffi::rocksdb_compactionfiltercontext_is_full_compaction(ptr) != 0;
         let is_manual_compaction =
             ffi::rocksdb_compactionfiltercontext_is_manual_compaction(ptr) != 0;
+        let column_family_id = ffi::rocksdb_compactionfiltercontext_column_family_id(ptr);
+
+        let mut len = 0;
+        let name = ffi::rocksdb_compactionfiltercontext_column_family_name(ptr, &mut len);
+        let column_family_name = if !name.is_null() {
+            let name = slice::from_raw_parts(name as *const u8, len);
+            String::from_utf8_lossy(name).into_owned()
+        } else {
+            String::new()
+        };
+
+        let reason = match ffi::rocksdb_compactionfiltercontext_reason(ptr) as c_uint {
+            ffi::rocksdb_table_file_creation_reason_flush => TableFileCreationReason::Flush,
+            ffi::rocksdb_table_file_creation_reason_compaction => {
+                TableFileCreationReason::Compaction
+            }
+            ffi::rocksdb_table_file_creation_reason_recovery => TableFileCreationReason::Recovery,
+            _ => TableFileCreationReason::Misc,
+        };
 
         Self {
             is_full_compaction,
             is_manual_compaction,
+            column_family_id,
+            column_family_name,
+            reason,
         }
     }
 }
@@ -72,11 +114,14 @@ where
 {
     let self_ = &mut *(raw_self as *mut F);
     let context = CompactionFilterContext::from_raw(context);
-    let filter = Box::new(self_.create(context));
+    let filter = match catch_panic(|| Some(self_.create(context)), || None) {
+        Some(filter) => Box::new(filter),
+        None => return ptr::null_mut(),
+    };
 
     let filter_ptr = Box::into_raw(filter);
 
-    ffi::rocksdb_compactionfilter_create(
+    ffi::rocksdb_compactionfilter_create_v2(
         filter_ptr as *mut c_void,
         Some(compaction_filter::destructor_callback::<F::Filter>),
         Some(compaction_filter::filter_callback::<F::Filter>),
@@ -15,22 +15,27 @@
 
 use libc::{c_char, c_int, c_void, size_t};
 use std::cmp::Ordering;
+use std::convert::TryFrom;
 use std::ffi::CString;
 use std::slice;
 
-pub type CompareFn = fn(&[u8], &[u8]) -> Ordering;
+use crate::panic_policy::abort_on_panic;
+
+pub type CompareFn = dyn Fn(&[u8], &[u8]) -> Ordering + Send + Sync;
 
 pub struct ComparatorCallback {
     pub name: CString,
-    pub f: CompareFn,
+    pub f: Box<CompareFn>,
 }
 
 pub unsafe extern "C" fn destructor_callback(raw_cb: *mut c_void) {
-    Box::from_raw(raw_cb as *mut ComparatorCallback);
+    abort_on_panic(|| {
+        Box::from_raw(raw_cb as *mut ComparatorCallback);
+    })
 }
 
 pub unsafe extern "C" fn name_callback(raw_cb: *mut c_void) -> *const c_char {
-    let cb: &mut ComparatorCallback = &mut *(raw_cb as *mut ComparatorCallback);
+    let cb: &ComparatorCallback = &*(raw_cb as *const ComparatorCallback);
     let ptr = cb.name.as_ptr();
     ptr as *const c_char
 }
@@ -42,12 +47,21 @@ pub unsafe extern "C" fn compare_callback(
     b_raw: *const c_char,
     b_len: size_t,
 ) -> c_int {
-    let cb: &mut ComparatorCallback = &mut *(raw_cb as *mut ComparatorCallback);
+    let cb: &ComparatorCallback = &*(raw_cb as *const ComparatorCallback);
     let a: &[u8] = slice::from_raw_parts(a_raw as *const u8, a_len as usize);
     let b: &[u8] = slice::from_raw_parts(b_raw as *const u8, b_len as usize);
-    match (cb.f)(a, b) {
+    match abort_on_panic(|| (cb.f)(a, b)) {
         Ordering::Less => -1,
         Ordering::Equal => 0,
         Ordering::Greater => 1,
     }
 }
+
+/// Orders keys of 8 bytes as big-endian `u64`s and all other keys byte-wise,
+/// which is consistent with the byte-wise order.
+pub fn big_endian_u64_compare(a: &[u8], b: &[u8]) -> Ordering {
+    match (<[u8; 8]>::try_from(a), <[u8; 8]>::try_from(b)) {
+        (Ok(a), Ok(b)) => u64::from_be_bytes(a).cmp(&u64::from_be_bytes(b)),
+        _ => a.cmp(b),
+    }
+}
@@ -250,7 +250,7 @@ fn external() {
 
 #[test]
 fn errors_do_stuff() {
-    use crate::{prelude::*, TemporaryDBPath};
+    use crate::{prelude::*, ErrorKind, TemporaryDBPath};
 
     let path = TemporaryDBPath::new();
     {
@@ -259,6 +259,7 @@ fn errors_do_stuff() {
         // The DB will still be open when we try to destroy it and the lock should fail.
         match DB::destroy(&opts, &path) {
             Err(s) => {
+                assert_eq!(s.kind(), ErrorKind::IOError);
                 let message = s.to_string();
                 assert!(message.contains("IO error:"));
                 assert!(message.contains("/LOCK:"));
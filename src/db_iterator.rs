@@ -13,7 +13,7 @@
 // limitations under the License.
 //
 
-use crate::ops::Iterate;
+use crate::{ops::Iterate, Error};
 use libc::{c_char, c_uchar, size_t};
 use std::marker::PhantomData;
 use std::slice;
@@ -121,6 +121,33 @@ pub struct DBIterator<'a> {
 
 unsafe impl<'a> Send for DBIterator<'a> {}
 
+/// A ``DBIterator`` that yields ``Result<KVBytes, Error>``.
+///
+/// ``DBIterator`` simply stops when the underlying iterator fails (for example
+/// on an I/O error or a checksum mismatch), which cannot be told apart from
+/// reaching the end of the range. This iterator instead yields the error as its
+/// last item.
+///
+/// ```
+/// use ckb_rocksdb::{prelude::*, IteratorMode};
+/// # use ckb_rocksdb::TemporaryDBPath;
+///
+/// let path = "_path_for_rocksdb_storage_fallible";
+/// # let path = TemporaryDBPath::new();
+/// # {
+///
+/// let db = DB::open_default(&path).unwrap();
+/// for item in db.iterator(IteratorMode::Start).fallible() {
+///     let (key, value) = item.unwrap();
+///     println!("Saw {:?} {:?}", key, value);
+/// }
+/// # }
+/// ```
+pub struct FallibleDBIterator<'a> {
+    inner: DBIterator<'a>,
+    done: bool,
+}
+
 pub enum Direction {
     Forward,
     Reverse,
@@ -353,6 +380,18 @@ impl<'a> DBRawIterator<'a> {
             None
         }
     }
+
+    /// Returns an error if the iterator has stopped because of a failure,
+    /// such as an I/O error or a data corruption, rather than because it
+    /// reached the end of the data.
+    ///
+    /// Should be checked whenever ``valid()`` returns false.
+    pub fn status(&self) -> Result<(), Error> {
+        unsafe {
+            ffi_try!(ffi::rocksdb_iter_get_error(self.inner));
+        }
+        Ok(())
+    }
 }
 
 impl<'a> Drop for DBRawIterator<'a> {
@@ -390,6 +429,21 @@ impl<'a> DBIterator<'a> {
     pub fn valid(&self) -> bool {
         self.raw.valid()
     }
+
+    /// Returns an error if the iteration has stopped because of a failure.
+    /// See ``DBRawIterator::status``.
+    pub fn status(&self) -> Result<(), Error> {
+        self.raw.status()
+    }
+
+    /// Turns this iterator into one that yields ``Result<KVBytes, Error>``,
+    /// reporting a failure of the underlying iterator as its last item.
+    pub fn fallible(self) -> FallibleDBIterator<'a> {
+        FallibleDBIterator {
+            inner: self,
+            done: false,
+        }
+    }
 }
 
 impl<'a> Iterator for DBIterator<'a> {
@@ -422,6 +476,37 @@ impl<'a> Iterator for DBIterator<'a> {
     }
 }
 
+impl<'a> FallibleDBIterator<'a> {
+    pub fn set_mode(&mut self, mode: IteratorMode) {
+        self.inner.set_mode(mode);
+        self.done = false;
+    }
+}
+
+impl<'a> Iterator for FallibleDBIterator<'a> {
+    type Item = Result<KVBytes, Error>;
+
+    fn next(&mut self) -> Option<Result<KVBytes, Error>> {
+        if self.done {
+            return None;
+        }
+
+        match self.inner.next() {
+            Some(item) => Some(Ok(item)),
+            None => {
+                self.done = true;
+                self.inner.status().err().map(Err)
+            }
+        }
+    }
+}
+
+impl<'a> From<FallibleDBIterator<'a>> for DBIterator<'a> {
+    fn from(iter: FallibleDBIterator<'a>) -> DBIterator<'a> {
+        iter.inner
+    }
+}
+
 impl<'a> From<DBIterator<'a>> for DBRawIterator<'a> {
     fn from(iter: DBIterator<'a>) -> DBRawIterator<'a> {
         iter.raw
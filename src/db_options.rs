@@ -17,20 +17,25 @@ use crate::{
     Error,
 };
 
+use std::cmp::Ordering;
 use std::ffi::{CStr, CString};
 use std::path::Path;
+use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
 
 use libc::{self, c_char, c_double, c_int, c_uchar, c_uint, c_void, size_t};
 
 use crate::compaction_filter::{self, CompactionFilterCallback, CompactionFilterFn};
 use crate::compaction_filter_factory::{self, CompactionFilterFactory};
-use crate::comparator::{self, ComparatorCallback, CompareFn};
+use crate::comparator::{self, ComparatorCallback};
+use crate::event_listener::{self, EventListener};
 use crate::ffi;
+use crate::logger::{self, Logger};
 use crate::merge_operator::{
     self, full_merge_callback, partial_merge_callback, MergeFn, MergeOperatorCallback,
 };
 use crate::slice_transform::SliceTransform;
+use crate::statistics::{Histogram, HistogramData, StatsLevel, Ticker};
 
 pub fn new_cache(capacity: size_t) -> *mut ffi::rocksdb_cache_t {
     unsafe { ffi::rocksdb_cache_create_lru(capacity) }
@@ -80,6 +85,391 @@ impl Cache {
     }
 }
 
+pub(crate) struct RateLimiterWrapper {
+    pub(crate) inner: *mut ffi::rocksdb_ratelimiter_t,
+}
+
+impl Drop for RateLimiterWrapper {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::rocksdb_ratelimiter_destroy(self.inner);
+        }
+    }
+}
+
+/// Limits the rate at which flushes and compactions write to disk.
+///
+/// A rate limiter can be shared by the `Options` of several databases and by
+/// `BackupEngineOptions`, in which case the rate applies to all of them
+/// together. Its rate can be changed while the databases are open.
+///
+/// # Examples
+///
+/// ```
+/// use ckb_rocksdb::{Options, RateLimiter};
+///
+/// let limiter = RateLimiter::new(16 * 1024 * 1024, 100 * 1000, 10).unwrap();
+/// let mut opts = Options::default();
+/// opts.set_rate_limiter(&limiter);
+///
+/// // Later, when I/O must be throttled.
+/// limiter.set_bytes_per_second(4 * 1024 * 1024);
+/// ```
+#[derive(Clone)]
+pub struct RateLimiter(pub(crate) Arc<RateLimiterWrapper>);
+
+impl RateLimiter {
+    /// Creates a rate limiter allowing `rate_bytes_per_sec` bytes to be
+    /// written per second.
+    ///
+    /// * `rate_bytes_per_sec` - the number of bytes allowed per second.
+    /// * `refill_period_us` - how often, in microseconds, the allowance is
+    ///   refilled. A smaller period smooths out writes at the cost of CPU.
+    /// * `fairness` - low priority requests get a chance to go first once in
+    ///   `fairness` times, even when high priority requests are waiting.
+    ///
+    /// Returns an `InvalidArgument` error unless all three are positive.
+    pub fn new(
+        rate_bytes_per_sec: i64,
+        refill_period_us: i64,
+        fairness: i32,
+    ) -> Result<RateLimiter, Error> {
+        check_rate_limiter_args(rate_bytes_per_sec, refill_period_us, fairness)?;
+        let inner = unsafe {
+            ffi::rocksdb_ratelimiter_create(rate_bytes_per_sec, refill_period_us, fairness)
+        };
+        Ok(RateLimiter(Arc::new(RateLimiterWrapper { inner })))
+    }
+
+    /// Creates a rate limiter which adjusts its rate to the demand, between
+    /// `rate_bytes_per_sec / 20` and `rate_bytes_per_sec`.
+    ///
+    /// See [`new`](#method.new) for the other arguments.
+    pub fn new_auto_tuned(
+        rate_bytes_per_sec: i64,
+        refill_period_us: i64,
+        fairness: i32,
+    ) -> Result<RateLimiter, Error> {
+        check_rate_limiter_args(rate_bytes_per_sec, refill_period_us, fairness)?;
+        let inner = unsafe {
+            ffi::rocksdb_ratelimiter_create_auto_tuned(
+                rate_bytes_per_sec,
+                refill_period_us,
+                fairness,
+            )
+        };
+        Ok(RateLimiter(Arc::new(RateLimiterWrapper { inner })))
+    }
+
+    /// Changes the number of bytes allowed per second, which must be
+    /// positive. For an auto-tuned limiter this is the upper bound.
+    pub fn set_bytes_per_second(&self, bytes_per_second: i64) {
+        assert_positive_rate(bytes_per_second);
+        unsafe {
+            ffi::rocksdb_ratelimiter_set_bytes_per_second(self.0.inner, bytes_per_second);
+        }
+    }
+
+    /// Returns the number of bytes currently allowed per second.
+    pub fn get_bytes_per_second(&self) -> i64 {
+        unsafe { ffi::rocksdb_ratelimiter_get_bytes_per_second(self.0.inner) }
+    }
+
+    /// Returns the total number of bytes which went through the limiter.
+    pub fn total_bytes_through(&self) -> i64 {
+        unsafe { ffi::rocksdb_ratelimiter_get_total_bytes_through(self.0.inner) }
+    }
+}
+
+// RocksDB only checks these in debug builds, and divides by the rate and
+// takes a random number modulo the fairness.
+fn check_rate_limiter_args(
+    rate_bytes_per_sec: i64,
+    refill_period_us: i64,
+    fairness: i32,
+) -> Result<(), Error> {
+    let message = if rate_bytes_per_sec <= 0 {
+        "the rate of a rate limiter must be positive"
+    } else if refill_period_us <= 0 {
+        "the refill period of a rate limiter must be positive"
+    } else if fairness <= 0 {
+        "the fairness of a rate limiter must be positive"
+    } else {
+        return Ok(());
+    };
+    Err(Error::from_status(format!("Invalid argument: {}", message)))
+}
+
+fn assert_positive_rate(bytes_per_second: i64) {
+    assert!(
+        bytes_per_second > 0,
+        "the rate of a rate limiter must be positive"
+    );
+}
+
+pub(crate) struct WriteBufferManagerWrapper {
+    pub(crate) inner: *mut ffi::rocksdb_write_buffer_manager_t,
+}
+
+impl Drop for WriteBufferManagerWrapper {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::rocksdb_write_buffer_manager_destroy(self.inner);
+        }
+    }
+}
+
+/// Bounds the memory used by memtables of every database and column family
+/// it is shared with.
+///
+/// When the usage grows past the buffer size, the memtables of the database
+/// being written to are flushed. Optionally, writes to all the databases are
+/// stalled until the usage goes back under the limit.
+///
+/// # Examples
+///
+/// ```
+/// use ckb_rocksdb::{prelude::*, Cache, TemporaryDBPath, TransactionDB, WriteBufferManager};
+///
+/// let cache = Cache::new_lru_cache(256 * 1024 * 1024).unwrap();
+/// let manager = WriteBufferManager::new_with_cache(64 * 1024 * 1024, &cache, false);
+///
+/// let mut opts = Options::default();
+/// opts.create_if_missing(true);
+/// opts.set_write_buffer_manager(&manager);
+///
+/// let path = TemporaryDBPath::new();
+/// let txn_path = TemporaryDBPath::new();
+/// let db = DB::open(&opts, &path).unwrap();
+/// let txn_db = TransactionDB::open(&opts, &txn_path).unwrap();
+/// db.put(b"k1", b"v1").unwrap();
+/// txn_db.put(b"k1", b"v1").unwrap();
+/// assert!(manager.get_usage() > 0);
+/// ```
+#[derive(Clone)]
+pub struct WriteBufferManager(pub(crate) Arc<WriteBufferManagerWrapper>);
+
+impl WriteBufferManager {
+    /// Creates a manager flushing memtables once they use more than
+    /// `buffer_size` bytes, 0 meaning no limit. The limit can be changed later
+    /// with `set_buffer_size`, but not added or removed.
+    ///
+    /// If `allow_stall` is set, writes are also stopped while the memory usage
+    /// exceeds `buffer_size`, until flushes bring it back under.
+    pub fn new(buffer_size: size_t, allow_stall: bool) -> WriteBufferManager {
+        let inner = unsafe {
+            ffi::rocksdb_write_buffer_manager_create(buffer_size, allow_stall as c_uchar)
+        };
+        WriteBufferManager(Arc::new(WriteBufferManagerWrapper { inner }))
+    }
+
+    /// Creates a manager which also charges the memory of the memtables to
+    /// `cache`, so that a single cache bounds both the block cache and the
+    /// memtables.
+    ///
+    /// See [`new`](#method.new) for the other arguments.
+    pub fn new_with_cache(
+        buffer_size: size_t,
+        cache: &Cache,
+        allow_stall: bool,
+    ) -> WriteBufferManager {
+        let inner = unsafe {
+            ffi::rocksdb_write_buffer_manager_create_with_cache(
+                buffer_size,
+                cache.0.inner,
+                allow_stall as c_uchar,
+            )
+        };
+        WriteBufferManager(Arc::new(WriteBufferManagerWrapper { inner }))
+    }
+
+    /// Returns true if the buffer size is not 0.
+    pub fn enabled(&self) -> bool {
+        unsafe { ffi::rocksdb_write_buffer_manager_enabled(self.0.inner) != 0 }
+    }
+
+    /// Returns true if the memory of the memtables is charged to a cache.
+    pub fn cost_to_cache(&self) -> bool {
+        unsafe { ffi::rocksdb_write_buffer_manager_cost_to_cache(self.0.inner) != 0 }
+    }
+
+    /// Returns the memory used by all the memtables.
+    pub fn get_usage(&self) -> usize {
+        unsafe { ffi::rocksdb_write_buffer_manager_memory_usage(self.0.inner) }
+    }
+
+    /// Returns the memory used by the memtables which are not being flushed.
+    pub fn get_mutable_memtable_usage(&self) -> usize {
+        unsafe { ffi::rocksdb_write_buffer_manager_mutable_memtable_memory_usage(self.0.inner) }
+    }
+
+    /// Returns the memory charged to the cache.
+    pub fn get_cache_usage(&self) -> usize {
+        unsafe { ffi::rocksdb_write_buffer_manager_dummy_entries_in_cache_usage(self.0.inner) }
+    }
+
+    /// Returns the buffer size.
+    pub fn get_buffer_size(&self) -> usize {
+        unsafe { ffi::rocksdb_write_buffer_manager_buffer_size(self.0.inner) }
+    }
+
+    /// Changes the buffer size, which must be positive, the function panics
+    /// otherwise. It also panics if the manager was created with a buffer
+    /// size of 0: the memory usage is only tracked while there is a limit, so
+    /// a limit can be neither added nor removed later.
+    pub fn set_buffer_size(&self, buffer_size: size_t) {
+        // RocksDB only checks this in debug builds.
+        assert!(
+            buffer_size > 0 && self.enabled(),
+            "the buffer size of a write buffer manager cannot be changed to or from 0"
+        );
+        unsafe {
+            ffi::rocksdb_write_buffer_manager_set_buffer_size(self.0.inner, buffer_size);
+        }
+    }
+}
+
+pub(crate) struct SstFileManagerWrapper {
+    pub(crate) inner: *mut ffi::rocksdb_sst_file_manager_t,
+    // The manager keeps a raw pointer to the env.
+    _env: Env,
+}
+
+impl Drop for SstFileManagerWrapper {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::rocksdb_sst_file_manager_destroy(self.inner);
+        }
+    }
+}
+
+/// Tracks the table and blob files of the databases it is shared with, to
+/// bound the disk space they use and to throttle file deletions.
+///
+/// # Examples
+///
+/// ```
+/// use ckb_rocksdb::{prelude::*, Env, SstFileManager, TemporaryDBPath};
+///
+/// let env = Env::default().unwrap();
+/// let manager = SstFileManager::new(&env).unwrap();
+/// manager.set_max_allowed_space_usage(64 * 1024 * 1024 * 1024);
+/// // Delete obsolete files at 64MB/s rather than all at once.
+/// manager.set_delete_rate_bytes_per_second(64 * 1024 * 1024);
+///
+/// let mut opts = Options::default();
+/// opts.create_if_missing(true);
+/// opts.set_env(&env);
+/// opts.set_sst_file_manager(&manager);
+///
+/// let path = TemporaryDBPath::new();
+/// let db = DB::open(&opts, &path).unwrap();
+/// db.put(b"k1", b"v1").unwrap();
+/// db.flush().unwrap();
+/// assert!(manager.get_total_size() > 0);
+/// ```
+#[derive(Clone)]
+pub struct SstFileManager(pub(crate) Arc<SstFileManagerWrapper>);
+
+impl SstFileManager {
+    /// Creates a manager for databases using `env`.
+    pub fn new(env: &Env) -> Result<SstFileManager, Error> {
+        let inner = unsafe { ffi_try!(ffi::rocksdb_sst_file_manager_create(env.0.inner)) };
+        Ok(SstFileManager(Arc::new(SstFileManagerWrapper {
+            inner,
+            _env: env.clone(),
+        })))
+    }
+
+    /// Sets the maximum size of the table and blob files. Once it is
+    /// reached, flushes and compactions fail and the databases stop accepting
+    /// writes. 0 means no limit.
+    ///
+    /// Default: 0
+    pub fn set_max_allowed_space_usage(&self, max_allowed_space: u64) {
+        unsafe {
+            ffi::rocksdb_sst_file_manager_set_max_allowed_space_usage(
+                self.0.inner,
+                max_allowed_space,
+            );
+        }
+    }
+
+    /// Sets the space to keep free, on top of the estimated output of running
+    /// compactions, before starting a new compaction. Compactions which would
+    /// not fit under the maximum allowed space are not started.
+    ///
+    /// Default: 0
+    pub fn set_compaction_buffer_size(&self, compaction_buffer_size: u64) {
+        unsafe {
+            ffi::rocksdb_sst_file_manager_set_compaction_buffer_size(
+                self.0.inner,
+                compaction_buffer_size,
+            );
+        }
+    }
+
+    /// Returns true if the files use more than the maximum allowed space.
+    pub fn is_max_allowed_space_reached(&self) -> bool {
+        unsafe { ffi::rocksdb_sst_file_manager_is_max_allowed_space_reached(self.0.inner) != 0 }
+    }
+
+    /// Returns true if the files, together with the estimated output of the
+    /// running compactions, use more than the maximum allowed space.
+    pub fn is_max_allowed_space_reached_including_compactions(&self) -> bool {
+        unsafe {
+            ffi::rocksdb_sst_file_manager_is_max_allowed_space_reached_including_compactions(
+                self.0.inner,
+            ) != 0
+        }
+    }
+
+    /// Returns the total size of the tracked files.
+    pub fn get_total_size(&self) -> u64 {
+        unsafe { ffi::rocksdb_sst_file_manager_get_total_size(self.0.inner) }
+    }
+
+    /// Returns the rate at which obsolete files are deleted, 0 meaning they
+    /// are deleted immediately.
+    pub fn get_delete_rate_bytes_per_second(&self) -> i64 {
+        unsafe { ffi::rocksdb_sst_file_manager_get_delete_rate_bytes_per_second(self.0.inner) }
+    }
+
+    /// Sets the rate at which obsolete files are deleted. Files waiting to be
+    /// deleted are moved to the trash, unless the trash is already larger than
+    /// the max trash/DB ratio. 0 means files are deleted immediately.
+    ///
+    /// Default: 0
+    pub fn set_delete_rate_bytes_per_second(&self, delete_rate: i64) {
+        unsafe {
+            ffi::rocksdb_sst_file_manager_set_delete_rate_bytes_per_second(
+                self.0.inner,
+                delete_rate,
+            );
+        }
+    }
+
+    /// Returns the max trash/DB ratio.
+    pub fn get_max_trash_db_ratio(&self) -> f64 {
+        unsafe { ffi::rocksdb_sst_file_manager_get_max_trash_db_ratio(self.0.inner) }
+    }
+
+    /// Sets the ratio of the trash size to the database size above which
+    /// files are deleted immediately, ignoring the delete rate.
+    ///
+    /// Default: 0.25
+    pub fn set_max_trash_db_ratio(&self, ratio: f64) {
+        unsafe {
+            ffi::rocksdb_sst_file_manager_set_max_trash_db_ratio(self.0.inner, ratio);
+        }
+    }
+
+    /// Returns the total size of the files waiting to be deleted.
+    pub fn get_total_trash_size(&self) -> u64 {
+        unsafe { ffi::rocksdb_sst_file_manager_get_total_trash_size(self.0.inner) }
+    }
+}
+
 /// An Env is an interface used by the rocksdb implementation to access
 /// operating system functionality like the filesystem etc.  Callers
 /// may wish to provide a custom Env object when opening a database to
@@ -205,7 +595,9 @@ impl Env {
 pub struct OptionsMustOutliveDB {
     pub(crate) env: Option<Env>,
     pub(crate) row_cache: Option<Cache>,
+    pub(crate) blob_cache: Option<Cache>,
     pub(crate) block_based: Option<BlockBasedOptionsMustOutliveDB>,
+    pub(crate) sst_file_manager: Option<SstFileManager>,
 }
 
 impl OptionsMustOutliveDB {
@@ -213,10 +605,12 @@ impl OptionsMustOutliveDB {
         Self {
             env: self.env.as_ref().map(Env::clone),
             row_cache: self.row_cache.as_ref().map(Cache::clone),
+            blob_cache: self.blob_cache.clone(),
             block_based: self
                 .block_based
                 .as_ref()
                 .map(BlockBasedOptionsMustOutliveDB::clone),
+            sst_file_manager: self.sst_file_manager.clone(),
         }
     }
 }
@@ -390,6 +784,9 @@ unsafe impl Send for CuckooTableOptions {}
 unsafe impl Send for ReadOptions {}
 unsafe impl Send for IngestExternalFileOptions {}
 unsafe impl Send for CacheWrapper {}
+unsafe impl Send for RateLimiterWrapper {}
+unsafe impl Send for WriteBufferManagerWrapper {}
+unsafe impl Send for SstFileManagerWrapper {}
 unsafe impl Send for EnvWrapper {}
 
 // Sync is similarly safe for many types because they do not expose interior mutability, and their
@@ -401,6 +798,9 @@ unsafe impl Sync for CuckooTableOptions {}
 unsafe impl Sync for ReadOptions {}
 unsafe impl Sync for IngestExternalFileOptions {}
 unsafe impl Sync for CacheWrapper {}
+unsafe impl Sync for RateLimiterWrapper {}
+unsafe impl Sync for WriteBufferManagerWrapper {}
+unsafe impl Sync for SstFileManagerWrapper {}
 unsafe impl Sync for EnvWrapper {}
 
 impl Drop for Options {
@@ -1029,6 +1429,18 @@ impl Options {
         self.outlive.env = Some(env.clone());
     }
 
+    /// Tracks the table and blob files of the databases opened with these
+    /// options with `manager`, which may be shared with other `Options`
+    /// using the same env.
+    ///
+    /// See [`SstFileManager`] for an example.
+    pub fn set_sst_file_manager(&mut self, manager: &SstFileManager) {
+        unsafe {
+            ffi::rocksdb_options_set_sst_file_manager(self.inner, manager.0.inner);
+        }
+        self.outlive.sst_file_manager = Some(manager.clone());
+    }
+
     /// Sets the compression algorithm that will be used for compressing blocks.
     ///
     /// Default: `DBCompressionType::Snappy` (`DBCompressionType::None` if
@@ -1048,6 +1460,102 @@ impl Options {
         }
     }
 
+    /// Enables integrated BlobDB: values of at least
+    /// [`min_blob_size`](#method.set_min_blob_size) bytes are written to blob
+    /// files during flushes and compactions, and the table files only keep a
+    /// reference to them. Large values are then not rewritten by every
+    /// compaction, which reduces write amplification.
+    ///
+    /// Default: false
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ckb_rocksdb::{Options, DBCompressionType};
+    ///
+    /// let mut opts = Options::default();
+    /// opts.set_enable_blob_files(true);
+    /// opts.set_min_blob_size(4096);
+    /// opts.set_blob_compression_type(DBCompressionType::Lz4);
+    /// opts.set_enable_blob_gc(true);
+    /// ```
+    pub fn set_enable_blob_files(&mut self, val: bool) {
+        unsafe {
+            ffi::rocksdb_options_set_enable_blob_files(self.inner, val as c_uchar);
+        }
+    }
+
+    /// Sets the size of the smallest value stored in blob files, when they
+    /// are enabled.
+    ///
+    /// Default: 0
+    pub fn set_min_blob_size(&mut self, val: u64) {
+        unsafe {
+            ffi::rocksdb_options_set_min_blob_size(self.inner, val);
+        }
+    }
+
+    /// Sets the size above which a new blob file is started.
+    ///
+    /// Default: 256MB
+    pub fn set_blob_file_size(&mut self, val: u64) {
+        unsafe {
+            ffi::rocksdb_options_set_blob_file_size(self.inner, val);
+        }
+    }
+
+    /// Sets the compression algorithm used for values in blob files.
+    ///
+    /// Default: `DBCompressionType::None`
+    pub fn set_blob_compression_type(&mut self, val: DBCompressionType) {
+        unsafe {
+            ffi::rocksdb_options_set_blob_compression_type(self.inner, val as c_int);
+        }
+    }
+
+    /// Enables garbage collection of blob files: during compactions, values
+    /// still referenced in the oldest blob files are moved to new blob files,
+    /// so that the old files can be deleted.
+    ///
+    /// Default: false
+    pub fn set_enable_blob_gc(&mut self, val: bool) {
+        unsafe {
+            ffi::rocksdb_options_set_enable_blob_gc(self.inner, val as c_uchar);
+        }
+    }
+
+    /// Sets the fraction of blob files, oldest first, whose values are moved
+    /// by garbage collection. Between 0 and 1.
+    ///
+    /// Default: 0.25
+    pub fn set_blob_gc_age_cutoff(&mut self, val: c_double) {
+        unsafe {
+            ffi::rocksdb_options_set_blob_gc_age_cutoff(self.inner, val);
+        }
+    }
+
+    /// If the ratio of garbage in the blob files eligible for garbage
+    /// collection exceeds this threshold, compactions are scheduled for the
+    /// table files referencing the oldest of them. Between 0 and 1, 1 meaning
+    /// such compactions are never forced.
+    ///
+    /// Default: 1.0
+    pub fn set_blob_gc_force_threshold(&mut self, val: c_double) {
+        unsafe {
+            ffi::rocksdb_options_set_blob_gc_force_threshold(self.inner, val);
+        }
+    }
+
+    /// Sets the cache for values read from blob files.
+    ///
+    /// Default: no cache
+    pub fn set_blob_cache(&mut self, cache: &Cache) {
+        unsafe {
+            ffi::rocksdb_options_set_blob_cache(self.inner, cache.0.inner);
+        }
+        self.outlive.blob_cache = Some(cache.clone());
+    }
+
     /// Sets the bottom-most compression algorithm that will be used for
     /// compressing blocks at the bottom-most level.
     ///
@@ -1319,7 +1827,7 @@ impl Options {
         });
 
         unsafe {
-            let cf = ffi::rocksdb_compactionfilter_create(
+            let cf = ffi::rocksdb_compactionfilter_create_v2(
                 Box::into_raw(cb).cast::<c_void>(),
                 Some(compaction_filter::destructor_callback::<CompactionFilterCallback<F>>),
                 Some(compaction_filter::filter_callback::<CompactionFilterCallback<F>>),
@@ -1344,7 +1852,7 @@ impl Options {
         let factory = Box::new(factory);
 
         unsafe {
-            let cff = ffi::rocksdb_compactionfilterfactory_create(
+            let cff = ffi::rocksdb_compactionfilterfactory_create_v2(
                 Box::into_raw(factory).cast::<c_void>(),
                 Some(compaction_filter_factory::destructor_callback::<F>),
                 Some(compaction_filter_factory::create_compaction_filter_callback::<F>),
@@ -1355,16 +1863,48 @@ impl Options {
         }
     }
 
+    /// Makes the compaction filter factory report `name` as the column family
+    /// of the contexts it receives through these options.
+    pub(crate) fn set_compaction_filter_factory_cf_name(&mut self, name: &CStr) {
+        unsafe {
+            ffi::rocksdb_options_set_compaction_filter_factory_column_family_name(
+                self.inner,
+                name.as_ptr(),
+            );
+        }
+    }
+
+    /// Adds a listener which is notified of flushes, compactions and other
+    /// background events of the databases opened with these options.
+    ///
+    /// Several listeners may be added; they are called in the order they were
+    /// added. See [`EventListener`] for an example.
+    pub fn add_event_listener<L>(&mut self, listener: L)
+    where
+        L: EventListener + 'static,
+    {
+        unsafe {
+            ffi::rocksdb_options_add_eventlistener(self.inner, event_listener::create(listener));
+        }
+    }
+
     /// Sets the comparator used to define the order of keys in the table.
     /// Default: a comparator that uses lexicographic byte-wise ordering
     ///
     /// The client must ensure that the comparator supplied here has the same
     /// name and orders keys *exactly* the same as the comparator provided to
     /// previous open calls on the same DB.
-    pub fn set_comparator(&mut self, name: &str, compare_fn: CompareFn) {
+    ///
+    /// `compare_fn` may be a closure, e.g. one capturing the version of the
+    /// key schema, and is dropped together with the comparator. It is called
+    /// from several threads at once.
+    pub fn set_comparator<F>(&mut self, name: &str, compare_fn: F)
+    where
+        F: Fn(&[u8], &[u8]) -> Ordering + Send + Sync + 'static,
+    {
         let cb = Box::new(ComparatorCallback {
             name: CString::new(name.as_bytes()).unwrap(),
-            f: compare_fn,
+            f: Box::new(compare_fn),
         });
 
         unsafe {
@@ -1378,6 +1918,30 @@ impl Options {
         }
     }
 
+    /// Sets RocksDB's built in comparator which orders keys in reverse
+    /// lexicographic byte-wise order.
+    pub fn set_reverse_bytewise_comparator(&mut self) {
+        unsafe {
+            ffi::rocksdb_options_set_reverse_bytewise_comparator(self.inner);
+        }
+    }
+
+    /// Sets a comparator for keys which are big-endian encoded `u64`s, such as
+    /// block numbers. Keys of 8 bytes are compared as integers, all other keys
+    /// byte-wise, which gives the same order as the default comparator but
+    /// under a different name.
+    ///
+    /// As big-endian integers of a fixed width already sort byte-wise, the
+    /// default comparator is enough for such keys and avoids a call into Rust
+    /// per comparison. This one only records the key schema in the name, so a
+    /// database created with it must always be opened with it.
+    pub fn set_big_endian_u64_comparator(&mut self) {
+        self.set_comparator(
+            "ckb_rocksdb.BigEndianU64Comparator",
+            comparator::big_endian_u64_compare,
+        );
+    }
+
     pub fn set_prefix_extractor(&mut self, prefix_extractor: SliceTransform) {
         unsafe {
             ffi::rocksdb_options_set_prefix_extractor(self.inner, prefix_extractor.inner);
@@ -1388,7 +1952,10 @@ impl Options {
         since = "0.5.0",
         note = "add_comparator has been renamed to set_comparator"
     )]
-    pub fn add_comparator(&mut self, name: &str, compare_fn: CompareFn) {
+    pub fn add_comparator<F>(&mut self, name: &str, compare_fn: F)
+    where
+        F: Fn(&[u8], &[u8]) -> Ordering + Send + Sync + 'static,
+    {
         self.set_comparator(name, compare_fn);
     }
 
@@ -1531,6 +2098,24 @@ impl Options {
         }
     }
 
+    /// Sends the info log to `logger` instead of a LOG file, dropping
+    /// messages below `level`.
+    ///
+    /// The level set by [`set_log_level`](#method.set_log_level) and the
+    /// settings of the LOG files, such as
+    /// [`set_db_log_dir`](#method.set_db_log_dir), are ignored once a logger is
+    /// set. See [`Logger`] for an example.
+    pub fn set_logger<L>(&mut self, level: LogLevel, logger: L)
+    where
+        L: Logger + 'static,
+    {
+        unsafe {
+            let logger = logger::create(level, logger);
+            ffi::rocksdb_options_set_info_log(self.inner, logger);
+            ffi::rocksdb_logger_destroy(logger);
+        }
+    }
+
     /// Allows OS to incrementally sync files to disk while they are being
     /// written, asynchronously, in the background. This operation can be used
     /// to smooth out write I/Os over time. Users shouldn't rely on it for
@@ -1865,6 +2450,17 @@ impl Options {
         }
     }
 
+    /// Accounts the memtables of the databases opened with these options to
+    /// `manager`, which may be shared with other `Options`.
+    ///
+    /// Overrides [`set_db_write_buffer_size`](#method.set_db_write_buffer_size).
+    /// See [`WriteBufferManager`] for an example.
+    pub fn set_write_buffer_manager(&mut self, manager: &WriteBufferManager) {
+        unsafe {
+            ffi::rocksdb_options_set_write_buffer_manager(self.inner, manager.0.inner);
+        }
+    }
+
     /// Control maximum total data size for a level.
     /// max_bytes_for_level_base is the max total for level-1.
     /// Maximum number of bytes for level L can be calculated as
@@ -2547,9 +3143,11 @@ impl Options {
         }
     }
 
+    /// Enables statistics, keeping those already enabled, e.g. by
+    /// `set_statistics_level`.
     pub fn enable_statistics(&mut self) {
         unsafe {
-            ffi::rocksdb_options_enable_statistics(self.inner);
+            ffi::rocksdb_options_ensure_statistics(self.inner);
         }
     }
 
@@ -2567,6 +3165,60 @@ impl Options {
         }
     }
 
+    /// Sets which statistics are collected, enabling statistics if they are
+    /// not yet.
+    ///
+    /// Default: `StatsLevel::ExceptDetailedTimers`
+    pub fn set_statistics_level(&mut self, level: StatsLevel) {
+        unsafe {
+            ffi::rocksdb_options_set_statistics_level(self.inner, level as c_int);
+        }
+    }
+
+    /// Returns which statistics are collected, `StatsLevel::DisableAll` if
+    /// statistics are not enabled.
+    pub fn get_statistics_level(&self) -> StatsLevel {
+        let level = unsafe { ffi::rocksdb_options_get_statistics_level(self.inner) };
+        StatsLevel::from_raw(level)
+    }
+
+    /// Returns the value of a ticker, `0` if statistics are not enabled.
+    ///
+    /// The statistics are shared with the databases opened with these options.
+    pub fn get_ticker_count(&self, ticker: Ticker) -> u64 {
+        unsafe { ffi::rocksdb_options_statistics_get_ticker_count(self.inner, ticker as u32) }
+    }
+
+    /// Returns the summary of a histogram, all zeros if statistics are not
+    /// enabled.
+    pub fn get_histogram_data(&self, histogram: Histogram) -> HistogramData {
+        let mut data = HistogramData::default();
+        unsafe {
+            ffi::rocksdb_options_statistics_get_histogram_data(
+                self.inner,
+                histogram as u32,
+                &mut data.median,
+                &mut data.p95,
+                &mut data.p99,
+                &mut data.average,
+                &mut data.std_dev,
+                &mut data.max,
+                &mut data.min,
+                &mut data.count,
+                &mut data.sum,
+            );
+        }
+        data
+    }
+
+    /// Resets all tickers and histograms to zero.
+    pub fn reset_statistics(&self) -> Result<(), Error> {
+        unsafe {
+            ffi_try!(ffi::rocksdb_options_statistics_reset(self.inner));
+        }
+        Ok(())
+    }
+
     /// If not zero, dump `rocksdb.stats` to LOG every `stats_dump_period_sec`.
     ///
     /// Default: `600` (10 mins)
@@ -2853,6 +3505,28 @@ impl Options {
         }
     }
 
+    /// Enables two-phase commit, which writes the prepare and commit markers of
+    /// named transactions to the WAL so that prepared but uncommitted
+    /// transactions can be recovered after a restart.
+    ///
+    /// `TransactionDB` always turns this on when it opens the database.
+    ///
+    /// Default: false
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ckb_rocksdb::Options;
+    ///
+    /// let mut options = Options::default();
+    /// options.set_allow_2pc(true);
+    /// ```
+    pub fn set_allow_2pc(&mut self, allow_2pc: bool) {
+        unsafe {
+            ffi::rocksdb_options_set_allow_2pc(self.inner, allow_2pc as c_uchar);
+        }
+    }
+
     /// Sets global cache for table-level rows. Cache must outlive DB instance which uses it.
     ///
     /// Default: null (disabled)
@@ -2867,6 +3541,8 @@ impl Options {
     /// Use to control write rate of flush and compaction. Flush has higher
     /// priority than compaction.
     /// If rate limiter is enabled, bytes_per_sync is set to 1MB by default.
+    /// Use [`set_rate_limiter`](#method.set_rate_limiter) to share a limiter
+    /// or change its rate later.
     ///
     /// Default: disable
     ///
@@ -2893,6 +3569,17 @@ impl Options {
         }
     }
 
+    /// Limits the write rate of flush and compaction with `limiter`, which may
+    /// be shared with other `Options`.
+    ///
+    /// If rate limiter is enabled, bytes_per_sync is set to 1MB by default.
+    /// See [`RateLimiter`] for an example.
+    pub fn set_rate_limiter(&mut self, limiter: &RateLimiter) {
+        unsafe {
+            ffi::rocksdb_options_set_ratelimiter(self.inner, limiter.0.inner);
+        }
+    }
+
     /// Sets the maximal size of the info log file.
     ///
     /// If the log file is larger than `max_log_file_size`, a new info log file
@@ -3652,6 +4339,7 @@ pub enum BottommostLevelCompaction {
 
 pub struct CompactOptions {
     pub(crate) inner: *mut ffi::rocksdb_compactoptions_t,
+    canceled: Option<Arc<AtomicBool>>,
 }
 
 impl Default for CompactOptions {
@@ -3659,7 +4347,10 @@ impl Default for CompactOptions {
         let opts = unsafe { ffi::rocksdb_compactoptions_create() };
         assert!(!opts.is_null(), "Could not create RocksDB Compact Options");
 
-        Self { inner: opts }
+        Self {
+            inner: opts,
+            canceled: None,
+        }
     }
 }
 
@@ -3705,6 +4396,34 @@ impl CompactOptions {
             ffi::rocksdb_compactoptions_set_target_level(self.inner, lvl);
         }
     }
+
+    /// Allows cancellation of a manual compaction started with these options:
+    /// storing `true` in `canceled`, e.g. from another thread, makes the
+    /// compaction stop early and return an error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ckb_rocksdb::CompactOptions;
+    /// use std::sync::atomic::{AtomicBool, Ordering};
+    /// use std::sync::Arc;
+    ///
+    /// let canceled = Arc::new(AtomicBool::new(false));
+    /// let mut opts = CompactOptions::default();
+    /// opts.set_canceled(Arc::clone(&canceled));
+    ///
+    /// // later, to abort the compaction
+    /// canceled.store(true, Ordering::Release);
+    /// ```
+    pub fn set_canceled(&mut self, canceled: Arc<AtomicBool>) {
+        unsafe {
+            ffi::rocksdb_compactoptions_set_canceled(
+                self.inner,
+                &*canceled as *const AtomicBool as *mut c_uchar,
+            );
+        }
+        self.canceled = Some(canceled);
+    }
 }
 
 /// Represents a path where sst files can be put into
@@ -3781,6 +4500,30 @@ mod tests {
         assert!(opts.get_statistics().is_none());
     }
 
+    #[test]
+    fn test_statistics_level() {
+        use crate::StatsLevel;
+
+        let mut opts = Options::default();
+        assert_eq!(opts.get_statistics_level(), StatsLevel::DisableAll);
+        opts.set_statistics_level(StatsLevel::All);
+        assert_eq!(opts.get_statistics_level(), StatsLevel::All);
+        assert!(opts.get_statistics().is_some());
+
+        // Enabling statistics afterwards keeps the level.
+        opts.enable_statistics();
+        assert_eq!(opts.get_statistics_level(), StatsLevel::All);
+
+        let mut opts = Options::default();
+        opts.enable_statistics();
+        assert_eq!(
+            opts.get_statistics_level(),
+            StatsLevel::ExceptDetailedTimers
+        );
+        opts.set_statistics_level(StatsLevel::ExceptTimers);
+        assert_eq!(opts.get_statistics_level(), StatsLevel::ExceptTimers);
+    }
+
     #[test]
     fn test_set_memtable_factory() {
         let mut opts = Options::default();
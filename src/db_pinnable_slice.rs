@@ -13,7 +13,7 @@
 // limitations under the License.
 //
 
-use libc::size_t;
+use libc::{c_char, size_t};
 
 use crate::ffi;
 use std::fmt;
@@ -80,6 +80,16 @@ impl<'a> DBPinnableSlice<'a> {
             db: PhantomData,
         }
     }
+
+    /// Copies `value` into a pinnable slice which owns its data.
+    pub(crate) fn copy_from(value: &[u8]) -> DBPinnableSlice<'a> {
+        unsafe {
+            DBPinnableSlice::from_c(ffi::rocksdb_pinnableslice_create_from(
+                value.as_ptr() as *const c_char,
+                value.len() as size_t,
+            ))
+        }
+    }
 }
 
 impl<'a> fmt::Debug for DBPinnableSlice<'a> {
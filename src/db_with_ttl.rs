@@ -35,6 +35,8 @@ impl DBWithTTL {
             name.as_ref(),
             "Failed to convert path to CString when opening rocksdb",
         )?;
+        let mut opts = opts.clone();
+        opts.set_compaction_filter_factory_cf_name(&cname);
         unsafe {
             let cf_handle = ffi_try!(ffi::rocksdb_create_column_family_with_ttl(
                 self.handle(),
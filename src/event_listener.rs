@@ -0,0 +1,494 @@
+// Copyright 2019 Tyler Neely
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Notifications about the background work of RocksDB.
+//!
+//! ```
+//! use ckb_rocksdb::{
+//!     event_listener::{EventListener, FlushJobInfo},
+//!     prelude::*,
+//! };
+//! use std::sync::atomic::{AtomicU64, Ordering};
+//! use std::sync::Arc;
+//! # use ckb_rocksdb::TemporaryDBPath;
+//!
+//! struct FlushCounter(Arc<AtomicU64>);
+//!
+//! impl EventListener for FlushCounter {
+//!     fn on_flush_completed(&self, info: &FlushJobInfo) {
+//!         self.0.fetch_add(info.num_entries, Ordering::Relaxed);
+//!     }
+//! }
+//!
+//! let flushed = Arc::new(AtomicU64::new(0));
+//! let mut opts = Options::default();
+//! opts.create_if_missing(true);
+//! opts.add_event_listener(FlushCounter(Arc::clone(&flushed)));
+//! # let path = TemporaryDBPath::new();
+//! let db = DB::open(&opts, &path).unwrap();
+//! db.put(b"k1", b"v1").unwrap();
+//! db.flush().unwrap();
+//!
+//! // Listeners run in background threads, which are done once the database
+//! // is closed.
+//! drop(db);
+//! assert_eq!(flushed.load(Ordering::Relaxed), 1);
+//! ```
+
+use std::ffi::CStr;
+use std::path::PathBuf;
+use std::ptr;
+use std::slice;
+
+use libc::{c_char, c_int, c_uint, c_void, size_t};
+
+use crate::ffi;
+use crate::ffi_util::error_message;
+use crate::panic_policy::{abort_on_panic, catch_panic};
+use crate::Error;
+
+/// Receives notifications about flushes, compactions and other background
+/// events of the databases opened with the options it was added to, see
+/// [`Options::add_event_listener`](crate::Options::add_event_listener).
+///
+/// The methods are called from the background threads of RocksDB, possibly
+/// several at once, and block the job they report about until they return,
+/// so they should be quick. They must not call back into the database.
+///
+/// If a method panics and the [`PanicPolicy`](crate::PanicPolicy) is `Fail`,
+/// the panic is ignored.
+pub trait EventListener: Send + Sync {
+    /// Called before a flush job starts.
+    fn on_flush_begin(&self, _info: &FlushJobInfo) {}
+
+    /// Called after a flush job has written its table file.
+    fn on_flush_completed(&self, _info: &FlushJobInfo) {}
+
+    /// Called before a compaction job starts.
+    fn on_compaction_begin(&self, _info: &CompactionJobInfo) {}
+
+    /// Called after a compaction job has finished, whether it succeeded or not.
+    fn on_compaction_completed(&self, _info: &CompactionJobInfo) {}
+
+    /// Called when writes to a column family are delayed, stopped or resumed.
+    fn on_stall_conditions_changed(&self, _info: &WriteStallInfo) {}
+
+    /// Called when a background job fails. The database may stop accepting
+    /// writes until the error is recovered from.
+    fn on_background_error(&self, _reason: BackgroundErrorReason, _error: &Error) {}
+
+    /// Called after a table file was deleted.
+    fn on_table_file_deleted(&self, _info: &TableFileDeletionInfo) {}
+}
+
+/// Why a flush was started
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FlushReason {
+    Others,
+    GetLiveFiles,
+    ShutDown,
+    ExternalFileIngestion,
+    ManualCompaction,
+    WriteBufferManager,
+    WriteBufferFull,
+    Test,
+    DeleteFiles,
+    AutoCompaction,
+    ManualFlush,
+    ErrorRecovery,
+    ErrorRecoveryRetryFlush,
+    WalFull,
+}
+
+impl FlushReason {
+    fn from_raw(reason: c_int) -> FlushReason {
+        match reason {
+            0x01 => FlushReason::GetLiveFiles,
+            0x02 => FlushReason::ShutDown,
+            0x03 => FlushReason::ExternalFileIngestion,
+            0x04 => FlushReason::ManualCompaction,
+            0x05 => FlushReason::WriteBufferManager,
+            0x06 => FlushReason::WriteBufferFull,
+            0x07 => FlushReason::Test,
+            0x08 => FlushReason::DeleteFiles,
+            0x09 => FlushReason::AutoCompaction,
+            0x0a => FlushReason::ManualFlush,
+            0x0b => FlushReason::ErrorRecovery,
+            0x0c => FlushReason::ErrorRecoveryRetryFlush,
+            0x0d => FlushReason::WalFull,
+            _ => FlushReason::Others,
+        }
+    }
+}
+
+/// Why a compaction was started
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompactionReason {
+    Unknown,
+    /// The number of L0 files reached `level0_file_num_compaction_trigger`
+    LevelL0FilesNum,
+    /// The size of a level exceeded its target size
+    LevelMaxLevelSize,
+    UniversalSizeAmplification,
+    UniversalSizeRatio,
+    UniversalSortedRunNum,
+    FifoMaxSize,
+    FifoReduceNumFiles,
+    FifoTtl,
+    /// A compaction requested through `compact_range`
+    ManualCompaction,
+    FilesMarkedForCompaction,
+    BottommostFiles,
+    Ttl,
+    Flush,
+    ExternalSstIngestion,
+    PeriodicCompaction,
+    ChangeTemperature,
+    ForcedBlobGc,
+}
+
+impl CompactionReason {
+    fn from_raw(reason: c_int) -> CompactionReason {
+        match reason {
+            1 => CompactionReason::LevelL0FilesNum,
+            2 => CompactionReason::LevelMaxLevelSize,
+            3 => CompactionReason::UniversalSizeAmplification,
+            4 => CompactionReason::UniversalSizeRatio,
+            5 => CompactionReason::UniversalSortedRunNum,
+            6 => CompactionReason::FifoMaxSize,
+            7 => CompactionReason::FifoReduceNumFiles,
+            8 => CompactionReason::FifoTtl,
+            9 => CompactionReason::ManualCompaction,
+            10 => CompactionReason::FilesMarkedForCompaction,
+            11 => CompactionReason::BottommostFiles,
+            12 => CompactionReason::Ttl,
+            13 => CompactionReason::Flush,
+            14 => CompactionReason::ExternalSstIngestion,
+            15 => CompactionReason::PeriodicCompaction,
+            16 => CompactionReason::ChangeTemperature,
+            17 => CompactionReason::ForcedBlobGc,
+            _ => CompactionReason::Unknown,
+        }
+    }
+}
+
+/// The background job which failed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BackgroundErrorReason {
+    Flush,
+    Compaction,
+    WriteCallback,
+    MemTable,
+    ManifestWrite,
+    FlushNoWal,
+    ManifestWriteNoWal,
+    /// A reason added by a later version of RocksDB
+    Unknown(u32),
+}
+
+impl BackgroundErrorReason {
+    fn from_raw(reason: c_int) -> BackgroundErrorReason {
+        match reason as c_uint {
+            ffi::rocksdb_background_error_reason_flush => BackgroundErrorReason::Flush,
+            ffi::rocksdb_background_error_reason_compaction => BackgroundErrorReason::Compaction,
+            ffi::rocksdb_background_error_reason_write_callback => {
+                BackgroundErrorReason::WriteCallback
+            }
+            ffi::rocksdb_background_error_reason_memtable => BackgroundErrorReason::MemTable,
+            ffi::rocksdb_background_error_reason_manifest_write => {
+                BackgroundErrorReason::ManifestWrite
+            }
+            ffi::rocksdb_background_error_reason_flush_no_wal => BackgroundErrorReason::FlushNoWal,
+            ffi::rocksdb_background_error_reason_manifest_write_no_wal => {
+                BackgroundErrorReason::ManifestWriteNoWal
+            }
+            reason => BackgroundErrorReason::Unknown(reason),
+        }
+    }
+}
+
+/// Whether writes to a column family are throttled
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WriteStallCondition {
+    Normal,
+    Delayed,
+    Stopped,
+}
+
+impl WriteStallCondition {
+    fn from_raw(condition: c_int) -> WriteStallCondition {
+        match condition as c_uint {
+            ffi::rocksdb_write_stall_condition_delayed => WriteStallCondition::Delayed,
+            ffi::rocksdb_write_stall_condition_stopped => WriteStallCondition::Stopped,
+            _ => WriteStallCondition::Normal,
+        }
+    }
+}
+
+/// A flush of a memtable to a table file
+#[derive(Debug, Clone)]
+pub struct FlushJobInfo {
+    pub cf_id: u32,
+    pub cf_name: String,
+    /// The path of the table file written by the flush
+    pub file_path: PathBuf,
+    pub job_id: i32,
+    /// Whether writes were slowed down because too many memtables were
+    /// waiting to be flushed
+    pub triggered_writes_slowdown: bool,
+    /// Whether writes were stopped because too many memtables were waiting
+    /// to be flushed
+    pub triggered_writes_stop: bool,
+    pub smallest_seqno: u64,
+    pub largest_seqno: u64,
+    pub reason: FlushReason,
+    /// The number of entries written to the table file
+    pub num_entries: u64,
+    /// The size of the data blocks of the table file
+    pub data_size: u64,
+}
+
+impl FlushJobInfo {
+    unsafe fn from_raw(info: *const ffi::rocksdb_flushjobinfo_t) -> FlushJobInfo {
+        FlushJobInfo {
+            cf_id: ffi::rocksdb_flushjobinfo_cf_id(info),
+            cf_name: get_string(|len| ffi::rocksdb_flushjobinfo_cf_name(info, len)),
+            file_path: get_string(|len| ffi::rocksdb_flushjobinfo_file_path(info, len)).into(),
+            job_id: ffi::rocksdb_flushjobinfo_job_id(info),
+            triggered_writes_slowdown: ffi::rocksdb_flushjobinfo_triggered_writes_slowdown(info)
+                != 0,
+            triggered_writes_stop: ffi::rocksdb_flushjobinfo_triggered_writes_stop(info) != 0,
+            smallest_seqno: ffi::rocksdb_flushjobinfo_smallest_seqno(info),
+            largest_seqno: ffi::rocksdb_flushjobinfo_largest_seqno(info),
+            reason: FlushReason::from_raw(ffi::rocksdb_flushjobinfo_flush_reason(info)),
+            num_entries: ffi::rocksdb_flushjobinfo_num_entries(info),
+            data_size: ffi::rocksdb_flushjobinfo_data_size(info),
+        }
+    }
+}
+
+/// A compaction of table files
+#[derive(Debug, Clone)]
+pub struct CompactionJobInfo {
+    pub cf_id: u32,
+    pub cf_name: String,
+    /// The result of the compaction, always `Ok` when it begins
+    pub status: Result<(), Error>,
+    pub job_id: i32,
+    /// The smallest level of the input files
+    pub base_input_level: i32,
+    pub output_level: i32,
+    pub input_files: Vec<PathBuf>,
+    pub output_files: Vec<PathBuf>,
+    pub reason: CompactionReason,
+    pub elapsed_micros: u64,
+    pub num_corrupt_keys: u64,
+    pub input_records: u64,
+    pub output_records: u64,
+    pub total_input_bytes: u64,
+    pub total_output_bytes: u64,
+}
+
+impl CompactionJobInfo {
+    unsafe fn from_raw(info: *const ffi::rocksdb_compactionjobinfo_t) -> CompactionJobInfo {
+        let input_files = (0..ffi::rocksdb_compactionjobinfo_input_files_count(info))
+            .map(|i| {
+                get_string(|len| ffi::rocksdb_compactionjobinfo_input_file_at(info, i, len)).into()
+            })
+            .collect();
+        let output_files = (0..ffi::rocksdb_compactionjobinfo_output_files_count(info))
+            .map(|i| {
+                get_string(|len| ffi::rocksdb_compactionjobinfo_output_file_at(info, i, len)).into()
+            })
+            .collect();
+
+        CompactionJobInfo {
+            cf_id: ffi::rocksdb_compactionjobinfo_cf_id(info),
+            cf_name: get_string(|len| ffi::rocksdb_compactionjobinfo_cf_name(info, len)),
+            status: get_status(|err| ffi::rocksdb_compactionjobinfo_status(info, err)),
+            job_id: ffi::rocksdb_compactionjobinfo_job_id(info),
+            base_input_level: ffi::rocksdb_compactionjobinfo_base_input_level(info),
+            output_level: ffi::rocksdb_compactionjobinfo_output_level(info),
+            input_files,
+            output_files,
+            reason: CompactionReason::from_raw(ffi::rocksdb_compactionjobinfo_compaction_reason(
+                info,
+            )),
+            elapsed_micros: ffi::rocksdb_compactionjobinfo_elapsed_micros(info),
+            num_corrupt_keys: ffi::rocksdb_compactionjobinfo_num_corrupt_keys(info),
+            input_records: ffi::rocksdb_compactionjobinfo_input_records(info),
+            output_records: ffi::rocksdb_compactionjobinfo_output_records(info),
+            total_input_bytes: ffi::rocksdb_compactionjobinfo_total_input_bytes(info),
+            total_output_bytes: ffi::rocksdb_compactionjobinfo_total_output_bytes(info),
+        }
+    }
+}
+
+/// A change of the write stall condition of a column family
+#[derive(Debug, Clone)]
+pub struct WriteStallInfo {
+    pub cf_name: String,
+    pub current: WriteStallCondition,
+    pub previous: WriteStallCondition,
+}
+
+impl WriteStallInfo {
+    unsafe fn from_raw(info: *const ffi::rocksdb_writestallinfo_t) -> WriteStallInfo {
+        WriteStallInfo {
+            cf_name: get_string(|len| ffi::rocksdb_writestallinfo_cf_name(info, len)),
+            current: WriteStallCondition::from_raw(ffi::rocksdb_writestallinfo_cur(info)),
+            previous: WriteStallCondition::from_raw(ffi::rocksdb_writestallinfo_prev(info)),
+        }
+    }
+}
+
+/// The deletion of a table file
+#[derive(Debug, Clone)]
+pub struct TableFileDeletionInfo {
+    pub db_name: String,
+    pub file_path: PathBuf,
+    pub job_id: i32,
+    pub status: Result<(), Error>,
+}
+
+impl TableFileDeletionInfo {
+    unsafe fn from_raw(info: *const ffi::rocksdb_tablefiledeletioninfo_t) -> TableFileDeletionInfo {
+        TableFileDeletionInfo {
+            db_name: get_string(|len| ffi::rocksdb_tablefiledeletioninfo_db_name(info, len)),
+            file_path: get_string(|len| ffi::rocksdb_tablefiledeletioninfo_file_path(info, len))
+                .into(),
+            job_id: ffi::rocksdb_tablefiledeletioninfo_job_id(info),
+            status: get_status(|err| ffi::rocksdb_tablefiledeletioninfo_status(info, err)),
+        }
+    }
+}
+
+unsafe fn get_string(f: impl FnOnce(*mut size_t) -> *const c_char) -> String {
+    let mut len: size_t = 0;
+    let ptr = f(&mut len);
+    let bytes = slice::from_raw_parts(ptr as *const u8, len);
+    String::from_utf8_lossy(bytes).into_owned()
+}
+
+unsafe fn get_status(f: impl FnOnce(*mut *mut c_char)) -> Result<(), Error> {
+    let mut err: *mut c_char = ptr::null_mut();
+    f(&mut err);
+    if err.is_null() {
+        Ok(())
+    } else {
+        Err(Error::from_status(error_message(err)))
+    }
+}
+
+pub(crate) fn create<L: EventListener>(listener: L) -> *mut ffi::rocksdb_eventlistener_t {
+    unsafe {
+        ffi::rocksdb_eventlistener_create(
+            Box::into_raw(Box::new(listener)).cast::<c_void>(),
+            Some(destructor_callback::<L>),
+            Some(on_flush_begin_callback::<L>),
+            Some(on_flush_completed_callback::<L>),
+            Some(on_compaction_begin_callback::<L>),
+            Some(on_compaction_completed_callback::<L>),
+            Some(on_stall_conditions_changed_callback::<L>),
+            Some(on_background_error_callback::<L>),
+            Some(on_table_file_deleted_callback::<L>),
+        )
+    }
+}
+
+unsafe extern "C" fn destructor_callback<L: EventListener>(raw_self: *mut c_void) {
+    abort_on_panic(|| {
+        drop(Box::from_raw(raw_self as *mut L));
+    })
+}
+
+unsafe extern "C" fn on_flush_begin_callback<L: EventListener>(
+    raw_self: *mut c_void,
+    info: *const ffi::rocksdb_flushjobinfo_t,
+) {
+    let listener = &*(raw_self as *const L);
+    catch_panic(
+        || listener.on_flush_begin(&FlushJobInfo::from_raw(info)),
+        || (),
+    );
+}
+
+unsafe extern "C" fn on_flush_completed_callback<L: EventListener>(
+    raw_self: *mut c_void,
+    info: *const ffi::rocksdb_flushjobinfo_t,
+) {
+    let listener = &*(raw_self as *const L);
+    catch_panic(
+        || listener.on_flush_completed(&FlushJobInfo::from_raw(info)),
+        || (),
+    );
+}
+
+unsafe extern "C" fn on_compaction_begin_callback<L: EventListener>(
+    raw_self: *mut c_void,
+    info: *const ffi::rocksdb_compactionjobinfo_t,
+) {
+    let listener = &*(raw_self as *const L);
+    catch_panic(
+        || listener.on_compaction_begin(&CompactionJobInfo::from_raw(info)),
+        || (),
+    );
+}
+
+unsafe extern "C" fn on_compaction_completed_callback<L: EventListener>(
+    raw_self: *mut c_void,
+    info: *const ffi::rocksdb_compactionjobinfo_t,
+) {
+    let listener = &*(raw_self as *const L);
+    catch_panic(
+        || listener.on_compaction_completed(&CompactionJobInfo::from_raw(info)),
+        || (),
+    );
+}
+
+unsafe extern "C" fn on_stall_conditions_changed_callback<L: EventListener>(
+    raw_self: *mut c_void,
+    info: *const ffi::rocksdb_writestallinfo_t,
+) {
+    let listener = &*(raw_self as *const L);
+    catch_panic(
+        || listener.on_stall_conditions_changed(&WriteStallInfo::from_raw(info)),
+        || (),
+    );
+}
+
+unsafe extern "C" fn on_background_error_callback<L: EventListener>(
+    raw_self: *mut c_void,
+    reason: c_int,
+    error: *const c_char,
+) {
+    let listener = &*(raw_self as *const L);
+    let error = Error::from_status(CStr::from_ptr(error).to_string_lossy().into_owned());
+    catch_panic(
+        || listener.on_background_error(BackgroundErrorReason::from_raw(reason), &error),
+        || (),
+    );
+}
+
+unsafe extern "C" fn on_table_file_deleted_callback<L: EventListener>(
+    raw_self: *mut c_void,
+    info: *const ffi::rocksdb_tablefiledeletioninfo_t,
+) {
+    let listener = &*(raw_self as *const L);
+    catch_panic(
+        || listener.on_table_file_deleted(&TableFileDeletionInfo::from_raw(info)),
+        || (),
+    );
+}
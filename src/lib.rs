@@ -78,47 +78,69 @@ mod db_options;
 mod db_pinnable_slice;
 mod db_vector;
 mod db_with_ttl;
+pub mod event_listener;
 mod handle;
+mod logger;
 pub mod merge_operator;
+mod metadata;
 mod open_raw;
 pub mod ops;
 mod optimistic_transaction;
 mod optimistic_transaction_db;
 mod options;
+mod panic_policy;
+pub mod properties;
 mod read_only_db;
 mod secondary_db;
 mod slice_transform;
 mod snapshot;
+mod sst_file_reader;
 mod sst_file_writer;
+pub mod statistics;
 mod transaction;
 mod transaction_db;
+mod wal_iterator;
 mod write_batch;
+mod write_batch_with_index;
 
 pub mod prelude;
 
 pub use crate::column_family::ColumnFamilyDescriptor;
 pub use crate::compaction_filter::Decision as CompactionDecision;
 pub use crate::db::DB;
-pub use crate::db_iterator::{DBIterator, DBRawIterator, Direction, IteratorMode};
+pub use crate::db_iterator::{
+    DBIterator, DBRawIterator, Direction, FallibleDBIterator, IteratorMode, KVBytes,
+};
 pub use crate::db_options::{
     BlockBasedIndexType, BlockBasedOptions, BottommostLevelCompaction, Cache, CompactOptions,
     CuckooTableOptions, DBCompactionStyle, DBCompressionType, DBPath, DBRecoveryMode,
     DataBlockIndexType, Env, FifoCompactOptions, FlushOptions, IngestExternalFileOptions, LogLevel,
-    MemtableFactory, Options, PlainTableFactoryOptions, ReadOptions, UniversalCompactOptions,
-    UniversalCompactionStopStyle, WriteOptions,
+    MemtableFactory, Options, PlainTableFactoryOptions, RateLimiter, ReadOptions, SstFileManager,
+    UniversalCompactOptions, UniversalCompactionStopStyle, WriteBufferManager, WriteOptions,
 };
 pub use crate::db_pinnable_slice::DBPinnableSlice;
 pub use crate::db_vector::DBVector;
 pub use crate::db_with_ttl::{DBWithTTL, TTLOpenDescriptor};
 pub use crate::handle::{ConstHandle, Handle};
+#[cfg(feature = "log")]
+pub use crate::logger::LogCrateLogger;
+pub use crate::logger::Logger;
+pub use crate::metadata::{
+    ColumnFamilyMetaData, FileDeletionsGuard, LevelMetaData, LiveFile, SstFileMetaData,
+};
 pub use crate::options::FullOptions;
+pub use crate::panic_policy::{panic_policy, set_panic_policy, PanicPolicy};
 pub use crate::read_only_db::ReadOnlyDB;
 pub use crate::secondary_db::{SecondaryDB, SecondaryOpenDescriptor};
 pub use crate::slice_transform::SliceTransform;
 pub use crate::snapshot::Snapshot;
-pub use crate::sst_file_writer::SstFileWriter;
+pub use crate::sst_file_reader::{SstFileReader, TableProperties};
+pub use crate::sst_file_writer::{ExternalSstFileInfo, SstFileWriter};
+pub use crate::statistics::{Histogram, HistogramData, StatsLevel, Ticker};
 pub use crate::util::TemporaryDBPath;
-pub use crate::write_batch::WriteBatch;
+pub use crate::wal_iterator::WalIterator;
+pub use crate::write_batch::{WriteBatch, WriteBatchHandler};
+pub use crate::write_batch_with_index::WriteBatchWithIndex;
 
 pub use crate::merge_operator::MergeOperands;
 use std::error;
@@ -127,18 +149,154 @@ use std::fmt;
 pub use crate::optimistic_transaction::{OptimisticTransaction, OptimisticTransactionSnapshot};
 pub use crate::optimistic_transaction_db::{OptimisticTransactionDB, OptimisticTransactionOptions};
 pub use crate::transaction::{Transaction, TransactionSnapshot};
-pub use crate::transaction_db::{TransactionDB, TransactionDBOptions, TransactionOptions};
+pub use crate::transaction_db::{
+    TransactionDB, TransactionDBOptions, TransactionOptions, TxnDBWritePolicy,
+};
+
+/// The category of an error, mirroring the `Code` of a RocksDB `Status`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum ErrorKind {
+    NotFound,
+    Corruption,
+    NotSupported,
+    InvalidArgument,
+    IOError,
+    MergeInProgress,
+    Incomplete,
+    ShutdownInProgress,
+    TimedOut,
+    Aborted,
+    Busy,
+    Expired,
+    TryAgain,
+    CompactionTooLarge,
+    ColumnFamilyDropped,
+    /// The error was not reported by RocksDB, or its code could not be recognised.
+    Unknown,
+}
+
+/// Additional detail for some error kinds, mirroring the `SubCode` of a RocksDB `Status`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum SubCode {
+    None,
+    MutexTimeout,
+    LockTimeout,
+    LockLimit,
+    NoSpace,
+    Deadlock,
+    StaleFile,
+    MemoryLimit,
+    SpaceLimit,
+    PathNotFound,
+    MergeOperandsInsufficientCapacity,
+    ManualCompactionPaused,
+    TxnNotPrepared,
+    IOFenced,
+}
 
-/// A simple wrapper round a string, used for errors reported from
-/// ffi calls.
+// Prefixes written by `Status::ToString()` for every status code.
+const ERROR_KIND_PREFIXES: &[(&str, ErrorKind)] = &[
+    ("NotFound: ", ErrorKind::NotFound),
+    ("Corruption: ", ErrorKind::Corruption),
+    ("Not implemented: ", ErrorKind::NotSupported),
+    ("Invalid argument: ", ErrorKind::InvalidArgument),
+    ("IO error: ", ErrorKind::IOError),
+    ("Merge in progress: ", ErrorKind::MergeInProgress),
+    ("Result incomplete: ", ErrorKind::Incomplete),
+    ("Shutdown in progress: ", ErrorKind::ShutdownInProgress),
+    ("Operation timed out: ", ErrorKind::TimedOut),
+    ("Operation aborted: ", ErrorKind::Aborted),
+    ("Resource busy: ", ErrorKind::Busy),
+    ("Operation expired: ", ErrorKind::Expired),
+    ("Operation failed. Try again.: ", ErrorKind::TryAgain),
+    ("Compaction too large: ", ErrorKind::CompactionTooLarge),
+    ("Column family dropped: ", ErrorKind::ColumnFamilyDropped),
+];
+
+// Messages appended by `Status::ToString()` right after the code prefix.
+const SUB_CODE_MESSAGES: &[(&str, SubCode)] = &[
+    ("Timeout Acquiring Mutex", SubCode::MutexTimeout),
+    ("Timeout waiting to lock key", SubCode::LockTimeout),
+    (
+        "Failed to acquire lock due to max_num_locks limit",
+        SubCode::LockLimit,
+    ),
+    ("No space left on device", SubCode::NoSpace),
+    ("Deadlock", SubCode::Deadlock),
+    ("Stale file handle", SubCode::StaleFile),
+    ("Memory limit reached", SubCode::MemoryLimit),
+    ("Space limit reached", SubCode::SpaceLimit),
+    ("No such file or directory", SubCode::PathNotFound),
+    (
+        "Insufficient capacity for merge operands",
+        SubCode::MergeOperandsInsufficientCapacity,
+    ),
+    ("Manual compaction paused", SubCode::ManualCompactionPaused),
+    ("Txn not prepared", SubCode::TxnNotPrepared),
+    ("IO fenced off", SubCode::IOFenced),
+];
+
+fn parse_status_message(message: &str) -> (ErrorKind, SubCode) {
+    let (kind, rest) = match ERROR_KIND_PREFIXES
+        .iter()
+        .find(|(prefix, _)| message.starts_with(prefix))
+    {
+        Some((prefix, kind)) => (*kind, &message[prefix.len()..]),
+        None => return (ErrorKind::Unknown, SubCode::None),
+    };
+
+    let subcode = SUB_CODE_MESSAGES
+        .iter()
+        .find(|(msg, _)| {
+            rest.starts_with(msg)
+                && (rest.len() == msg.len() || rest[msg.len()..].starts_with(": "))
+        })
+        .map_or(SubCode::None, |(_, subcode)| *subcode);
+
+    (kind, subcode)
+}
+
+/// An error reported from ffi calls.
+///
+/// The message RocksDB produced is kept as-is, and its status code and subcode
+/// are available through `kind()` and `subcode()`.
 #[derive(Debug, Clone, PartialEq)]
 pub struct Error {
+    kind: ErrorKind,
+    subcode: SubCode,
     message: String,
 }
 
 impl Error {
+    /// Creates an error from a message. Its kind is `ErrorKind::Unknown`, as
+    /// the error was not reported by RocksDB.
     pub fn new(message: String) -> Error {
-        Error { message }
+        Error {
+            kind: ErrorKind::Unknown,
+            subcode: SubCode::None,
+            message,
+        }
+    }
+
+    /// Creates an error from a status message produced by RocksDB, recognising
+    /// its status code and subcode.
+    pub(crate) fn from_status(message: String) -> Error {
+        let (kind, subcode) = parse_status_message(&message);
+        Error {
+            kind,
+            subcode,
+            message,
+        }
+    }
+
+    /// Returns the status code of this error.
+    pub fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+
+    /// Returns the status subcode of this error.
+    pub fn subcode(&self) -> SubCode {
+        self.subcode
     }
 
     pub fn into_string(self) -> String {
@@ -177,3 +335,37 @@ pub struct ColumnFamily {
 }
 
 unsafe impl Send for ColumnFamily {}
+
+#[test]
+fn error_kind_from_status_message() {
+    let e = Error::from_status("Resource busy: ".to_owned());
+    assert_eq!(e.kind(), ErrorKind::Busy);
+    assert_eq!(e.subcode(), SubCode::None);
+
+    let e = Error::from_status("Corruption: block checksum mismatch".to_owned());
+    assert_eq!(e.kind(), ErrorKind::Corruption);
+    assert_eq!(e.subcode(), SubCode::None);
+    assert_eq!(e.as_ref(), "Corruption: block checksum mismatch");
+
+    let e = Error::from_status("IO error: No space left on device: While appending".to_owned());
+    assert_eq!(e.kind(), ErrorKind::IOError);
+    assert_eq!(e.subcode(), SubCode::NoSpace);
+
+    let e = Error::from_status(
+        "Resource busy: Failed to acquire lock due to max_num_locks limit".to_owned(),
+    );
+    assert_eq!(e.kind(), ErrorKind::Busy);
+    assert_eq!(e.subcode(), SubCode::LockLimit);
+
+    let e = Error::from_status("Operation failed. Try again.: ".to_owned());
+    assert_eq!(e.kind(), ErrorKind::TryAgain);
+
+    let e = Error::from_status("Could not create Cache".to_owned());
+    assert_eq!(e.kind(), ErrorKind::Unknown);
+    assert_eq!(e.subcode(), SubCode::None);
+
+    // Messages built on the Rust side are never parsed.
+    let e = Error::new("IO error: No space left on device".to_owned());
+    assert_eq!(e.kind(), ErrorKind::Unknown);
+    assert_eq!(e.subcode(), SubCode::None);
+}
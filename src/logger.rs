@@ -0,0 +1,121 @@
+// Copyright 2019 Tyler Neely
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+use crate::ffi;
+use crate::panic_policy::{abort_on_panic, catch_panic};
+use crate::LogLevel;
+
+use libc::{c_char, c_int, c_void, size_t};
+use std::slice;
+
+/// Receives the messages RocksDB writes to its info log.
+///
+/// Any `Fn(LogLevel, &str)` closure which is `Send + Sync` is a logger.
+/// Messages are passed without a trailing newline. Loggers are called from
+/// foreground and background threads alike, so they should be quick.
+///
+/// # Examples
+///
+/// ```
+/// use ckb_rocksdb::{prelude::*, LogLevel, TemporaryDBPath};
+///
+/// let mut opts = Options::default();
+/// opts.create_if_missing(true);
+/// opts.set_logger(LogLevel::Info, |level: LogLevel, message: &str| {
+///     eprintln!("[rocksdb {:?}] {}", level, message);
+/// });
+///
+/// let path = TemporaryDBPath::new();
+/// let db = DB::open(&opts, &path).unwrap();
+/// ```
+pub trait Logger: Send + Sync {
+    fn log(&self, level: LogLevel, message: &str);
+}
+
+impl<F> Logger for F
+where
+    F: Fn(LogLevel, &str) + Send + Sync,
+{
+    fn log(&self, level: LogLevel, message: &str) {
+        self(level, message)
+    }
+}
+
+/// A [`Logger`] forwarding messages to the [`log`](https://docs.rs/log) crate,
+/// under the `rocksdb` target.
+///
+/// `Fatal` messages are logged as errors, and header messages, which describe
+/// the options the database was opened with, as infos.
+#[cfg(feature = "log")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LogCrateLogger;
+
+#[cfg(feature = "log")]
+impl Logger for LogCrateLogger {
+    fn log(&self, level: LogLevel, message: &str) {
+        let level = match level {
+            LogLevel::Debug => log::Level::Debug,
+            LogLevel::Info | LogLevel::Header => log::Level::Info,
+            LogLevel::Warn => log::Level::Warn,
+            LogLevel::Error | LogLevel::Fatal => log::Level::Error,
+        };
+        log::log!(target: "rocksdb", level, "{}", message);
+    }
+}
+
+fn log_level_from_raw(level: c_int) -> LogLevel {
+    match level {
+        0 => LogLevel::Debug,
+        1 => LogLevel::Info,
+        2 => LogLevel::Warn,
+        3 => LogLevel::Error,
+        4 => LogLevel::Fatal,
+        _ => LogLevel::Header,
+    }
+}
+
+pub(crate) fn create<L: Logger>(level: LogLevel, logger: L) -> *mut ffi::rocksdb_logger_t {
+    unsafe {
+        ffi::rocksdb_logger_create_callback_logger(
+            level as c_int,
+            Box::into_raw(Box::new(logger)).cast::<c_void>(),
+            Some(destructor_callback::<L>),
+            Some(log_callback::<L>),
+        )
+    }
+}
+
+unsafe extern "C" fn destructor_callback<L: Logger>(raw_self: *mut c_void) {
+    abort_on_panic(|| {
+        drop(Box::from_raw(raw_self as *mut L));
+    })
+}
+
+unsafe extern "C" fn log_callback<L: Logger>(
+    raw_self: *mut c_void,
+    level: c_int,
+    msg: *const c_char,
+    len: size_t,
+) {
+    catch_panic(
+        || {
+            let logger = &*(raw_self as *const L);
+            let msg = slice::from_raw_parts(msg as *const u8, len);
+            let msg = String::from_utf8_lossy(msg);
+            logger.log(log_level_from_raw(level), msg.trim_end_matches('\n'));
+        },
+        || (),
+    )
+}
@@ -65,6 +65,8 @@ use std::mem;
 use std::ptr;
 use std::slice;
 
+use crate::panic_policy::{abort_on_panic, catch_panic};
+
 pub trait MergeFn:
     Fn(&[u8], Option<&[u8]>, &mut MergeOperands) -> Option<Vec<u8>> + Send + Sync + 'static
 {
@@ -81,8 +83,10 @@ pub struct MergeOperatorCallback<F: MergeFn, PF: MergeFn> {
 }
 
 pub unsafe extern "C" fn destructor_callback<F: MergeFn, PF: MergeFn>(raw_cb: *mut c_void) {
-    let _: Box<MergeOperatorCallback<F, PF>> =
-        Box::from_raw(raw_cb as *mut MergeOperatorCallback<F, PF>);
+    abort_on_panic(|| {
+        let _: Box<MergeOperatorCallback<F, PF>> =
+            Box::from_raw(raw_cb as *mut MergeOperatorCallback<F, PF>);
+    })
 }
 
 pub unsafe extern "C" fn delete_callback(
@@ -128,7 +132,7 @@ pub unsafe extern "C" fn full_merge_callback<F: MergeFn, PF: MergeFn>(
             existing_value_len as usize,
         ))
     };
-    (cb.full_merge_fn)(key, oldval, operands).map_or_else(
+    catch_panic(|| (cb.full_merge_fn)(key, oldval, operands), || None).map_or_else(
         || {
             *new_value_length = 0;
             *success = 0_u8;
@@ -155,7 +159,7 @@ pub unsafe extern "C" fn partial_merge_callback<F: MergeFn, PF: MergeFn>(
     let cb = &mut *(raw_cb as *mut MergeOperatorCallback<F, PF>);
     let operands = &mut MergeOperands::new(operands_list, operands_list_len, num_operands);
     let key = slice::from_raw_parts(raw_key as *const u8, key_len as usize);
-    (cb.partial_merge_fn)(key, None, operands).map_or_else(
+    catch_panic(|| (cb.partial_merge_fn)(key, None, operands), || None).map_or_else(
         || {
             *new_value_length = 0;
             *success = 0_u8;
@@ -0,0 +1,208 @@
+// Copyright 2019 Tyler Neely
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+use crate::ffi;
+use crate::ops::FileDeletions;
+
+use libc::{c_char, c_void, size_t};
+use std::ffi::CStr;
+use std::path::PathBuf;
+use std::slice;
+
+/// A live SST file, as reported by `GetLiveFilesMetadata::get_live_files_metadata`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LiveFile {
+    /// Name of the column family the file belongs to.
+    pub column_family_name: String,
+    /// File name, relative to `directory`.
+    pub name: String,
+    /// Directory the file is stored in.
+    pub directory: PathBuf,
+    /// LSM level the file belongs to.
+    pub level: i32,
+    /// File size in bytes.
+    pub size: u64,
+    pub smallest_key: Vec<u8>,
+    pub largest_key: Vec<u8>,
+    pub smallest_seqno: u64,
+    pub largest_seqno: u64,
+    /// Number of entries, deletions included.
+    pub num_entries: u64,
+    pub num_deletions: u64,
+}
+
+/// The LSM tree of a column family, as reported by
+/// `GetColumnFamilyMetadata::get_column_family_metadata`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColumnFamilyMetaData {
+    pub name: String,
+    /// Total size of the SST files in bytes.
+    pub size: u64,
+    /// Total number of SST files.
+    pub file_count: usize,
+    /// One entry per level, empty levels included.
+    pub levels: Vec<LevelMetaData>,
+}
+
+/// A level of a column family's LSM tree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LevelMetaData {
+    pub level: i32,
+    /// Total size of the level's SST files in bytes.
+    pub size: u64,
+    pub files: Vec<SstFileMetaData>,
+}
+
+/// An SST file of a column family's LSM tree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SstFileMetaData {
+    /// File name, relative to `directory`.
+    pub name: String,
+    /// Directory the file is stored in.
+    pub directory: PathBuf,
+    /// File size in bytes.
+    pub size: u64,
+    pub smallest_key: Vec<u8>,
+    pub largest_key: Vec<u8>,
+    pub smallest_seqno: u64,
+    pub largest_seqno: u64,
+    /// Number of entries, deletions included.
+    pub num_entries: u64,
+    pub num_deletions: u64,
+}
+
+/// Keeps the file deletions of a database disabled until dropped, see
+/// `FileDeletions::disable_file_deletions`.
+pub struct FileDeletionsGuard<'a, D: FileDeletions + ?Sized> {
+    pub(crate) db: &'a D,
+}
+
+impl<D: FileDeletions + ?Sized> Drop for FileDeletionsGuard<'_, D> {
+    fn drop(&mut self) {
+        // Enabling file deletions without force cannot fail.
+        let _ = self.db.enable_file_deletions(false);
+    }
+}
+
+pub(crate) unsafe fn live_files(db: *mut ffi::rocksdb_t) -> Vec<LiveFile> {
+    let lf = ffi::rocksdb_livefiles(db);
+    let count = ffi::rocksdb_livefiles_count(lf);
+    let files = (0..count)
+        .map(|i| {
+            let mut len: size_t = 0;
+            let ptr = ffi::rocksdb_livefiles_smallestkey(lf, i, &mut len);
+            let smallest_key = slice::from_raw_parts(ptr as *const u8, len).to_vec();
+            let ptr = ffi::rocksdb_livefiles_largestkey(lf, i, &mut len);
+            let largest_key = slice::from_raw_parts(ptr as *const u8, len).to_vec();
+            LiveFile {
+                column_family_name: borrowed_string(ffi::rocksdb_livefiles_column_family_name(
+                    lf, i,
+                )),
+                name: borrowed_string(ffi::rocksdb_livefiles_name(lf, i)),
+                directory: PathBuf::from(borrowed_string(ffi::rocksdb_livefiles_directory(lf, i))),
+                level: ffi::rocksdb_livefiles_level(lf, i),
+                size: ffi::rocksdb_livefiles_size(lf, i) as u64,
+                smallest_key,
+                largest_key,
+                smallest_seqno: ffi::rocksdb_livefiles_smallest_seqno(lf, i),
+                largest_seqno: ffi::rocksdb_livefiles_largest_seqno(lf, i),
+                num_entries: ffi::rocksdb_livefiles_entries(lf, i),
+                num_deletions: ffi::rocksdb_livefiles_deletions(lf, i),
+            }
+        })
+        .collect();
+    ffi::rocksdb_livefiles_destroy(lf);
+    files
+}
+
+pub(crate) unsafe fn column_family_metadata(
+    db: *mut ffi::rocksdb_t,
+    cf: *mut ffi::rocksdb_column_family_handle_t,
+) -> ColumnFamilyMetaData {
+    let cf_meta = ffi::rocksdb_get_column_family_metadata_cf(db, cf);
+    let level_count = ffi::rocksdb_column_family_metadata_get_level_count(cf_meta);
+    let levels = (0..level_count)
+        .map(|i| {
+            let level_meta = ffi::rocksdb_column_family_metadata_get_level_metadata(cf_meta, i);
+            let file_count = ffi::rocksdb_level_metadata_get_file_count(level_meta);
+            let files = (0..file_count)
+                .map(|j| {
+                    let file_meta =
+                        ffi::rocksdb_level_metadata_get_sst_file_metadata(level_meta, j);
+                    let file = sst_file_metadata(file_meta);
+                    ffi::rocksdb_sst_file_metadata_destroy(file_meta);
+                    file
+                })
+                .collect();
+            let level = LevelMetaData {
+                level: ffi::rocksdb_level_metadata_get_level(level_meta),
+                size: ffi::rocksdb_level_metadata_get_size(level_meta),
+                files,
+            };
+            ffi::rocksdb_level_metadata_destroy(level_meta);
+            level
+        })
+        .collect();
+    let metadata = ColumnFamilyMetaData {
+        name: owned_string(ffi::rocksdb_column_family_metadata_get_name(cf_meta)),
+        size: ffi::rocksdb_column_family_metadata_get_size(cf_meta),
+        file_count: ffi::rocksdb_column_family_metadata_get_file_count(cf_meta),
+        levels,
+    };
+    ffi::rocksdb_column_family_metadata_destroy(cf_meta);
+    metadata
+}
+
+unsafe fn sst_file_metadata(file_meta: *mut ffi::rocksdb_sst_file_metadata_t) -> SstFileMetaData {
+    let mut len: size_t = 0;
+    let ptr = ffi::rocksdb_sst_file_metadata_get_smallestkey(file_meta, &mut len);
+    let smallest_key = owned_bytes(ptr, len);
+    let ptr = ffi::rocksdb_sst_file_metadata_get_largestkey(file_meta, &mut len);
+    let largest_key = owned_bytes(ptr, len);
+    SstFileMetaData {
+        name: owned_string(ffi::rocksdb_sst_file_metadata_get_relative_filename(
+            file_meta,
+        )),
+        directory: PathBuf::from(owned_string(ffi::rocksdb_sst_file_metadata_get_directory(
+            file_meta,
+        ))),
+        size: ffi::rocksdb_sst_file_metadata_get_size(file_meta),
+        smallest_key,
+        largest_key,
+        smallest_seqno: ffi::rocksdb_sst_file_metadata_get_smallest_seqno(file_meta),
+        largest_seqno: ffi::rocksdb_sst_file_metadata_get_largest_seqno(file_meta),
+        num_entries: ffi::rocksdb_sst_file_metadata_get_num_entries(file_meta),
+        num_deletions: ffi::rocksdb_sst_file_metadata_get_num_deletions(file_meta),
+    }
+}
+
+unsafe fn borrowed_string(ptr: *const c_char) -> String {
+    CStr::from_ptr(ptr).to_string_lossy().into_owned()
+}
+
+unsafe fn owned_string(ptr: *mut c_char) -> String {
+    let s = borrowed_string(ptr);
+    libc::free(ptr as *mut c_void);
+    s
+}
+
+unsafe fn owned_bytes(ptr: *mut c_char, len: size_t) -> Vec<u8> {
+    if ptr.is_null() {
+        return Vec::new();
+    }
+    let bytes = slice::from_raw_parts(ptr as *const u8, len).to_vec();
+    libc::free(ptr as *mut c_void);
+    bytes
+}
@@ -79,6 +79,22 @@ pub trait OpenRaw: Sized {
             .map(|cf| CString::new(cf.name.as_bytes()).unwrap())
             .collect();
 
+        for (cf, name) in cfs.iter_mut().zip(&cf_names) {
+            cf.options.set_compaction_filter_factory_cf_name(name);
+        }
+
+        // Without descriptors only the default column family is opened, with
+        // the database options.
+        let mut default_options;
+        let options = if cfs.is_empty() {
+            default_options = input.options.clone();
+            default_options
+                .set_compaction_filter_factory_cf_name(&CString::new("default").unwrap());
+            &default_options
+        } else {
+            input.options
+        };
+
         let mut cf_names: Vec<_> = cf_names.iter().map(|cf| cf.as_ptr()).collect();
         let mut cf_options: Vec<_> = cfs.iter().map(|cf| cf.options.inner as *const _).collect();
 
@@ -88,7 +104,7 @@ pub trait OpenRaw: Sized {
                 .collect();
 
         let pointer = Self::open_ffi(OpenRawFFI {
-            options: input.options.inner,
+            options: options.inner,
             path: cpath.as_ptr(),
             num_column_families: cf_names.len() as c_int,
             column_family_names: cf_names.as_mut_ptr(),
@@ -1,7 +1,9 @@
 use crate::ffi;
 
+use crate::checkpoint::{ExportImportFilesMetaData, RawExportImportFilesMetaData};
 use crate::{ffi_util::to_cstring, handle::Handle, ColumnFamily, Error, Options};
 
+use libc::c_uchar;
 use std::collections::BTreeMap;
 
 pub trait GetColumnFamilys {
@@ -17,6 +19,27 @@ pub trait GetColumnFamilys {
 
 pub trait CreateCF {
     fn create_cf<N: AsRef<str>>(&mut self, name: N, opts: &Options) -> Result<(), Error>;
+
+    /// Creates a column family from the files described by `metadata`, as
+    /// produced by `Checkpoint::export_column_family`.
+    ///
+    /// The files are moved instead of copied when `move_files` is set; they
+    /// must then live on the same file system as the database.
+    ///
+    /// The default implementation returns a `NotSupported` error, so that
+    /// implementors outside this crate keep compiling; every database of this
+    /// crate which can create column families overrides it.
+    fn create_cf_with_import<N: AsRef<str>>(
+        &mut self,
+        _name: N,
+        _opts: &Options,
+        _metadata: &ExportImportFilesMetaData,
+        _move_files: bool,
+    ) -> Result<(), Error> {
+        Err(Error::from_status(
+            "Not implemented: importing a column family".to_owned(),
+        ))
+    }
 }
 
 pub trait DropCF {
@@ -32,6 +55,8 @@ where
             name.as_ref(),
             "Failed to convert path to CString when opening rocksdb",
         )?;
+        let mut opts = opts.clone();
+        opts.set_compaction_filter_factory_cf_name(&cname);
         unsafe {
             let cf_handle = ffi_try!(ffi::rocksdb_create_column_family(
                 self.handle(),
@@ -44,6 +69,35 @@ where
         };
         Ok(())
     }
+
+    fn create_cf_with_import<N: AsRef<str>>(
+        &mut self,
+        name: N,
+        opts: &Options,
+        metadata: &ExportImportFilesMetaData,
+        move_files: bool,
+    ) -> Result<(), Error> {
+        let cname = to_cstring(
+            name.as_ref(),
+            "Failed to convert path to CString when opening rocksdb",
+        )?;
+        let metadata = RawExportImportFilesMetaData::from_metadata(metadata)?;
+        let mut opts = opts.clone();
+        opts.set_compaction_filter_factory_cf_name(&cname);
+        unsafe {
+            let cf_handle = ffi_try!(ffi::rocksdb_create_column_family_with_import(
+                self.handle(),
+                opts.inner,
+                cname.as_ptr(),
+                move_files as c_uchar,
+                metadata.inner,
+            ));
+
+            self.get_mut_cfs()
+                .insert(name.as_ref().to_string(), ColumnFamily::new(cf_handle));
+        };
+        Ok(())
+    }
 }
 
 impl<T> DropCF for T
@@ -1,13 +1,32 @@
 use super::columnfamily::GetColumnFamilys;
-use crate::{ffi_util::opt_bytes_to_ptr, handle::Handle, ColumnFamily};
+use crate::{ffi_util::opt_bytes_to_ptr, handle::Handle, ColumnFamily, CompactOptions, Error};
 use libc::size_t;
 
 pub trait CompactRange {
     fn compact_range<S: AsRef<[u8]>, E: AsRef<[u8]>>(&self, start: Option<S>, end: Option<E>);
+
+    /// Same as `compact_range` but with custom options, reporting failures
+    /// such as a cancellation through `CompactOptions::set_canceled`.
+    fn compact_range_opt<S: AsRef<[u8]>, E: AsRef<[u8]>>(
+        &self,
+        start: Option<S>,
+        end: Option<E>,
+        opts: &CompactOptions,
+    ) -> Result<(), Error>;
 }
 
 pub trait CompactRangeCF {
     fn compact_range_cf(&self, cf: &ColumnFamily, start: Option<&[u8]>, end: Option<&[u8]>);
+
+    /// Same as `compact_range_cf` but with custom options, reporting failures
+    /// such as a cancellation through `CompactOptions::set_canceled`.
+    fn compact_range_cf_opt(
+        &self,
+        cf: &ColumnFamily,
+        start: Option<&[u8]>,
+        end: Option<&[u8]>,
+        opts: &CompactOptions,
+    ) -> Result<(), Error>;
 }
 
 impl<T> CompactRange for T
@@ -28,6 +47,28 @@ where
             );
         }
     }
+
+    fn compact_range_opt<S: AsRef<[u8]>, E: AsRef<[u8]>>(
+        &self,
+        start: Option<S>,
+        end: Option<E>,
+        opts: &CompactOptions,
+    ) -> Result<(), Error> {
+        unsafe {
+            let start = start.as_ref().map(AsRef::as_ref);
+            let end = end.as_ref().map(AsRef::as_ref);
+
+            ffi_try!(ffi::rocksdb_compact_range_opt_with_status(
+                self.handle(),
+                opts.inner,
+                opt_bytes_to_ptr(start),
+                start.map_or(0, |s| s.len()) as size_t,
+                opt_bytes_to_ptr(end),
+                end.map_or(0, |e| e.len()) as size_t,
+            ));
+            Ok(())
+        }
+    }
 }
 
 impl<T> CompactRangeCF for T
@@ -46,4 +87,25 @@ where
             );
         }
     }
+
+    fn compact_range_cf_opt(
+        &self,
+        cf: &ColumnFamily,
+        start: Option<&[u8]>,
+        end: Option<&[u8]>,
+        opts: &CompactOptions,
+    ) -> Result<(), Error> {
+        unsafe {
+            ffi_try!(ffi::rocksdb_compact_range_cf_opt_with_status(
+                self.handle(),
+                cf.inner,
+                opts.inner,
+                opt_bytes_to_ptr(start),
+                start.map_or(0, |s| s.len()) as size_t,
+                opt_bytes_to_ptr(end),
+                end.map_or(0, |e| e.len()) as size_t,
+            ));
+            Ok(())
+        }
+    }
 }
@@ -14,9 +14,11 @@
 //
 
 use crate::ffi;
+use crate::ffi_util::error_message;
 use libc::{c_char, size_t};
+use std::ptr;
 
-use crate::{handle::Handle, ColumnFamily, DBVector, Error, ReadOptions};
+use crate::{handle::Handle, ColumnFamily, DBPinnableSlice, DBVector, Error, ReadOptions};
 
 pub trait Get<R> {
     fn get_full<K: AsRef<[u8]>>(
@@ -33,6 +35,33 @@ pub trait Get<R> {
     fn get_opt<K: AsRef<[u8]>>(&self, key: K, readopts: &R) -> Result<Option<DBVector>, Error> {
         self.get_full(key, Some(readopts))
     }
+
+    fn multi_get_full<K, I>(
+        &self,
+        keys: I,
+        readopts: Option<&R>,
+    ) -> Vec<Result<Option<DBVector>, Error>>
+    where
+        K: AsRef<[u8]>,
+        I: IntoIterator<Item = K>;
+
+    /// Return the values associated with the given keys, in the order
+    /// the keys were passed in.
+    fn multi_get<K, I>(&self, keys: I) -> Vec<Result<Option<DBVector>, Error>>
+    where
+        K: AsRef<[u8]>,
+        I: IntoIterator<Item = K>,
+    {
+        self.multi_get_full(keys, None)
+    }
+
+    fn multi_get_opt<K, I>(&self, keys: I, readopts: &R) -> Vec<Result<Option<DBVector>, Error>>
+    where
+        K: AsRef<[u8]>,
+        I: IntoIterator<Item = K>,
+    {
+        self.multi_get_full(keys, Some(readopts))
+    }
 }
 
 pub trait GetCF<R> {
@@ -55,6 +84,100 @@ pub trait GetCF<R> {
     ) -> Result<Option<DBVector>, Error> {
         self.get_cf_full(Some(cf), key, Some(readopts))
     }
+
+    /// Return the values associated with the given keys, each of which is
+    /// looked up in its own column family (`None` being the default one).
+    ///
+    /// The default implementation issues one lookup per key, implementors
+    /// backed by RocksDB's `MultiGet` override it.
+    fn multi_get_cf_full<'c, K, I>(
+        &self,
+        keys: I,
+        readopts: Option<&R>,
+    ) -> Vec<Result<Option<DBVector>, Error>>
+    where
+        K: AsRef<[u8]>,
+        I: IntoIterator<Item = (Option<&'c ColumnFamily>, K)>,
+    {
+        keys.into_iter()
+            .map(|(cf, key)| self.get_cf_full(cf, key, readopts))
+            .collect()
+    }
+
+    fn multi_get_cf<'c, K, I>(&self, keys: I) -> Vec<Result<Option<DBVector>, Error>>
+    where
+        K: AsRef<[u8]>,
+        I: IntoIterator<Item = (&'c ColumnFamily, K)>,
+    {
+        self.multi_get_cf_full(keys.into_iter().map(|(cf, key)| (Some(cf), key)), None)
+    }
+
+    fn multi_get_cf_opt<'c, K, I>(
+        &self,
+        keys: I,
+        readopts: &R,
+    ) -> Vec<Result<Option<DBVector>, Error>>
+    where
+        K: AsRef<[u8]>,
+        I: IntoIterator<Item = (&'c ColumnFamily, K)>,
+    {
+        self.multi_get_cf_full(
+            keys.into_iter().map(|(cf, key)| (Some(cf), key)),
+            Some(readopts),
+        )
+    }
+
+    /// Return the values associated with the given keys in a single column
+    /// family using RocksDB's batched `MultiGet`. Setting `sorted_input` tells
+    /// RocksDB the keys are already sorted, saving it a sort of its own.
+    ///
+    /// The default implementation issues one lookup per key and copies the
+    /// values, implementors backed by RocksDB's `MultiGet` override it.
+    fn batched_multi_get_cf_full<K, I>(
+        &self,
+        cf: &ColumnFamily,
+        keys: I,
+        _sorted_input: bool,
+        readopts: Option<&R>,
+    ) -> Vec<Result<Option<DBPinnableSlice<'_>>, Error>>
+    where
+        K: AsRef<[u8]>,
+        I: IntoIterator<Item = K>,
+    {
+        keys.into_iter()
+            .map(|key| {
+                self.get_cf_full(Some(cf), key, readopts)
+                    .map(|value| value.map(|value| DBPinnableSlice::copy_from(&value)))
+            })
+            .collect()
+    }
+
+    fn batched_multi_get_cf<K, I>(
+        &self,
+        cf: &ColumnFamily,
+        keys: I,
+        sorted_input: bool,
+    ) -> Vec<Result<Option<DBPinnableSlice<'_>>, Error>>
+    where
+        K: AsRef<[u8]>,
+        I: IntoIterator<Item = K>,
+    {
+        self.batched_multi_get_cf_full(cf, keys, sorted_input, None)
+    }
+
+    fn batched_multi_get_cf_opt<K, I>(
+        &self,
+        cf: &ColumnFamily,
+        keys: I,
+        sorted_input: bool,
+        readopts: &R,
+    ) -> Vec<Result<Option<DBPinnableSlice<'_>>, Error>>
+    where
+        K: AsRef<[u8]>,
+        I: IntoIterator<Item = K>,
+    {
+        self.batched_multi_get_cf_full(cf, keys, sorted_input, Some(readopts))
+    }
 }
 
 impl<T, R> Get<R> for T
@@ -68,6 +191,159 @@ where
     ) -> Result<Option<DBVector>, Error> {
         self.get_cf_full(None, key, readopts)
     }
+
+    fn multi_get_full<K, I>(
+        &self,
+        keys: I,
+        readopts: Option<&R>,
+    ) -> Vec<Result<Option<DBVector>, Error>>
+    where
+        K: AsRef<[u8]>,
+        I: IntoIterator<Item = K>,
+    {
+        self.multi_get_cf_full(keys.into_iter().map(|key| (None, key)), readopts)
+    }
+}
+
+/// Raw argument arrays of a single `multi_get` FFI call, `cfs` is `None` when
+/// every key belongs to the default column family.
+pub(crate) struct MultiGetArgs<'a> {
+    pub cfs: Option<&'a [*const ffi::rocksdb_column_family_handle_t]>,
+    pub keys: &'a [*const c_char],
+    pub keys_sizes: &'a [size_t],
+    pub values: &'a mut [*mut c_char],
+    pub values_sizes: &'a mut [size_t],
+    pub errs: &'a mut [*mut c_char],
+}
+
+/// Drives RocksDB's `MultiGet` for keys spread over column families.
+///
+/// Keys of the default column family and keys with an explicit handle are
+/// resolved with (at most) one `call` each, results come back in input order.
+/// `call` receives the read options handle along with the raw arrays.
+pub(crate) fn multi_get_with<'c, K, I, F>(
+    keys: I,
+    readopts: Option<&ReadOptions>,
+    mut call: F,
+) -> Vec<Result<Option<DBVector>, Error>>
+where
+    K: AsRef<[u8]>,
+    I: IntoIterator<Item = (Option<&'c ColumnFamily>, K)>,
+    F: FnMut(*mut ffi::rocksdb_readoptions_t, MultiGetArgs),
+{
+    let keys: Vec<_> = keys.into_iter().collect();
+
+    let mut default_readopts = None;
+    let ro_handle = match ReadOptions::input_or_default(readopts, &mut default_readopts) {
+        Ok(ro_handle) => ro_handle,
+        Err(e) => return keys.iter().map(|_| Err(e.clone())).collect(),
+    };
+
+    let (default_idx, cf_idx): (Vec<usize>, Vec<usize>) =
+        (0..keys.len()).partition(|&i| keys[i].0.is_none());
+
+    let mut results: Vec<Option<Result<Option<DBVector>, Error>>> =
+        keys.iter().map(|_| None).collect();
+
+    for idx in [default_idx, cf_idx] {
+        if idx.is_empty() {
+            continue;
+        }
+
+        let cfs: Vec<_> = idx
+            .iter()
+            .filter_map(|&i| keys[i].0)
+            .map(|cf| cf.inner as *const _)
+            .collect();
+        let keys_ptrs: Vec<_> = idx
+            .iter()
+            .map(|&i| keys[i].1.as_ref().as_ptr() as *const c_char)
+            .collect();
+        let keys_sizes: Vec<_> = idx
+            .iter()
+            .map(|&i| keys[i].1.as_ref().len() as size_t)
+            .collect();
+        let mut values = vec![ptr::null_mut(); idx.len()];
+        let mut values_sizes = vec![0; idx.len()];
+        let mut errs = vec![ptr::null_mut(); idx.len()];
+
+        call(
+            ro_handle,
+            MultiGetArgs {
+                cfs: if cfs.is_empty() { None } else { Some(&cfs) },
+                keys: &keys_ptrs,
+                keys_sizes: &keys_sizes,
+                values: &mut values,
+                values_sizes: &mut values_sizes,
+                errs: &mut errs,
+            },
+        );
+
+        for (n, &i) in idx.iter().enumerate() {
+            results[i] = Some(if !errs[n].is_null() {
+                Err(Error::from_status(error_message(errs[n])))
+            } else if values[n].is_null() {
+                Ok(None)
+            } else {
+                Ok(Some(unsafe {
+                    DBVector::from_c(values[n] as *mut u8, values_sizes[n])
+                }))
+            });
+        }
+    }
+
+    results.into_iter().flatten().collect()
+}
+
+/// Drives RocksDB's batched `MultiGet`, `call` receives the read options
+/// handle, the key pointers and sizes and the value and error output arrays.
+pub(crate) fn batched_multi_get_with<'a, K, I, F>(
+    keys: I,
+    readopts: Option<&ReadOptions>,
+    call: F,
+) -> Vec<Result<Option<DBPinnableSlice<'a>>, Error>>
+where
+    K: AsRef<[u8]>,
+    I: IntoIterator<Item = K>,
+    F: FnOnce(
+        *mut ffi::rocksdb_readoptions_t,
+        &[*const c_char],
+        &[size_t],
+        &mut [*mut ffi::rocksdb_pinnableslice_t],
+        &mut [*mut c_char],
+    ),
+{
+    let keys: Vec<K> = keys.into_iter().collect();
+
+    let mut default_readopts = None;
+    let ro_handle = match ReadOptions::input_or_default(readopts, &mut default_readopts) {
+        Ok(ro_handle) => ro_handle,
+        Err(e) => return keys.iter().map(|_| Err(e.clone())).collect(),
+    };
+
+    let keys_ptrs: Vec<_> = keys
+        .iter()
+        .map(|k| k.as_ref().as_ptr() as *const c_char)
+        .collect();
+    let keys_sizes: Vec<_> = keys.iter().map(|k| k.as_ref().len() as size_t).collect();
+    let mut values = vec![ptr::null_mut(); keys.len()];
+    let mut errs = vec![ptr::null_mut(); keys.len()];
+
+    call(ro_handle, &keys_ptrs, &keys_sizes, &mut values, &mut errs);
+
+    values
+        .into_iter()
+        .zip(errs)
+        .map(|(value, err)| {
+            if !err.is_null() {
+                Err(Error::from_status(error_message(err)))
+            } else if value.is_null() {
+                Ok(None)
+            } else {
+                Ok(Some(unsafe { DBPinnableSlice::from_c(value) }))
+            }
+        })
+        .collect()
 }
 
 impl<T> GetCF<ReadOptions> for T
@@ -116,4 +392,70 @@ where
             }
         }
     }
+
+    fn multi_get_cf_full<'c, K, I>(
+        &self,
+        keys: I,
+        readopts: Option<&ReadOptions>,
+    ) -> Vec<Result<Option<DBVector>, Error>>
+    where
+        K: AsRef<[u8]>,
+        I: IntoIterator<Item = (Option<&'c ColumnFamily>, K)>,
+    {
+        multi_get_with(keys, readopts, |ro_handle, args| unsafe {
+            match args.cfs {
+                Some(cfs) => ffi::rocksdb_multi_get_cf(
+                    self.handle(),
+                    ro_handle,
+                    cfs.as_ptr(),
+                    args.keys.len(),
+                    args.keys.as_ptr(),
+                    args.keys_sizes.as_ptr(),
+                    args.values.as_mut_ptr(),
+                    args.values_sizes.as_mut_ptr(),
+                    args.errs.as_mut_ptr(),
+                ),
+                None => ffi::rocksdb_multi_get(
+                    self.handle(),
+                    ro_handle,
+                    args.keys.len(),
+                    args.keys.as_ptr(),
+                    args.keys_sizes.as_ptr(),
+                    args.values.as_mut_ptr(),
+                    args.values_sizes.as_mut_ptr(),
+                    args.errs.as_mut_ptr(),
+                ),
+            }
+        })
+    }
+
+    fn batched_multi_get_cf_full<K, I>(
+        &self,
+        cf: &ColumnFamily,
+        keys: I,
+        sorted_input: bool,
+        readopts: Option<&ReadOptions>,
+    ) -> Vec<Result<Option<DBPinnableSlice<'_>>, Error>>
+    where
+        K: AsRef<[u8]>,
+        I: IntoIterator<Item = K>,
+    {
+        batched_multi_get_with(
+            keys,
+            readopts,
+            |ro_handle, keys, keys_sizes, values, errs| unsafe {
+                ffi::rocksdb_batched_multi_get_cf(
+                    self.handle(),
+                    ro_handle,
+                    cf.inner,
+                    keys.len(),
+                    keys.as_ptr(),
+                    keys_sizes.as_ptr(),
+                    values.as_mut_ptr(),
+                    errs.as_mut_ptr(),
+                    sorted_input,
+                )
+            },
+        )
+    }
 }
@@ -0,0 +1,52 @@
+// Copyright 2019 Tyler Neely
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+use crate::ffi;
+use std::ptr;
+
+use crate::{handle::Handle, Error, WalIterator};
+
+pub trait GetUpdatesSince {
+    /// Returns an iterator over the write batches in the write-ahead log,
+    /// starting with the batch that contains `seq_number`.
+    ///
+    /// Only the WAL files still on disk can be read, see
+    /// `Options::set_wal_ttl_seconds` and `Options::set_wal_size_limit_mb`
+    /// to keep them around after their data has been flushed.
+    fn get_updates_since(&self, seq_number: u64) -> Result<WalIterator<'_>, Error>;
+
+    /// The sequence number of the most recent write.
+    fn latest_sequence_number(&self) -> u64;
+}
+
+impl<T> GetUpdatesSince for T
+where
+    T: Handle<ffi::rocksdb_t>,
+{
+    fn get_updates_since(&self, seq_number: u64) -> Result<WalIterator<'_>, Error> {
+        unsafe {
+            let iter = ffi_try!(ffi::rocksdb_get_updates_since(
+                self.handle(),
+                seq_number,
+                ptr::null(),
+            ));
+            Ok(WalIterator::new(iter))
+        }
+    }
+
+    fn latest_sequence_number(&self) -> u64 {
+        unsafe { ffi::rocksdb_get_latest_sequence_number(self.handle()) }
+    }
+}
@@ -0,0 +1,79 @@
+use crate::ffi;
+use crate::{
+    handle::Handle,
+    metadata::{self, ColumnFamilyMetaData, FileDeletionsGuard, LiveFile},
+    ColumnFamily, Error,
+};
+use libc::c_uchar;
+
+pub trait GetLiveFilesMetadata {
+    /// Returns the SST files of all column families, at all levels.
+    fn get_live_files_metadata(&self) -> Vec<LiveFile>;
+}
+
+pub trait GetColumnFamilyMetadata {
+    /// Returns the levels of the column family `cf` along with their SST files.
+    fn get_column_family_metadata(&self, cf: &ColumnFamily) -> ColumnFamilyMetaData;
+}
+
+pub trait FileDeletions {
+    /// Prevents obsolete files from being deleted until the returned guard is
+    /// dropped, so that the live files can be copied consistently.
+    ///
+    /// Guards may overlap: file deletions resume once all of them are dropped.
+    fn disable_file_deletions(&self) -> Result<FileDeletionsGuard<'_, Self>, Error> {
+        self.disable_file_deletions_raw()?;
+        Ok(FileDeletionsGuard { db: self })
+    }
+
+    /// Prevents obsolete files from being deleted without a guard, for when
+    /// file deletions must stay disabled beyond a scope.
+    ///
+    /// Calls are counted: file deletions resume once `enable_file_deletions`
+    /// has been called as many times.
+    fn disable_file_deletions_raw(&self) -> Result<(), Error>;
+
+    /// Undoes one `disable_file_deletions_raw` call, or all of them when
+    /// `force` is set, including those of live guards.
+    fn enable_file_deletions(&self, force: bool) -> Result<(), Error>;
+}
+
+impl<T> GetLiveFilesMetadata for T
+where
+    T: Handle<ffi::rocksdb_t>,
+{
+    fn get_live_files_metadata(&self) -> Vec<LiveFile> {
+        unsafe { metadata::live_files(self.handle()) }
+    }
+}
+
+impl<T> GetColumnFamilyMetadata for T
+where
+    T: Handle<ffi::rocksdb_t>,
+{
+    fn get_column_family_metadata(&self, cf: &ColumnFamily) -> ColumnFamilyMetaData {
+        unsafe { metadata::column_family_metadata(self.handle(), cf.inner) }
+    }
+}
+
+impl<T> FileDeletions for T
+where
+    T: Handle<ffi::rocksdb_t>,
+{
+    fn disable_file_deletions_raw(&self) -> Result<(), Error> {
+        unsafe {
+            ffi_try!(ffi::rocksdb_disable_file_deletions(self.handle()));
+        }
+        Ok(())
+    }
+
+    fn enable_file_deletions(&self, force: bool) -> Result<(), Error> {
+        unsafe {
+            ffi_try!(ffi::rocksdb_enable_file_deletions(
+                self.handle(),
+                force as c_uchar,
+            ));
+        }
+        Ok(())
+    }
+}
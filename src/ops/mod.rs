@@ -27,19 +27,22 @@ mod open;
 mod checkpoint;
 mod compact;
 mod flush;
+mod get_updates_since;
 mod ingest_external_file;
 mod iter;
+mod metadata;
 mod property;
 mod setoptions;
 mod transaction;
 
 pub use self::delete::{Delete, DeleteCF};
+pub(crate) use self::get::{batched_multi_get_with, multi_get_with};
 pub use self::get::{Get, GetCF};
 pub use self::get_pinned::{GetPinned, GetPinnedCF};
 pub use self::ingest_external_file::{IngestExternalFile, IngestExternalFileCF};
 pub use self::merge::{Merge, MergeCF};
 pub use self::put::{Put, PutCF};
-pub use self::writebatch::WriteOps;
+pub use self::writebatch::{WriteBatchLike, WriteOps};
 
 pub use self::open::{Open, OpenCF};
 
@@ -57,7 +60,9 @@ pub use self::columnfamily::DropCF;
 pub use self::columnfamily::GetColumnFamilys;
 pub use self::compact::{CompactRange, CompactRangeCF};
 pub use self::flush::Flush;
+pub use self::get_updates_since::GetUpdatesSince;
 pub use self::iter::{Iterate, IterateCF};
+pub use self::metadata::{FileDeletions, GetColumnFamilyMetadata, GetLiveFilesMetadata};
 pub use self::property::{GetProperty, GetPropertyCF};
 pub use self::setoptions::SetOptions;
 pub use self::transaction::TransactionBegin;
@@ -1,20 +1,107 @@
 use crate::ffi;
 
-use crate::{handle::Handle, Error, WriteBatch, WriteOptions};
+use crate::{handle::Handle, Error, WriteBatch, WriteBatchWithIndex, WriteOptions};
+
+/// A batch of writes which can be applied atomically by `WriteOps`.
+///
+/// Implemented by `WriteBatch` and `WriteBatchWithIndex`. The trait is sealed,
+/// it cannot be implemented outside of this crate.
+pub trait WriteBatchLike: private::Sealed {}
+
+impl WriteBatchLike for WriteBatch {}
+
+impl WriteBatchLike for WriteBatchWithIndex {}
+
+pub(crate) mod private {
+    use crate::{ffi, Error};
+
+    pub trait Sealed {
+        unsafe fn write_to_db(
+            &self,
+            db: *mut ffi::rocksdb_t,
+            writeopts: *mut ffi::rocksdb_writeoptions_t,
+        ) -> Result<(), Error>;
+
+        unsafe fn write_to_transaction_db(
+            &self,
+            txn_db: *mut ffi::rocksdb_transactiondb_t,
+            writeopts: *mut ffi::rocksdb_writeoptions_t,
+        ) -> Result<(), Error>;
+    }
+}
+
+impl private::Sealed for WriteBatch {
+    unsafe fn write_to_db(
+        &self,
+        db: *mut ffi::rocksdb_t,
+        writeopts: *mut ffi::rocksdb_writeoptions_t,
+    ) -> Result<(), Error> {
+        ffi_try!(ffi::rocksdb_write(db, writeopts, self.handle(),));
+        Ok(())
+    }
+
+    unsafe fn write_to_transaction_db(
+        &self,
+        txn_db: *mut ffi::rocksdb_transactiondb_t,
+        writeopts: *mut ffi::rocksdb_writeoptions_t,
+    ) -> Result<(), Error> {
+        ffi_try!(ffi::rocksdb_transactiondb_write(
+            txn_db,
+            writeopts,
+            self.handle(),
+        ));
+        Ok(())
+    }
+}
+
+impl private::Sealed for WriteBatchWithIndex {
+    unsafe fn write_to_db(
+        &self,
+        db: *mut ffi::rocksdb_t,
+        writeopts: *mut ffi::rocksdb_writeoptions_t,
+    ) -> Result<(), Error> {
+        ffi_try!(ffi::rocksdb_write_writebatch_wi(
+            db,
+            writeopts,
+            self.handle(),
+        ));
+        Ok(())
+    }
+
+    unsafe fn write_to_transaction_db(
+        &self,
+        txn_db: *mut ffi::rocksdb_transactiondb_t,
+        writeopts: *mut ffi::rocksdb_writeoptions_t,
+    ) -> Result<(), Error> {
+        ffi_try!(ffi::rocksdb_transactiondb_write_writebatch_wi(
+            txn_db,
+            writeopts,
+            self.handle(),
+        ));
+        Ok(())
+    }
+}
 
 pub trait WriteOps {
-    fn write_full(&self, batch: &WriteBatch, writeopts: Option<&WriteOptions>)
-        -> Result<(), Error>;
+    fn write_full<B: WriteBatchLike>(
+        &self,
+        batch: &B,
+        writeopts: Option<&WriteOptions>,
+    ) -> Result<(), Error>;
 
-    fn write(&self, batch: &WriteBatch) -> Result<(), Error> {
+    fn write<B: WriteBatchLike>(&self, batch: &B) -> Result<(), Error> {
         self.write_full(batch, None)
     }
 
-    fn write_opt(&self, batch: &WriteBatch, writeopts: &WriteOptions) -> Result<(), Error> {
+    fn write_opt<B: WriteBatchLike>(
+        &self,
+        batch: &B,
+        writeopts: &WriteOptions,
+    ) -> Result<(), Error> {
         self.write_full(batch, Some(writeopts))
     }
 
-    fn write_without_wal(&self, batch: &WriteBatch) -> Result<(), Error> {
+    fn write_without_wal<B: WriteBatchLike>(&self, batch: &B) -> Result<(), Error> {
         let mut wo = WriteOptions::new();
         wo.disable_wal(true);
         self.write_opt(batch, &wo)
@@ -25,18 +112,15 @@ impl<T> WriteOps for T
 where
     T: Handle<ffi::rocksdb_t> + super::Write,
 {
-    fn write_full(
+    fn write_full<B: WriteBatchLike>(
         &self,
-        batch: &WriteBatch,
+        batch: &B,
         writeopts: Option<&WriteOptions>,
     ) -> Result<(), Error> {
         let mut default_writeopts = None;
 
         let wo_handle = WriteOptions::input_or_default(writeopts, &mut default_writeopts)?;
 
-        unsafe {
-            ffi_try!(ffi::rocksdb_write(self.handle(), wo_handle, batch.handle(),));
-            Ok(())
-        }
+        unsafe { batch.write_to_db(self.handle(), wo_handle) }
     }
 }
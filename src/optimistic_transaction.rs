@@ -192,6 +192,72 @@ impl GetCF<ReadOptions> for OptimisticTransaction {
             }
         }
     }
+
+    fn multi_get_cf_full<'c, K, I>(
+        &self,
+        keys: I,
+        readopts: Option<&ReadOptions>,
+    ) -> Vec<Result<Option<DBVector>, Error>>
+    where
+        K: AsRef<[u8]>,
+        I: IntoIterator<Item = (Option<&'c ColumnFamily>, K)>,
+    {
+        multi_get_with(keys, readopts, |ro_handle, args| unsafe {
+            match args.cfs {
+                Some(cfs) => ffi::rocksdb_transaction_multi_get_cf(
+                    self.handle(),
+                    ro_handle,
+                    cfs.as_ptr(),
+                    args.keys.len(),
+                    args.keys.as_ptr(),
+                    args.keys_sizes.as_ptr(),
+                    args.values.as_mut_ptr(),
+                    args.values_sizes.as_mut_ptr(),
+                    args.errs.as_mut_ptr(),
+                ),
+                None => ffi::rocksdb_transaction_multi_get(
+                    self.handle(),
+                    ro_handle,
+                    args.keys.len(),
+                    args.keys.as_ptr(),
+                    args.keys_sizes.as_ptr(),
+                    args.values.as_mut_ptr(),
+                    args.values_sizes.as_mut_ptr(),
+                    args.errs.as_mut_ptr(),
+                ),
+            }
+        })
+    }
+
+    fn batched_multi_get_cf_full<K, I>(
+        &self,
+        cf: &ColumnFamily,
+        keys: I,
+        sorted_input: bool,
+        readopts: Option<&ReadOptions>,
+    ) -> Vec<Result<Option<DBPinnableSlice<'_>>, Error>>
+    where
+        K: AsRef<[u8]>,
+        I: IntoIterator<Item = K>,
+    {
+        batched_multi_get_with(
+            keys,
+            readopts,
+            |ro_handle, keys, keys_sizes, values, errs| unsafe {
+                ffi::rocksdb_transaction_batched_multi_get_cf(
+                    self.handle(),
+                    ro_handle,
+                    cf.inner,
+                    keys.len(),
+                    keys.as_ptr(),
+                    keys_sizes.as_ptr(),
+                    values.as_mut_ptr(),
+                    errs.as_mut_ptr(),
+                    sorted_input,
+                )
+            },
+        )
+    }
 }
 
 impl Iterate for OptimisticTransaction {
@@ -371,6 +437,37 @@ impl<'a> GetCF<ReadOptions> for OptimisticTransactionSnapshot<'a> {
         ro.set_snapshot(self);
         self.txn.get_cf_full(cf, key, Some(&ro))
     }
+
+    fn multi_get_cf_full<'c, K, I>(
+        &self,
+        keys: I,
+        readopts: Option<&ReadOptions>,
+    ) -> Vec<Result<Option<DBVector>, Error>>
+    where
+        K: AsRef<[u8]>,
+        I: IntoIterator<Item = (Option<&'c ColumnFamily>, K)>,
+    {
+        let mut ro = readopts.cloned().unwrap_or_default();
+        ro.set_snapshot(self);
+        self.txn.multi_get_cf_full(keys, Some(&ro))
+    }
+
+    fn batched_multi_get_cf_full<K, I>(
+        &self,
+        cf: &ColumnFamily,
+        keys: I,
+        sorted_input: bool,
+        readopts: Option<&ReadOptions>,
+    ) -> Vec<Result<Option<DBPinnableSlice<'_>>, Error>>
+    where
+        K: AsRef<[u8]>,
+        I: IntoIterator<Item = K>,
+    {
+        let mut ro = readopts.cloned().unwrap_or_default();
+        ro.set_snapshot(self);
+        self.txn
+            .batched_multi_get_cf_full(cf, keys, sorted_input, Some(&ro))
+    }
 }
 
 impl<'a> Drop for OptimisticTransactionSnapshot<'a> {
@@ -440,7 +537,7 @@ impl<'a> GetPinnedCF<'a> for OptimisticTransaction {
             };
 
             if !err.is_null() {
-                return Err(Error::new(ffi_util::error_message(err)));
+                return Err(Error::from_status(ffi_util::error_message(err)));
             }
 
             if val.is_null() {
@@ -490,7 +587,7 @@ impl<'a> GetPinnedCF<'a> for OptimisticTransactionSnapshot<'a> {
             };
 
             if !err.is_null() {
-                return Err(Error::new(ffi_util::error_message(err)));
+                return Err(Error::from_status(ffi_util::error_message(err)));
             }
 
             if val.is_null() {
@@ -0,0 +1,75 @@
+// Copyright 2019 Tyler Neely
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Handling of panics in callbacks invoked by RocksDB.
+//!
+//! Unwinding from a Rust callback into RocksDB is undefined behaviour, so
+//! every callback catches panics before they reach the C++ code. What happens
+//! next is decided by the process wide [`PanicPolicy`].
+
+use std::panic::{self, AssertUnwindSafe};
+use std::process;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+static POLICY: AtomicU8 = AtomicU8::new(PanicPolicy::Abort as u8);
+
+/// What to do when a Rust callback invoked by RocksDB panics.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum PanicPolicy {
+    /// Abort the process. This is the default.
+    Abort = 0,
+    /// Report the failure to RocksDB, if the callback has a way to do so:
+    ///
+    /// * a merge operator fails the merge, so the read, write or compaction
+    ///   that needed it returns a corruption error;
+    /// * a compaction filter keeps the entry;
+    /// * a compaction filter factory creates no filter for the compaction;
+    /// * an event listener ignores the event;
+    /// * a logger drops the message.
+    ///
+    /// Comparators and slice transforms can't report failures and always abort.
+    Fail = 1,
+}
+
+/// Sets the policy applied when a callback panics.
+pub fn set_panic_policy(policy: PanicPolicy) {
+    POLICY.store(policy as u8, Ordering::Relaxed);
+}
+
+/// Returns the policy applied when a callback panics.
+pub fn panic_policy() -> PanicPolicy {
+    match POLICY.load(Ordering::Relaxed) {
+        1 => PanicPolicy::Fail,
+        _ => PanicPolicy::Abort,
+    }
+}
+
+/// Runs `f`, calling `on_panic` instead of returning its result if it panics
+/// and the policy is `Fail`.
+pub(crate) fn catch_panic<R>(f: impl FnOnce() -> R, on_panic: impl FnOnce() -> R) -> R {
+    match panic::catch_unwind(AssertUnwindSafe(f)) {
+        Ok(result) => result,
+        Err(_) => match panic_policy() {
+            PanicPolicy::Fail => on_panic(),
+            PanicPolicy::Abort => process::abort(),
+        },
+    }
+}
+
+/// Runs `f`, aborting the process if it panics.
+pub(crate) fn abort_on_panic<R>(f: impl FnOnce() -> R) -> R {
+    panic::catch_unwind(AssertUnwindSafe(f)).unwrap_or_else(|_| process::abort())
+}
@@ -0,0 +1,58 @@
+// Copyright 2019 Tyler Neely
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Names of the properties reported by
+//! [`GetProperty`](crate::ops::GetProperty) and
+//! [`GetPropertyCF`](crate::ops::GetPropertyCF).
+//!
+//! ```
+//! use ckb_rocksdb::{prelude::*, properties, TemporaryDBPath};
+//!
+//! let mut opts = Options::default();
+//! opts.create_if_missing(true);
+//! opts.set_enable_blob_files(true);
+//!
+//! let path = TemporaryDBPath::new();
+//! let db = DB::open(&opts, &path).unwrap();
+//! db.put(b"k1", b"v1").unwrap();
+//! db.flush().unwrap();
+//! let num_blob_files = db.property_int_value(properties::NUM_BLOB_FILES).unwrap();
+//! assert_eq!(num_blob_files, Some(1));
+//! ```
+
+/// Number of blob files in the current version.
+pub const NUM_BLOB_FILES: &str = "rocksdb.num-blob-files";
+
+/// Total number and size of the blob files, and total amount of garbage in
+/// them, in the current version, as a string.
+pub const BLOB_STATS: &str = "rocksdb.blob-stats";
+
+/// Total size of the blob files over all versions.
+pub const TOTAL_BLOB_FILE_SIZE: &str = "rocksdb.total-blob-file-size";
+
+/// Total size of the blob files in the current version.
+pub const LIVE_BLOB_FILE_SIZE: &str = "rocksdb.live-blob-file-size";
+
+/// Total amount of garbage in the blob files in the current version.
+pub const LIVE_BLOB_FILE_GARBAGE_SIZE: &str = "rocksdb.live-blob-file-garbage-size";
+
+/// Capacity of the blob cache.
+pub const BLOB_CACHE_CAPACITY: &str = "rocksdb.blob-cache-capacity";
+
+/// Memory used by the entries of the blob cache.
+pub const BLOB_CACHE_USAGE: &str = "rocksdb.blob-cache-usage";
+
+/// Memory used by the pinned entries of the blob cache.
+pub const BLOB_CACHE_PINNED_USAGE: &str = "rocksdb.blob-cache-pinned-usage";
@@ -18,6 +18,7 @@ use std::slice;
 use libc::{c_char, c_void, size_t};
 
 use crate::ffi;
+use crate::panic_policy::abort_on_panic;
 
 /// A SliceTranform is a generic pluggable way of transforming one string
 /// to another. Its primary use-case is in configuring rocksdb
@@ -34,14 +35,38 @@ pub struct SliceTransform {
 // opening a DB.
 
 impl SliceTransform {
-    pub fn create(
+    /// Creates a transform from `transform_fn`, which must return a prefix of
+    /// the key it is given, and an optional `in_domain_fn`, which tells if the
+    /// transform applies to a key. Without it every key is in the domain.
+    ///
+    /// Both functions may be closures capturing their configuration, such as
+    /// a prefix length computed at runtime, and are dropped together with the
+    /// transform.
+    ///
+    /// ```
+    /// use ckb_rocksdb::{prelude::*, SliceTransform};
+    ///
+    /// let prefix_len = 4;
+    /// let transform = SliceTransform::create(
+    ///     "prefix",
+    ///     move |key: &[u8]| &key[..prefix_len],
+    ///     Some(Box::new(move |key: &[u8]| key.len() >= prefix_len)),
+    /// );
+    ///
+    /// let mut opts = Options::default();
+    /// opts.set_prefix_extractor(transform);
+    /// ```
+    pub fn create<F>(
         name: &str,
-        transform_fn: TransformFn<'_>,
-        in_domain_fn: Option<InDomainFn>,
-    ) -> SliceTransform {
+        transform_fn: F,
+        in_domain_fn: Option<Box<InDomainFn>>,
+    ) -> SliceTransform
+    where
+        F: Fn(&[u8]) -> &[u8] + Send + Sync + 'static,
+    {
         let cb = Box::into_raw(Box::new(TransformCallback {
             name: CString::new(name.as_bytes()).unwrap(),
-            transform_fn,
+            transform_fn: Box::new(transform_fn),
             in_domain_fn,
         }));
 
@@ -73,21 +98,23 @@ impl SliceTransform {
     }
 }
 
-pub type TransformFn<'a> = fn(&'a [u8]) -> &'a [u8];
-pub type InDomainFn = fn(&[u8]) -> bool;
+pub type TransformFn = dyn Fn(&[u8]) -> &[u8] + Send + Sync;
+pub type InDomainFn = dyn Fn(&[u8]) -> bool + Send + Sync;
 
-pub struct TransformCallback<'a> {
+pub struct TransformCallback {
     pub name: CString,
-    pub transform_fn: TransformFn<'a>,
-    pub in_domain_fn: Option<InDomainFn>,
+    pub transform_fn: Box<TransformFn>,
+    pub in_domain_fn: Option<Box<InDomainFn>>,
 }
 
 pub unsafe extern "C" fn slice_transform_destructor_callback(raw_cb: *mut c_void) {
-    Box::from_raw(raw_cb as *mut TransformCallback<'_>);
+    abort_on_panic(|| {
+        Box::from_raw(raw_cb as *mut TransformCallback);
+    })
 }
 
 pub unsafe extern "C" fn slice_transform_name_callback(raw_cb: *mut c_void) -> *const c_char {
-    let cb = &mut *(raw_cb as *mut TransformCallback<'_>);
+    let cb = &*(raw_cb as *const TransformCallback);
     cb.name.as_ptr()
 }
 
@@ -97,9 +124,9 @@ pub unsafe extern "C" fn transform_callback(
     key_len: size_t,
     dst_length: *mut size_t,
 ) -> *mut c_char {
-    let cb = &mut *(raw_cb as *mut TransformCallback<'_>);
+    let cb = &*(raw_cb as *const TransformCallback);
     let key = slice::from_raw_parts(raw_key as *const u8, key_len as usize);
-    let prefix = (cb.transform_fn)(key);
+    let prefix = abort_on_panic(|| (cb.transform_fn)(key));
     *dst_length = prefix.len() as size_t;
     prefix.as_ptr() as *mut c_char
 }
@@ -109,10 +136,10 @@ pub unsafe extern "C" fn in_domain_callback(
     raw_key: *const c_char,
     key_len: size_t,
 ) -> u8 {
-    let cb = &mut *(raw_cb as *mut TransformCallback<'_>);
+    let cb = &*(raw_cb as *const TransformCallback);
     let key = slice::from_raw_parts(raw_key as *const u8, key_len as usize);
-    if let Some(in_domain) = cb.in_domain_fn {
-        in_domain(key) as u8
+    if let Some(in_domain) = &cb.in_domain_fn {
+        abort_on_panic(|| in_domain(key)) as u8
     } else {
         0xff
     }
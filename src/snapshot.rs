@@ -14,7 +14,8 @@
 //
 
 use crate::{
-    handle::ConstHandle, ops::*, ColumnFamily, DBRawIterator, DBVector, Error, ReadOptions, DB,
+    handle::ConstHandle, ops::*, ColumnFamily, DBPinnableSlice, DBRawIterator, DBVector, Error,
+    ReadOptions, DB,
 };
 
 /// A consistent view of the database at the point of creation.
@@ -59,6 +60,37 @@ impl<'a> GetCF<ReadOptions> for Snapshot<'a> {
 
         self.db.get_cf_full(cf, key, Some(&ro))
     }
+
+    fn multi_get_cf_full<'c, K, I>(
+        &self,
+        keys: I,
+        readopts: Option<&ReadOptions>,
+    ) -> Vec<Result<Option<DBVector>, Error>>
+    where
+        K: AsRef<[u8]>,
+        I: IntoIterator<Item = (Option<&'c ColumnFamily>, K)>,
+    {
+        let mut ro = readopts.cloned().unwrap_or_default();
+        ro.set_snapshot(self);
+        self.db.multi_get_cf_full(keys, Some(&ro))
+    }
+
+    fn batched_multi_get_cf_full<K, I>(
+        &self,
+        cf: &ColumnFamily,
+        keys: I,
+        sorted_input: bool,
+        readopts: Option<&ReadOptions>,
+    ) -> Vec<Result<Option<DBPinnableSlice<'_>>, Error>>
+    where
+        K: AsRef<[u8]>,
+        I: IntoIterator<Item = K>,
+    {
+        let mut ro = readopts.cloned().unwrap_or_default();
+        ro.set_snapshot(self);
+        self.db
+            .batched_multi_get_cf_full(cf, keys, sorted_input, Some(&ro))
+    }
 }
 
 impl<'a> Drop for Snapshot<'a> {
@@ -0,0 +1,205 @@
+// Copyright 2019 Tyler Neely
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+use crate::{
+    ffi, ffi_util::to_cpath, handle::Handle, ops::Iterate, DBRawIterator, Error, Options,
+    ReadOptions,
+};
+
+use libc::{c_char, c_uchar, c_void, size_t};
+use std::{marker::PhantomData, path::Path, ptr, slice};
+
+/// SstFileReader reads sst files generated by a DB or by `SstFileWriter`,
+/// for instance to check them before ingesting them.
+///
+/// Entries are read through the `Iterate` trait, as for a DB. Deletions are
+/// not visible to iterators.
+///
+/// # Examples
+///
+/// ```
+/// use ckb_rocksdb::{prelude::*, IteratorMode, SstFileReader, SstFileWriter, TemporaryDBPath};
+///
+/// let dir = TemporaryDBPath::new();
+/// let path = dir.join("data.sst");
+/// let opts = Options::default();
+///
+/// let mut writer = SstFileWriter::create(&opts);
+/// writer.open(&path).unwrap();
+/// writer.put(b"k1", b"v1").unwrap();
+/// writer.finish().unwrap();
+///
+/// let reader = SstFileReader::open(&opts, &path).unwrap();
+/// reader.verify_checksum().unwrap();
+/// assert_eq!(reader.table_properties().num_entries, 1);
+/// assert_eq!(reader.smallest_key().unwrap(), Some(b"k1".to_vec()));
+/// for (key, value) in reader.iterator(IteratorMode::Start) {
+///     println!("Saw {:?} {:?}", key, value);
+/// }
+/// ```
+pub struct SstFileReader {
+    inner: *mut ffi::rocksdb_sstfilereader_t,
+}
+
+unsafe impl Send for SstFileReader {}
+unsafe impl Sync for SstFileReader {}
+
+/// Properties of an sst file, as reported by `SstFileReader::table_properties`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TableProperties {
+    /// Number of entries, deletions included.
+    pub num_entries: u64,
+    pub num_deletions: u64,
+    /// Total size of the keys before compression.
+    pub raw_key_size: u64,
+    /// Total size of the values before compression.
+    pub raw_value_size: u64,
+    /// Size of the data blocks.
+    pub data_size: u64,
+    /// Name of the comparator the file was written with.
+    pub comparator_name: String,
+    /// Creation time of the oldest data in the file, in seconds since the
+    /// epoch. Zero when unknown, as for files written by `SstFileWriter`.
+    pub creation_time: u64,
+    /// Creation time of the file, in seconds since the epoch. Zero when
+    /// unknown, as for files written by `SstFileWriter`.
+    pub file_creation_time: u64,
+}
+
+impl SstFileReader {
+    /// Opens the sst file located at `path`. `opts` should match the ones the
+    /// file was written with, in particular its comparator.
+    pub fn open<P: AsRef<Path>>(opts: &Options, path: P) -> Result<SstFileReader, Error> {
+        let cpath = to_cpath(
+            &path,
+            "Failed to convert path to CString when opening SstFileReader.",
+        )?;
+
+        // Dropping the reader destroys it if opening fails.
+        let reader = SstFileReader {
+            inner: unsafe { ffi::rocksdb_sstfilereader_create(opts.inner) },
+        };
+        unsafe {
+            ffi_try!(ffi::rocksdb_sstfilereader_open(
+                reader.inner,
+                cpath.as_ptr()
+            ));
+        }
+        Ok(reader)
+    }
+
+    /// Reads the whole file and verifies the checksums of its blocks.
+    pub fn verify_checksum(&self) -> Result<(), Error> {
+        unsafe {
+            ffi_try!(ffi::rocksdb_sstfilereader_verify_checksum(self.inner,));
+        }
+        Ok(())
+    }
+
+    /// Returns the table properties of the file.
+    pub fn table_properties(&self) -> TableProperties {
+        unsafe {
+            let props = ffi::rocksdb_sstfilereader_get_table_properties(self.inner);
+            let mut len: size_t = 0;
+            let ptr = ffi::rocksdb_table_properties_get_comparator_name(props, &mut len);
+            let comparator_name =
+                String::from_utf8_lossy(slice::from_raw_parts(ptr as *const u8, len)).into_owned();
+            let properties = TableProperties {
+                num_entries: ffi::rocksdb_table_properties_get_num_entries(props),
+                num_deletions: ffi::rocksdb_table_properties_get_num_deletions(props),
+                raw_key_size: ffi::rocksdb_table_properties_get_raw_key_size(props),
+                raw_value_size: ffi::rocksdb_table_properties_get_raw_value_size(props),
+                data_size: ffi::rocksdb_table_properties_get_data_size(props),
+                comparator_name,
+                creation_time: ffi::rocksdb_table_properties_get_creation_time(props),
+                file_creation_time: ffi::rocksdb_table_properties_get_file_creation_time(props),
+            };
+            ffi::rocksdb_table_properties_destroy(props);
+            properties
+        }
+    }
+
+    /// Returns the smallest key of the file, `None` when it has no entries.
+    ///
+    /// Deletions and range tombstones count, as they do when the file is
+    /// ingested, so the key may not be visible to an iterator. This reads the
+    /// file.
+    pub fn smallest_key(&self) -> Result<Option<Vec<u8>>, Error> {
+        Ok(self.key_range()?.map(|(smallest, _)| smallest))
+    }
+
+    /// Returns the largest key of the file, `None` when it has no entries.
+    ///
+    /// Deletions and range tombstones count, as they do when the file is
+    /// ingested; a range tombstone contributes its end key, which it does
+    /// not cover. This reads the file.
+    pub fn largest_key(&self) -> Result<Option<Vec<u8>>, Error> {
+        Ok(self.key_range()?.map(|(_, largest)| largest))
+    }
+
+    fn key_range(&self) -> Result<Option<KeyRange>, Error> {
+        let mut has_range: c_uchar = 0;
+        let mut smallest: *mut c_char = ptr::null_mut();
+        let mut smallest_len: size_t = 0;
+        let mut largest: *mut c_char = ptr::null_mut();
+        let mut largest_len: size_t = 0;
+        unsafe {
+            ffi_try!(ffi::rocksdb_sstfilereader_get_key_range(
+                self.inner,
+                &mut has_range,
+                &mut smallest,
+                &mut smallest_len,
+                &mut largest,
+                &mut largest_len
+            ));
+            if has_range == 0 {
+                return Ok(None);
+            }
+            Ok(Some((
+                take_key(smallest, smallest_len),
+                take_key(largest, largest_len),
+            )))
+        }
+    }
+}
+
+/// The smallest and largest keys of a file.
+type KeyRange = (Vec<u8>, Vec<u8>);
+
+/// Copies a key allocated by RocksDB with `malloc`, then frees it.
+unsafe fn take_key(ptr: *mut c_char, len: size_t) -> Vec<u8> {
+    let key = slice::from_raw_parts(ptr as *const u8, len).to_vec();
+    libc::free(ptr as *mut c_void);
+    key
+}
+
+impl Iterate for SstFileReader {
+    fn get_raw_iter<'a: 'b, 'b>(&'a self, readopts: &ReadOptions) -> DBRawIterator<'b> {
+        unsafe {
+            DBRawIterator {
+                inner: ffi::rocksdb_sstfilereader_new_iterator(self.inner, readopts.handle()),
+                db: PhantomData,
+            }
+        }
+    }
+}
+
+impl Drop for SstFileReader {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::rocksdb_sstfilereader_destroy(self.inner);
+        }
+    }
+}
@@ -13,10 +13,15 @@
 // limitations under the License.
 //`
 
-use crate::{ffi, ffi_util::to_cpath, Error, Options};
+use crate::{backup::BackupSource, ffi, ffi_util::to_cpath, ColumnFamily, Error, Options};
 
 use libc::{self, c_char, size_t};
-use std::{ffi::CString, marker::PhantomData, path::Path};
+use std::{
+    ffi::CString,
+    marker::PhantomData,
+    path::{Path, PathBuf},
+    slice,
+};
 
 /// SstFileWriter is used to create sst files that can be added to database later
 /// All keys in files generated by SstFileWriter will have sequence number = 0.
@@ -30,6 +35,28 @@ pub struct SstFileWriter<'a> {
 unsafe impl<'a> Send for SstFileWriter<'a> {}
 unsafe impl<'a> Sync for SstFileWriter<'a> {}
 
+/// Describes a file written by `SstFileWriter`, as returned by
+/// `SstFileWriter::finish`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExternalSstFileInfo {
+    pub file_path: PathBuf,
+    /// Smallest point key, empty if the file only holds range deletions.
+    pub smallest_key: Vec<u8>,
+    /// Largest point key, empty if the file only holds range deletions.
+    pub largest_key: Vec<u8>,
+    /// Start of the smallest range deletion, empty if there is none.
+    pub smallest_range_del_key: Vec<u8>,
+    /// End of the largest range deletion, empty if there is none.
+    pub largest_range_del_key: Vec<u8>,
+    /// Sequence number of the entries, always zero for a new file.
+    pub sequence_number: u64,
+    /// File size in bytes.
+    pub file_size: u64,
+    /// Number of point entries, deletions included.
+    pub num_entries: u64,
+    pub num_range_del_entries: u64,
+}
+
 struct EnvOptions {
     inner: *mut ffi::rocksdb_envoptions_t,
 }
@@ -62,6 +89,38 @@ impl<'a> SstFileWriter<'a> {
         }
     }
 
+    /// Initializes SstFileWriter with the options of the column family the
+    /// file will be ingested into, such as those it was created or opened with,
+    /// so that the comparator and compression match. Unlike `create_cf`, no
+    /// database needs to be open.
+    ///
+    /// This is `create` under a name which tells which options to pass.
+    pub fn create_with_cf_options(cf_opts: &'a Options) -> Self {
+        Self::create(cf_opts)
+    }
+
+    /// Initializes SstFileWriter with the current options of the column family
+    /// `cf` of `db`, so that its comparator and compression match the ones of
+    /// the column family the file will be ingested into.
+    ///
+    /// `db` may be any database, including a `TransactionDB`, whose base
+    /// database is used.
+    pub fn create_cf<D>(db: &'a D, cf: &'a ColumnFamily) -> Self
+    where
+        D: BackupSource,
+    {
+        let env_options = EnvOptions::default();
+
+        let writer = unsafe {
+            ffi::rocksdb_sstfilewriter_create_cf(db.base_db_ptr(), cf.inner, env_options.inner)
+        };
+
+        Self {
+            inner: writer,
+            phantom: PhantomData,
+        }
+    }
+
     fn create_raw(opts: &Options, env_opts: &EnvOptions) -> *mut ffi::rocksdb_sstfilewriter_t {
         unsafe { ffi::rocksdb_sstfilewriter_create(env_opts.inner, opts.inner) }
     }
@@ -87,10 +146,35 @@ impl<'a> SstFileWriter<'a> {
     }
 
     /// Finalize writing to sst file and close file.
-    pub fn finish(&mut self) -> Result<(), Error> {
+    pub fn finish(&mut self) -> Result<ExternalSstFileInfo, Error> {
         unsafe {
-            ffi_try!(ffi::rocksdb_sstfilewriter_finish(self.inner,));
-            Ok(())
+            let info = ffi_try!(ffi::rocksdb_sstfilewriter_finish_with_info(self.inner,));
+            let mut len: size_t = 0;
+            let ptr = ffi::rocksdb_external_sst_file_info_file_path(info, &mut len);
+            let file_path = PathBuf::from(String::from_utf8_lossy(raw_bytes(ptr, len)).as_ref());
+            let ptr = ffi::rocksdb_external_sst_file_info_smallest_key(info, &mut len);
+            let smallest_key = raw_bytes(ptr, len).to_vec();
+            let ptr = ffi::rocksdb_external_sst_file_info_largest_key(info, &mut len);
+            let largest_key = raw_bytes(ptr, len).to_vec();
+            let ptr = ffi::rocksdb_external_sst_file_info_smallest_range_del_key(info, &mut len);
+            let smallest_range_del_key = raw_bytes(ptr, len).to_vec();
+            let ptr = ffi::rocksdb_external_sst_file_info_largest_range_del_key(info, &mut len);
+            let largest_range_del_key = raw_bytes(ptr, len).to_vec();
+            let file_info = ExternalSstFileInfo {
+                file_path,
+                smallest_key,
+                largest_key,
+                smallest_range_del_key,
+                largest_range_del_key,
+                sequence_number: ffi::rocksdb_external_sst_file_info_sequence_number(info),
+                file_size: ffi::rocksdb_external_sst_file_info_file_size(info),
+                num_entries: ffi::rocksdb_external_sst_file_info_num_entries(info),
+                num_range_del_entries: ffi::rocksdb_external_sst_file_info_num_range_del_entries(
+                    info,
+                ),
+            };
+            ffi::rocksdb_external_sst_file_info_destroy(info);
+            Ok(file_info)
         }
     }
 
@@ -161,6 +245,28 @@ impl<'a> SstFileWriter<'a> {
             Ok(())
         }
     }
+
+    /// Adds a range deletion of the keys in [begin, end) to currently opened file.
+    /// Unlike point entries, range deletions may be added in any order.
+    pub fn delete_range<K: AsRef<[u8]>>(&mut self, begin: K, end: K) -> Result<(), Error> {
+        let begin = begin.as_ref();
+        let end = end.as_ref();
+
+        unsafe {
+            ffi_try!(ffi::rocksdb_sstfilewriter_delete_range(
+                self.inner,
+                begin.as_ptr() as *const c_char,
+                begin.len() as size_t,
+                end.as_ptr() as *const c_char,
+                end.len() as size_t,
+            ));
+            Ok(())
+        }
+    }
+}
+
+unsafe fn raw_bytes<'a>(ptr: *const c_char, len: size_t) -> &'a [u8] {
+    slice::from_raw_parts(ptr as *const u8, len)
 }
 
 impl<'a> Drop for SstFileWriter<'a> {
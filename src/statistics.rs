@@ -0,0 +1,359 @@
+// Copyright 2019 Tyler Neely
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Typed access to the statistics collected by RocksDB, see
+//! [`Options::enable_statistics`](crate::Options::enable_statistics).
+
+macro_rules! named_enum {
+    (
+        $(#[$meta:meta])*
+        pub enum $name:ident {
+            $($variant:ident = $str:literal,)*
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        #[repr(u32)]
+        pub enum $name {
+            $($variant,)*
+        }
+
+        impl $name {
+            /// All the variants, in the order of their RocksDB values.
+            pub const ALL: &'static [$name] = &[$($name::$variant,)*];
+
+            /// Returns the name RocksDB uses for this statistic, e.g. in
+            /// [`Options::get_statistics`](crate::Options::get_statistics).
+            pub fn name(self) -> &'static str {
+                match self {
+                    $($name::$variant => $str,)*
+                }
+            }
+        }
+    };
+}
+
+named_enum! {
+    /// A counter of RocksDB statistics.
+    ///
+    /// The variants mirror `rocksdb::Tickers`, which only ever appends new
+    /// tickers.
+    pub enum Ticker {
+        BlockCacheMiss = "rocksdb.block.cache.miss",
+        BlockCacheHit = "rocksdb.block.cache.hit",
+        BlockCacheAdd = "rocksdb.block.cache.add",
+        BlockCacheAddFailures = "rocksdb.block.cache.add.failures",
+        BlockCacheIndexMiss = "rocksdb.block.cache.index.miss",
+        BlockCacheIndexHit = "rocksdb.block.cache.index.hit",
+        BlockCacheIndexAdd = "rocksdb.block.cache.index.add",
+        BlockCacheIndexBytesInsert = "rocksdb.block.cache.index.bytes.insert",
+        BlockCacheIndexBytesEvict = "rocksdb.block.cache.index.bytes.evict",
+        BlockCacheFilterMiss = "rocksdb.block.cache.filter.miss",
+        BlockCacheFilterHit = "rocksdb.block.cache.filter.hit",
+        BlockCacheFilterAdd = "rocksdb.block.cache.filter.add",
+        BlockCacheFilterBytesInsert = "rocksdb.block.cache.filter.bytes.insert",
+        BlockCacheFilterBytesEvict = "rocksdb.block.cache.filter.bytes.evict",
+        BlockCacheDataMiss = "rocksdb.block.cache.data.miss",
+        BlockCacheDataHit = "rocksdb.block.cache.data.hit",
+        BlockCacheDataAdd = "rocksdb.block.cache.data.add",
+        BlockCacheDataBytesInsert = "rocksdb.block.cache.data.bytes.insert",
+        BlockCacheBytesRead = "rocksdb.block.cache.bytes.read",
+        BlockCacheBytesWrite = "rocksdb.block.cache.bytes.write",
+        BloomFilterUseful = "rocksdb.bloom.filter.useful",
+        BloomFilterFullPositive = "rocksdb.bloom.filter.full.positive",
+        BloomFilterFullTruePositive = "rocksdb.bloom.filter.full.true.positive",
+        BloomFilterMicros = "rocksdb.bloom.filter.micros",
+        PersistentCacheHit = "rocksdb.persistent.cache.hit",
+        PersistentCacheMiss = "rocksdb.persistent.cache.miss",
+        SimBlockCacheHit = "rocksdb.sim.block.cache.hit",
+        SimBlockCacheMiss = "rocksdb.sim.block.cache.miss",
+        MemtableHit = "rocksdb.memtable.hit",
+        MemtableMiss = "rocksdb.memtable.miss",
+        GetHitL0 = "rocksdb.l0.hit",
+        GetHitL1 = "rocksdb.l1.hit",
+        GetHitL2AndUp = "rocksdb.l2andup.hit",
+        CompactionKeyDropNewerEntry = "rocksdb.compaction.key.drop.new",
+        CompactionKeyDropObsolete = "rocksdb.compaction.key.drop.obsolete",
+        CompactionKeyDropRangeDel = "rocksdb.compaction.key.drop.range_del",
+        CompactionKeyDropUser = "rocksdb.compaction.key.drop.user",
+        CompactionRangeDelDropObsolete = "rocksdb.compaction.range_del.drop.obsolete",
+        CompactionOptimizedDelDropObsolete = "rocksdb.compaction.optimized.del.drop.obsolete",
+        CompactionCancelled = "rocksdb.compaction.cancelled",
+        NumberKeysWritten = "rocksdb.number.keys.written",
+        NumberKeysRead = "rocksdb.number.keys.read",
+        NumberKeysUpdated = "rocksdb.number.keys.updated",
+        BytesWritten = "rocksdb.bytes.written",
+        BytesRead = "rocksdb.bytes.read",
+        NumberDbSeek = "rocksdb.number.db.seek",
+        NumberDbNext = "rocksdb.number.db.next",
+        NumberDbPrev = "rocksdb.number.db.prev",
+        NumberDbSeekFound = "rocksdb.number.db.seek.found",
+        NumberDbNextFound = "rocksdb.number.db.next.found",
+        NumberDbPrevFound = "rocksdb.number.db.prev.found",
+        IterBytesRead = "rocksdb.db.iter.bytes.read",
+        NoFileCloses = "rocksdb.no.file.closes",
+        NoFileOpens = "rocksdb.no.file.opens",
+        NoFileErrors = "rocksdb.no.file.errors",
+        StallL0SlowdownMicros = "rocksdb.l0.slowdown.micros",
+        StallMemtableCompactionMicros = "rocksdb.memtable.compaction.micros",
+        StallL0NumFilesMicros = "rocksdb.l0.num.files.stall.micros",
+        StallMicros = "rocksdb.stall.micros",
+        DbMutexWaitMicros = "rocksdb.db.mutex.wait.micros",
+        RateLimitDelayMillis = "rocksdb.rate.limit.delay.millis",
+        NoIterators = "rocksdb.num.iterators",
+        NumberMultigetCalls = "rocksdb.number.multiget.get",
+        NumberMultigetKeysRead = "rocksdb.number.multiget.keys.read",
+        NumberMultigetBytesRead = "rocksdb.number.multiget.bytes.read",
+        NumberFilteredDeletes = "rocksdb.number.deletes.filtered",
+        NumberMergeFailures = "rocksdb.number.merge.failures",
+        BloomFilterPrefixChecked = "rocksdb.bloom.filter.prefix.checked",
+        BloomFilterPrefixUseful = "rocksdb.bloom.filter.prefix.useful",
+        NumberOfReseeksInIteration = "rocksdb.number.reseeks.iteration",
+        GetUpdatesSinceCalls = "rocksdb.getupdatessince.calls",
+        BlockCacheCompressedMiss = "rocksdb.block.cachecompressed.miss",
+        BlockCacheCompressedHit = "rocksdb.block.cachecompressed.hit",
+        BlockCacheCompressedAdd = "rocksdb.block.cachecompressed.add",
+        BlockCacheCompressedAddFailures = "rocksdb.block.cachecompressed.add.failures",
+        WalFileSynced = "rocksdb.wal.synced",
+        WalFileBytes = "rocksdb.wal.bytes",
+        WriteDoneBySelf = "rocksdb.write.self",
+        WriteDoneByOther = "rocksdb.write.other",
+        WriteTimedout = "rocksdb.write.timeout",
+        WriteWithWal = "rocksdb.write.wal",
+        CompactReadBytes = "rocksdb.compact.read.bytes",
+        CompactWriteBytes = "rocksdb.compact.write.bytes",
+        FlushWriteBytes = "rocksdb.flush.write.bytes",
+        CompactReadBytesMarked = "rocksdb.compact.read.marked.bytes",
+        CompactReadBytesPeriodic = "rocksdb.compact.read.periodic.bytes",
+        CompactReadBytesTtl = "rocksdb.compact.read.ttl.bytes",
+        CompactWriteBytesMarked = "rocksdb.compact.write.marked.bytes",
+        CompactWriteBytesPeriodic = "rocksdb.compact.write.periodic.bytes",
+        CompactWriteBytesTtl = "rocksdb.compact.write.ttl.bytes",
+        NumberDirectLoadTableProperties = "rocksdb.number.direct.load.table.properties",
+        NumberSuperversionAcquires = "rocksdb.number.superversion_acquires",
+        NumberSuperversionReleases = "rocksdb.number.superversion_releases",
+        NumberSuperversionCleanups = "rocksdb.number.superversion_cleanups",
+        NumberBlockCompressed = "rocksdb.number.block.compressed",
+        NumberBlockDecompressed = "rocksdb.number.block.decompressed",
+        NumberBlockNotCompressed = "rocksdb.number.block.not_compressed",
+        MergeOperationTotalTime = "rocksdb.merge.operation.time.nanos",
+        FilterOperationTotalTime = "rocksdb.filter.operation.time.nanos",
+        RowCacheHit = "rocksdb.row.cache.hit",
+        RowCacheMiss = "rocksdb.row.cache.miss",
+        ReadAmpEstimateUsefulBytes = "rocksdb.read.amp.estimate.useful.bytes",
+        ReadAmpTotalReadBytes = "rocksdb.read.amp.total.read.bytes",
+        NumberRateLimiterDrains = "rocksdb.number.rate_limiter.drains",
+        NumberIterSkip = "rocksdb.number.iter.skip",
+        BlobDbNumPut = "rocksdb.blobdb.num.put",
+        BlobDbNumWrite = "rocksdb.blobdb.num.write",
+        BlobDbNumGet = "rocksdb.blobdb.num.get",
+        BlobDbNumMultiget = "rocksdb.blobdb.num.multiget",
+        BlobDbNumSeek = "rocksdb.blobdb.num.seek",
+        BlobDbNumNext = "rocksdb.blobdb.num.next",
+        BlobDbNumPrev = "rocksdb.blobdb.num.prev",
+        BlobDbNumKeysWritten = "rocksdb.blobdb.num.keys.written",
+        BlobDbNumKeysRead = "rocksdb.blobdb.num.keys.read",
+        BlobDbBytesWritten = "rocksdb.blobdb.bytes.written",
+        BlobDbBytesRead = "rocksdb.blobdb.bytes.read",
+        BlobDbWriteInlined = "rocksdb.blobdb.write.inlined",
+        BlobDbWriteInlinedTtl = "rocksdb.blobdb.write.inlined.ttl",
+        BlobDbWriteBlob = "rocksdb.blobdb.write.blob",
+        BlobDbWriteBlobTtl = "rocksdb.blobdb.write.blob.ttl",
+        BlobDbBlobFileBytesWritten = "rocksdb.blobdb.blob.file.bytes.written",
+        BlobDbBlobFileBytesRead = "rocksdb.blobdb.blob.file.bytes.read",
+        BlobDbBlobFileSynced = "rocksdb.blobdb.blob.file.synced",
+        BlobDbBlobIndexExpiredCount = "rocksdb.blobdb.blob.index.expired.count",
+        BlobDbBlobIndexExpiredSize = "rocksdb.blobdb.blob.index.expired.size",
+        BlobDbBlobIndexEvictedCount = "rocksdb.blobdb.blob.index.evicted.count",
+        BlobDbBlobIndexEvictedSize = "rocksdb.blobdb.blob.index.evicted.size",
+        BlobDbGcNumFiles = "rocksdb.blobdb.gc.num.files",
+        BlobDbGcNumNewFiles = "rocksdb.blobdb.gc.num.new.files",
+        BlobDbGcFailures = "rocksdb.blobdb.gc.failures",
+        BlobDbGcNumKeysOverwritten = "rocksdb.blobdb.gc.num.keys.overwritten",
+        BlobDbGcNumKeysExpired = "rocksdb.blobdb.gc.num.keys.expired",
+        BlobDbGcNumKeysRelocated = "rocksdb.blobdb.gc.num.keys.relocated",
+        BlobDbGcBytesOverwritten = "rocksdb.blobdb.gc.bytes.overwritten",
+        BlobDbGcBytesExpired = "rocksdb.blobdb.gc.bytes.expired",
+        BlobDbGcBytesRelocated = "rocksdb.blobdb.gc.bytes.relocated",
+        BlobDbFifoNumFilesEvicted = "rocksdb.blobdb.fifo.num.files.evicted",
+        BlobDbFifoNumKeysEvicted = "rocksdb.blobdb.fifo.num.keys.evicted",
+        BlobDbFifoBytesEvicted = "rocksdb.blobdb.fifo.bytes.evicted",
+        TxnPrepareMutexOverhead = "rocksdb.txn.overhead.mutex.prepare",
+        TxnOldCommitMapMutexOverhead = "rocksdb.txn.overhead.mutex.old.commit.map",
+        TxnDuplicateKeyOverhead = "rocksdb.txn.overhead.duplicate.key",
+        TxnSnapshotMutexOverhead = "rocksdb.txn.overhead.mutex.snapshot",
+        TxnGetTryAgain = "rocksdb.txn.get.tryagain",
+        NumberMultigetKeysFound = "rocksdb.number.multiget.keys.found",
+        NoIteratorCreated = "rocksdb.num.iterator.created",
+        NoIteratorDeleted = "rocksdb.num.iterator.deleted",
+        BlockCacheCompressionDictMiss = "rocksdb.block.cache.compression.dict.miss",
+        BlockCacheCompressionDictHit = "rocksdb.block.cache.compression.dict.hit",
+        BlockCacheCompressionDictAdd = "rocksdb.block.cache.compression.dict.add",
+        BlockCacheCompressionDictBytesInsert = "rocksdb.block.cache.compression.dict.bytes.insert",
+        BlockCacheCompressionDictBytesEvict = "rocksdb.block.cache.compression.dict.bytes.evict",
+        BlockCacheAddRedundant = "rocksdb.block.cache.add.redundant",
+        BlockCacheIndexAddRedundant = "rocksdb.block.cache.index.add.redundant",
+        BlockCacheFilterAddRedundant = "rocksdb.block.cache.filter.add.redundant",
+        BlockCacheDataAddRedundant = "rocksdb.block.cache.data.add.redundant",
+        BlockCacheCompressionDictAddRedundant = "rocksdb.block.cache.compression.dict.add.redundant",
+        FilesMarkedTrash = "rocksdb.files.marked.trash",
+        FilesDeletedImmediately = "rocksdb.files.deleted.immediately",
+        ErrorHandlerBgErrorCount = "rocksdb.error.handler.bg.errro.count",
+        ErrorHandlerBgIoErrorCount = "rocksdb.error.handler.bg.io.errro.count",
+        ErrorHandlerBgRetryableIoErrorCount = "rocksdb.error.handler.bg.retryable.io.errro.count",
+        ErrorHandlerAutoresumeCount = "rocksdb.error.handler.autoresume.count",
+        ErrorHandlerAutoresumeRetryTotalCount = "rocksdb.error.handler.autoresume.retry.total.count",
+        ErrorHandlerAutoresumeSuccessCount = "rocksdb.error.handler.autoresume.success.count",
+        MemtablePayloadBytesAtFlush = "rocksdb.memtable.payload.bytes.at.flush",
+        MemtableGarbageBytesAtFlush = "rocksdb.memtable.garbage.bytes.at.flush",
+        SecondaryCacheHits = "rocksdb.secondary.cache.hits",
+        VerifyChecksumReadBytes = "rocksdb.verify_checksum.read.bytes",
+        BackupReadBytes = "rocksdb.backup.read.bytes",
+        BackupWriteBytes = "rocksdb.backup.write.bytes",
+        RemoteCompactReadBytes = "rocksdb.remote.compact.read.bytes",
+        RemoteCompactWriteBytes = "rocksdb.remote.compact.write.bytes",
+        HotFileReadBytes = "rocksdb.hot.file.read.bytes",
+        WarmFileReadBytes = "rocksdb.warm.file.read.bytes",
+        ColdFileReadBytes = "rocksdb.cold.file.read.bytes",
+        HotFileReadCount = "rocksdb.hot.file.read.count",
+        WarmFileReadCount = "rocksdb.warm.file.read.count",
+        ColdFileReadCount = "rocksdb.cold.file.read.count",
+        LastLevelReadBytes = "rocksdb.last.level.read.bytes",
+        LastLevelReadCount = "rocksdb.last.level.read.count",
+        NonLastLevelReadBytes = "rocksdb.non.last.level.read.bytes",
+        NonLastLevelReadCount = "rocksdb.non.last.level.read.count",
+        BlockChecksumComputeCount = "rocksdb.block.checksum.compute.count",
+        MultigetCoroutineCount = "rocksdb.multiget.coroutine.count",
+        BlobDbCacheMiss = "rocksdb.blobdb.cache.miss",
+        BlobDbCacheHit = "rocksdb.blobdb.cache.hit",
+        BlobDbCacheAdd = "rocksdb.blobdb.cache.add",
+        BlobDbCacheAddFailures = "rocksdb.blobdb.cache.add.failures",
+        BlobDbCacheBytesRead = "rocksdb.blobdb.cache.bytes.read",
+        BlobDbCacheBytesWrite = "rocksdb.blobdb.cache.bytes.write",
+    }
+}
+
+named_enum! {
+    /// A distribution of RocksDB statistics.
+    ///
+    /// The variants mirror `rocksdb::Histograms`, which only ever appends new
+    /// histograms.
+    pub enum Histogram {
+        DbGet = "rocksdb.db.get.micros",
+        DbWrite = "rocksdb.db.write.micros",
+        CompactionTime = "rocksdb.compaction.times.micros",
+        CompactionCpuTime = "rocksdb.compaction.times.cpu_micros",
+        SubcompactionSetupTime = "rocksdb.subcompaction.setup.times.micros",
+        TableSyncMicros = "rocksdb.table.sync.micros",
+        CompactionOutfileSyncMicros = "rocksdb.compaction.outfile.sync.micros",
+        WalFileSyncMicros = "rocksdb.wal.file.sync.micros",
+        ManifestFileSyncMicros = "rocksdb.manifest.file.sync.micros",
+        TableOpenIoMicros = "rocksdb.table.open.io.micros",
+        DbMultiget = "rocksdb.db.multiget.micros",
+        ReadBlockCompactionMicros = "rocksdb.read.block.compaction.micros",
+        ReadBlockGetMicros = "rocksdb.read.block.get.micros",
+        WriteRawBlockMicros = "rocksdb.write.raw.block.micros",
+        StallL0SlowdownCount = "rocksdb.l0.slowdown.count",
+        StallMemtableCompactionCount = "rocksdb.memtable.compaction.count",
+        StallL0NumFilesCount = "rocksdb.num.files.stall.count",
+        HardRateLimitDelayCount = "rocksdb.hard.rate.limit.delay.count",
+        SoftRateLimitDelayCount = "rocksdb.soft.rate.limit.delay.count",
+        NumFilesInSingleCompaction = "rocksdb.numfiles.in.singlecompaction",
+        DbSeek = "rocksdb.db.seek.micros",
+        WriteStall = "rocksdb.db.write.stall",
+        SstReadMicros = "rocksdb.sst.read.micros",
+        NumSubcompactionsScheduled = "rocksdb.num.subcompactions.scheduled",
+        BytesPerRead = "rocksdb.bytes.per.read",
+        BytesPerWrite = "rocksdb.bytes.per.write",
+        BytesPerMultiget = "rocksdb.bytes.per.multiget",
+        BytesCompressed = "rocksdb.bytes.compressed",
+        BytesDecompressed = "rocksdb.bytes.decompressed",
+        CompressionTimesNanos = "rocksdb.compression.times.nanos",
+        DecompressionTimesNanos = "rocksdb.decompression.times.nanos",
+        ReadNumMergeOperands = "rocksdb.read.num.merge_operands",
+        BlobDbKeySize = "rocksdb.blobdb.key.size",
+        BlobDbValueSize = "rocksdb.blobdb.value.size",
+        BlobDbWriteMicros = "rocksdb.blobdb.write.micros",
+        BlobDbGetMicros = "rocksdb.blobdb.get.micros",
+        BlobDbMultigetMicros = "rocksdb.blobdb.multiget.micros",
+        BlobDbSeekMicros = "rocksdb.blobdb.seek.micros",
+        BlobDbNextMicros = "rocksdb.blobdb.next.micros",
+        BlobDbPrevMicros = "rocksdb.blobdb.prev.micros",
+        BlobDbBlobFileWriteMicros = "rocksdb.blobdb.blob.file.write.micros",
+        BlobDbBlobFileReadMicros = "rocksdb.blobdb.blob.file.read.micros",
+        BlobDbBlobFileSyncMicros = "rocksdb.blobdb.blob.file.sync.micros",
+        BlobDbGcMicros = "rocksdb.blobdb.gc.micros",
+        BlobDbCompressionMicros = "rocksdb.blobdb.compression.micros",
+        BlobDbDecompressionMicros = "rocksdb.blobdb.decompression.micros",
+        FlushTime = "rocksdb.db.flush.micros",
+        SstBatchSize = "rocksdb.sst.batch.size",
+        NumIndexAndFilterBlocksReadPerLevel = "rocksdb.num.index.and.filter.blocks.read.per.level",
+        NumDataBlocksReadPerLevel = "rocksdb.num.data.blocks.read.per.level",
+        NumSstReadPerLevel = "rocksdb.num.sst.read.per.level",
+        ErrorHandlerAutoresumeRetryCount = "rocksdb.error.handler.autoresume.retry.count",
+        AsyncReadBytes = "rocksdb.async.read.bytes",
+        PollWaitMicros = "rocksdb.poll.wait.micros",
+        PrefetchedBytesDiscarded = "rocksdb.prefetched.bytes.discarded",
+        MultigetIoBatchSize = "rocksdb.multiget.io.batch.size",
+        NumLevelReadPerMultiget = "rocksdb.num.level.read.per.multiget",
+    }
+}
+
+/// The summary of a [`Histogram`].
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct HistogramData {
+    pub median: f64,
+    pub p95: f64,
+    pub p99: f64,
+    pub average: f64,
+    pub std_dev: f64,
+    pub max: f64,
+    pub min: f64,
+    pub count: u64,
+    pub sum: u64,
+}
+
+/// Which statistics are collected, see
+/// [`Options::set_statistics_level`](crate::Options::set_statistics_level).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(i32)]
+pub enum StatsLevel {
+    /// Collect no statistics, including tickers.
+    DisableAll = 0,
+    /// Collect tickers but no histograms or timers.
+    ExceptHistogramOrTimers = 1,
+    /// Collect tickers and histograms but no timers.
+    ExceptTimers = 2,
+    /// Collect all statistics except the timers which may be costly. This is
+    /// the default.
+    ExceptDetailedTimers = 3,
+    /// Collect all statistics except the time spent waiting for mutexes.
+    ExceptTimeForMutex = 4,
+    /// Collect all statistics.
+    All = 5,
+}
+
+impl StatsLevel {
+    pub(crate) fn from_raw(level: i32) -> StatsLevel {
+        match level {
+            0 => StatsLevel::DisableAll,
+            1 => StatsLevel::ExceptHistogramOrTimers,
+            2 => StatsLevel::ExceptTimers,
+            3 => StatsLevel::ExceptDetailedTimers,
+            4 => StatsLevel::ExceptTimeForMutex,
+            _ => StatsLevel::All,
+        }
+    }
+}
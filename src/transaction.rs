@@ -7,6 +7,7 @@ use crate::{
 };
 use libc::{c_char, c_uchar, c_void, size_t};
 use std::marker::PhantomData;
+use std::slice;
 
 pub struct Transaction<'a, T> {
     inner: *mut ffi::rocksdb_transaction_t,
@@ -29,6 +30,46 @@ impl<'a, T> Transaction<'a, T> {
         Ok(())
     }
 
+    /// Sets the name of the transaction, which is required to `prepare` it.
+    /// The name must be unique among the transactions of the database.
+    pub fn set_name<N: AsRef<[u8]>>(&self, name: N) -> Result<(), Error> {
+        let name = name.as_ref();
+        unsafe {
+            ffi_try!(ffi::rocksdb_transaction_set_name(
+                self.inner,
+                name.as_ptr() as *const c_char,
+                name.len() as size_t,
+            ));
+        }
+        Ok(())
+    }
+
+    /// Get the name of the transaction, empty if it has none.
+    pub fn name(&self) -> Vec<u8> {
+        unsafe {
+            let mut name_len: size_t = 0;
+            let name = ffi::rocksdb_transaction_get_name(self.inner, &mut name_len);
+            if name.is_null() {
+                return Vec::new();
+            }
+            let result = slice::from_raw_parts(name as *const u8, name_len).to_vec();
+            ffi::rocksdb_free(name as *mut c_void);
+            result
+        }
+    }
+
+    /// Prepares the transaction for a two-phase commit. Once prepared, the
+    /// transaction survives a restart until it is committed or rolled back,
+    /// see `TransactionDB::prepared_transactions`.
+    ///
+    /// The transaction must have a name.
+    pub fn prepare(&self) -> Result<(), Error> {
+        unsafe {
+            ffi_try!(ffi::rocksdb_transaction_prepare(self.inner));
+        }
+        Ok(())
+    }
+
     /// Transaction rollback
     pub fn rollback(&self) -> Result<(), Error> {
         unsafe { ffi_try!(ffi::rocksdb_transaction_rollback(self.inner,)) }
@@ -132,6 +173,88 @@ impl<'a, T> Transaction<'a, T> {
             }
         }
     }
+
+    /// Multi Get For Update, locking every key exclusively
+    /// ReadOptions: Default
+    pub fn multi_get_for_update<K, I>(&self, keys: I) -> Vec<Result<Option<DBVector>, Error>>
+    where
+        K: AsRef<[u8]>,
+        I: IntoIterator<Item = K>,
+    {
+        self.multi_get_for_update_full(keys.into_iter().map(|key| (None, key)), None)
+    }
+
+    /// Multi Get For Update with custom ReadOptions
+    pub fn multi_get_for_update_opt<K, I>(
+        &self,
+        keys: I,
+        readopts: &ReadOptions,
+    ) -> Vec<Result<Option<DBVector>, Error>>
+    where
+        K: AsRef<[u8]>,
+        I: IntoIterator<Item = K>,
+    {
+        self.multi_get_for_update_full(keys.into_iter().map(|key| (None, key)), Some(readopts))
+    }
+
+    pub fn multi_get_for_update_cf<'c, K, I>(&self, keys: I) -> Vec<Result<Option<DBVector>, Error>>
+    where
+        K: AsRef<[u8]>,
+        I: IntoIterator<Item = (&'c ColumnFamily, K)>,
+    {
+        self.multi_get_for_update_full(keys.into_iter().map(|(cf, key)| (Some(cf), key)), None)
+    }
+
+    pub fn multi_get_for_update_cf_opt<'c, K, I>(
+        &self,
+        keys: I,
+        readopts: &ReadOptions,
+    ) -> Vec<Result<Option<DBVector>, Error>>
+    where
+        K: AsRef<[u8]>,
+        I: IntoIterator<Item = (&'c ColumnFamily, K)>,
+    {
+        self.multi_get_for_update_full(
+            keys.into_iter().map(|(cf, key)| (Some(cf), key)),
+            Some(readopts),
+        )
+    }
+
+    fn multi_get_for_update_full<'c, K, I>(
+        &self,
+        keys: I,
+        readopts: Option<&ReadOptions>,
+    ) -> Vec<Result<Option<DBVector>, Error>>
+    where
+        K: AsRef<[u8]>,
+        I: IntoIterator<Item = (Option<&'c ColumnFamily>, K)>,
+    {
+        multi_get_with(keys, readopts, |ro_handle, args| unsafe {
+            match args.cfs {
+                Some(cfs) => ffi::rocksdb_transaction_multi_get_for_update_cf(
+                    self.handle(),
+                    ro_handle,
+                    cfs.as_ptr(),
+                    args.keys.len(),
+                    args.keys.as_ptr(),
+                    args.keys_sizes.as_ptr(),
+                    args.values.as_mut_ptr(),
+                    args.values_sizes.as_mut_ptr(),
+                    args.errs.as_mut_ptr(),
+                ),
+                None => ffi::rocksdb_transaction_multi_get_for_update(
+                    self.handle(),
+                    ro_handle,
+                    args.keys.len(),
+                    args.keys.as_ptr(),
+                    args.keys_sizes.as_ptr(),
+                    args.values.as_mut_ptr(),
+                    args.values_sizes.as_mut_ptr(),
+                    args.errs.as_mut_ptr(),
+                ),
+            }
+        })
+    }
 }
 
 impl<'a, T> Drop for Transaction<'a, T> {
@@ -196,6 +319,72 @@ where
             }
         }
     }
+
+    fn multi_get_cf_full<'c, K, I>(
+        &self,
+        keys: I,
+        readopts: Option<&ReadOptions>,
+    ) -> Vec<Result<Option<DBVector>, Error>>
+    where
+        K: AsRef<[u8]>,
+        I: IntoIterator<Item = (Option<&'c ColumnFamily>, K)>,
+    {
+        multi_get_with(keys, readopts, |ro_handle, args| unsafe {
+            match args.cfs {
+                Some(cfs) => ffi::rocksdb_transaction_multi_get_cf(
+                    self.handle(),
+                    ro_handle,
+                    cfs.as_ptr(),
+                    args.keys.len(),
+                    args.keys.as_ptr(),
+                    args.keys_sizes.as_ptr(),
+                    args.values.as_mut_ptr(),
+                    args.values_sizes.as_mut_ptr(),
+                    args.errs.as_mut_ptr(),
+                ),
+                None => ffi::rocksdb_transaction_multi_get(
+                    self.handle(),
+                    ro_handle,
+                    args.keys.len(),
+                    args.keys.as_ptr(),
+                    args.keys_sizes.as_ptr(),
+                    args.values.as_mut_ptr(),
+                    args.values_sizes.as_mut_ptr(),
+                    args.errs.as_mut_ptr(),
+                ),
+            }
+        })
+    }
+
+    fn batched_multi_get_cf_full<K, I>(
+        &self,
+        cf: &ColumnFamily,
+        keys: I,
+        sorted_input: bool,
+        readopts: Option<&ReadOptions>,
+    ) -> Vec<Result<Option<DBPinnableSlice<'_>>, Error>>
+    where
+        K: AsRef<[u8]>,
+        I: IntoIterator<Item = K>,
+    {
+        batched_multi_get_with(
+            keys,
+            readopts,
+            |ro_handle, keys, keys_sizes, values, errs| unsafe {
+                ffi::rocksdb_transaction_batched_multi_get_cf(
+                    self.handle(),
+                    ro_handle,
+                    cf.inner,
+                    keys.len(),
+                    keys.as_ptr(),
+                    keys_sizes.as_ptr(),
+                    values.as_mut_ptr(),
+                    errs.as_mut_ptr(),
+                    sorted_input,
+                )
+            },
+        )
+    }
 }
 impl<T> Iterate for Transaction<'_, T> {
     fn get_raw_iter<'a: 'b, 'b>(&'a self, readopts: &ReadOptions) -> DBRawIterator<'b> {
@@ -254,6 +443,37 @@ where
         ro.set_snapshot(self);
         self.db.get_cf_full(cf, key, Some(&ro))
     }
+
+    fn multi_get_cf_full<'c, K, I>(
+        &self,
+        keys: I,
+        readopts: Option<&ReadOptions>,
+    ) -> Vec<Result<Option<DBVector>, Error>>
+    where
+        K: AsRef<[u8]>,
+        I: IntoIterator<Item = (Option<&'c ColumnFamily>, K)>,
+    {
+        let mut ro = readopts.cloned().unwrap_or_default();
+        ro.set_snapshot(self);
+        self.db.multi_get_cf_full(keys, Some(&ro))
+    }
+
+    fn batched_multi_get_cf_full<K, I>(
+        &self,
+        cf: &ColumnFamily,
+        keys: I,
+        sorted_input: bool,
+        readopts: Option<&ReadOptions>,
+    ) -> Vec<Result<Option<DBPinnableSlice<'_>>, Error>>
+    where
+        K: AsRef<[u8]>,
+        I: IntoIterator<Item = K>,
+    {
+        let mut ro = readopts.cloned().unwrap_or_default();
+        ro.set_snapshot(self);
+        self.db
+            .batched_multi_get_cf_full(cf, keys, sorted_input, Some(&ro))
+    }
 }
 
 impl<'a, T> PutCF<()> for Transaction<'a, T> {
@@ -443,7 +663,7 @@ impl<'a, T> GetPinnedCF<'a> for Transaction<'a, T> {
             };
 
             if !err.is_null() {
-                return Err(Error::new(ffi_util::error_message(err)));
+                return Err(Error::from_status(ffi_util::error_message(err)));
             }
 
             if val.is_null() {
@@ -493,7 +713,7 @@ impl<'a, T> GetPinnedCF<'a> for TransactionSnapshot<'a, T> {
             };
 
             if !err.is_null() {
-                return Err(Error::new(ffi_util::error_message(err)));
+                return Err(Error::from_status(ffi_util::error_message(err)));
             }
 
             if val.is_null() {
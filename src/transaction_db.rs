@@ -1,27 +1,36 @@
 use crate::{
+    checkpoint::{ExportImportFilesMetaData, RawExportImportFilesMetaData},
     db_options::OptionsMustOutliveDB,
     db_vector::DBVector,
     ffi_util::to_cstring,
     handle::{ConstHandle, Handle},
+    metadata::{self, ColumnFamilyMetaData, LiveFile},
     open_raw::{OpenRaw, OpenRawFFI},
     ops::*,
-    write_batch::WriteBatch,
-    ColumnFamily, DBRawIterator, Error, Options, ReadOptions, Transaction, WriteOptions,
+    wal_iterator::WalIterator,
+    ColumnFamily, DBPinnableSlice, DBRawIterator, Error, Options, ReadOptions, Transaction,
+    WriteOptions,
 };
 
 use crate::ffi;
-use libc::{c_char, c_uchar, size_t};
+use libc::{c_char, c_int, c_uchar, c_void, size_t};
 use std::collections::BTreeMap;
 use std::marker::PhantomData;
 use std::path::Path;
 use std::path::PathBuf;
 use std::ptr;
+use std::slice;
+use std::sync::Mutex;
 
 /// A transaction database.
 pub struct TransactionDB {
     inner: *mut ffi::rocksdb_transactiondb_t,
     path: PathBuf,
     cfs: BTreeMap<String, ColumnFamily>,
+    base_db: *mut ffi::rocksdb_t,
+    // Transactions recovered in the prepared state when opening, not yet
+    // handed out. The database deletes those left on close.
+    recovered: Mutex<Vec<*mut ffi::rocksdb_transaction_t>>,
     _outlive: Vec<OptionsMustOutliveDB>,
 }
 
@@ -29,6 +38,10 @@ impl TransactionDB {
     pub fn path(&self) -> &Path {
         self.path.as_path()
     }
+
+    pub fn base_db_ptr(&self) -> *mut ffi::rocksdb_t {
+        self.base_db
+    }
 }
 
 impl Handle<ffi::rocksdb_transactiondb_t> for TransactionDB {
@@ -83,10 +96,24 @@ impl OpenRaw for TransactionDB {
             .into_iter()
             .map(|(k, h)| (k, ColumnFamily::new(h)))
             .collect();
+        let base_db = unsafe { ffi::rocksdb_transactiondb_get_base_db(pointer) };
+        let recovered = unsafe {
+            let mut cnt: size_t = 0;
+            let txns = ffi::rocksdb_transactiondb_get_prepared_transactions(pointer, &mut cnt);
+            if txns.is_null() {
+                Vec::new()
+            } else {
+                let recovered = slice::from_raw_parts(txns, cnt).to_vec();
+                ffi::rocksdb_free(txns as *mut c_void);
+                recovered
+            }
+        };
         Ok(TransactionDB {
             inner: pointer,
             path,
             cfs,
+            base_db,
+            recovered: Mutex::new(recovered),
             _outlive: outlive,
         })
     }
@@ -107,6 +134,20 @@ impl Write for TransactionDB {}
 unsafe impl Send for TransactionDB {}
 unsafe impl Sync for TransactionDB {}
 
+impl TransactionDB {
+    /// Returns the transactions that were prepared but neither committed nor
+    /// rolled back before the database was last closed, and recovered when it
+    /// was opened.
+    ///
+    /// Each of them has to be either committed or rolled back. They are handed
+    /// out only once, later calls return the remaining ones, that is none.
+    /// Transactions prepared since the database was opened are never returned.
+    pub fn prepared_transactions(&self) -> Vec<Transaction<'_, TransactionDB>> {
+        let recovered = std::mem::take(&mut *self.recovered.lock().unwrap());
+        recovered.into_iter().map(Transaction::new).collect()
+    }
+}
+
 impl TransactionBegin for TransactionDB {
     type WriteOptions = WriteOptions;
     type TransactionOptions = TransactionOptions;
@@ -160,6 +201,7 @@ impl IterateCF for TransactionDB {
 impl Drop for TransactionDB {
     fn drop(&mut self) {
         unsafe {
+            ffi::rocksdb_transactiondb_close_base_db(self.base_db);
             ffi::rocksdb_transactiondb_close(self.inner);
         }
     }
@@ -203,6 +245,41 @@ impl TransactionDBOptions {
             )
         }
     }
+
+    /// Sets when the data of a transaction is written to the DB, see
+    /// `TxnDBWritePolicy`.
+    ///
+    /// Default: `TxnDBWritePolicy::WriteCommitted`
+    pub fn set_write_policy(&self, write_policy: TxnDBWritePolicy) {
+        unsafe {
+            ffi::rocksdb_transactiondb_options_set_write_policy(self.inner, write_policy as c_int)
+        }
+    }
+
+    /// Skips the locking of keys written by transactions, which is only safe
+    /// when the application guarantees that concurrent transactions never
+    /// write the same keys, e.g. when recovering prepared transactions.
+    ///
+    /// Default: false
+    pub fn set_skip_concurrency_control(&self, skip: bool) {
+        unsafe {
+            ffi::rocksdb_transactiondb_options_set_skip_concurrency_control(
+                self.inner,
+                skip as c_uchar,
+            )
+        }
+    }
+}
+
+/// When the data of a transaction is written to the DB.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum TxnDBWritePolicy {
+    /// Write only the committed data.
+    WriteCommitted = ffi::rocksdb_txndb_write_committed as isize,
+    /// Write the data after the prepare phase of a two-phase commit.
+    WritePrepared = ffi::rocksdb_txndb_write_prepared as isize,
+    /// Write the data before the prepare phase of a two-phase commit.
+    WriteUnprepared = ffi::rocksdb_txndb_write_unprepared as isize,
 }
 
 impl Drop for TransactionDBOptions {
@@ -262,6 +339,14 @@ impl TransactionOptions {
             ffi::rocksdb_transaction_options_set_set_snapshot(self.inner, set_snapshot as c_uchar);
         }
     }
+
+    /// Allows the transaction to be committed without calling `prepare` first,
+    /// even though it has a name.
+    pub fn set_skip_prepare(&self, skip_prepare: bool) {
+        unsafe {
+            ffi::rocksdb_transaction_options_set_skip_prepare(self.inner, skip_prepare as c_uchar)
+        }
+    }
 }
 
 impl Drop for TransactionOptions {
@@ -286,6 +371,23 @@ impl CreateCheckpointObject for TransactionDB {
     }
 }
 
+impl GetUpdatesSince for TransactionDB {
+    fn get_updates_since(&self, seq_number: u64) -> Result<WalIterator<'_>, Error> {
+        unsafe {
+            let iter = ffi_try!(ffi::rocksdb_transactiondb_get_updates_since(
+                self.inner,
+                seq_number,
+                ptr::null(),
+            ));
+            Ok(WalIterator::new(iter))
+        }
+    }
+
+    fn latest_sequence_number(&self) -> u64 {
+        unsafe { ffi::rocksdb_transactiondb_get_latest_sequence_number(self.inner) }
+    }
+}
+
 impl GetCF<ReadOptions> for TransactionDB {
     fn get_cf_full<K: AsRef<[u8]>>(
         &self,
@@ -329,6 +431,72 @@ impl GetCF<ReadOptions> for TransactionDB {
             }
         }
     }
+
+    fn multi_get_cf_full<'c, K, I>(
+        &self,
+        keys: I,
+        readopts: Option<&ReadOptions>,
+    ) -> Vec<Result<Option<DBVector>, Error>>
+    where
+        K: AsRef<[u8]>,
+        I: IntoIterator<Item = (Option<&'c ColumnFamily>, K)>,
+    {
+        multi_get_with(keys, readopts, |ro_handle, args| unsafe {
+            match args.cfs {
+                Some(cfs) => ffi::rocksdb_transactiondb_multi_get_cf(
+                    self.handle(),
+                    ro_handle,
+                    cfs.as_ptr(),
+                    args.keys.len(),
+                    args.keys.as_ptr(),
+                    args.keys_sizes.as_ptr(),
+                    args.values.as_mut_ptr(),
+                    args.values_sizes.as_mut_ptr(),
+                    args.errs.as_mut_ptr(),
+                ),
+                None => ffi::rocksdb_transactiondb_multi_get(
+                    self.handle(),
+                    ro_handle,
+                    args.keys.len(),
+                    args.keys.as_ptr(),
+                    args.keys_sizes.as_ptr(),
+                    args.values.as_mut_ptr(),
+                    args.values_sizes.as_mut_ptr(),
+                    args.errs.as_mut_ptr(),
+                ),
+            }
+        })
+    }
+
+    fn batched_multi_get_cf_full<K, I>(
+        &self,
+        cf: &ColumnFamily,
+        keys: I,
+        sorted_input: bool,
+        readopts: Option<&ReadOptions>,
+    ) -> Vec<Result<Option<DBPinnableSlice<'_>>, Error>>
+    where
+        K: AsRef<[u8]>,
+        I: IntoIterator<Item = K>,
+    {
+        batched_multi_get_with(
+            keys,
+            readopts,
+            |ro_handle, keys, keys_sizes, values, errs| unsafe {
+                ffi::rocksdb_transactiondb_batched_multi_get_cf(
+                    self.handle(),
+                    ro_handle,
+                    cf.handle(),
+                    keys.len(),
+                    keys.as_ptr(),
+                    keys_sizes.as_ptr(),
+                    values.as_mut_ptr(),
+                    errs.as_mut_ptr(),
+                    sorted_input,
+                )
+            },
+        )
+    }
 }
 
 impl PutCF<WriteOptions> for TransactionDB {
@@ -475,6 +643,8 @@ impl CreateCF for TransactionDB {
             name.as_ref(),
             "Failed to convert path to CString when opening rocksdb",
         )?;
+        let mut opts = opts.clone();
+        opts.set_compaction_filter_factory_cf_name(&cname);
         unsafe {
             let cf_handle = ffi_try!(ffi::rocksdb_transactiondb_create_column_family(
                 self.handle(),
@@ -487,6 +657,66 @@ impl CreateCF for TransactionDB {
         };
         Ok(())
     }
+
+    fn create_cf_with_import<N: AsRef<str>>(
+        &mut self,
+        name: N,
+        opts: &Options,
+        metadata: &ExportImportFilesMetaData,
+        move_files: bool,
+    ) -> Result<(), Error> {
+        let cname = to_cstring(
+            name.as_ref(),
+            "Failed to convert path to CString when opening rocksdb",
+        )?;
+        let metadata = RawExportImportFilesMetaData::from_metadata(metadata)?;
+        let mut opts = opts.clone();
+        opts.set_compaction_filter_factory_cf_name(&cname);
+        unsafe {
+            let cf_handle = ffi_try!(ffi::rocksdb_transactiondb_create_column_family_with_import(
+                self.handle(),
+                opts.const_handle(),
+                cname.as_ptr(),
+                move_files as c_uchar,
+                metadata.inner,
+            ));
+
+            self.get_mut_cfs()
+                .insert(name.as_ref().to_string(), ColumnFamily::new(cf_handle));
+        };
+        Ok(())
+    }
+}
+
+impl GetLiveFilesMetadata for TransactionDB {
+    fn get_live_files_metadata(&self) -> Vec<LiveFile> {
+        unsafe { metadata::live_files(self.base_db) }
+    }
+}
+
+impl GetColumnFamilyMetadata for TransactionDB {
+    fn get_column_family_metadata(&self, cf: &ColumnFamily) -> ColumnFamilyMetaData {
+        unsafe { metadata::column_family_metadata(self.base_db, cf.inner) }
+    }
+}
+
+impl FileDeletions for TransactionDB {
+    fn disable_file_deletions_raw(&self) -> Result<(), Error> {
+        unsafe {
+            ffi_try!(ffi::rocksdb_disable_file_deletions(self.base_db));
+        }
+        Ok(())
+    }
+
+    fn enable_file_deletions(&self, force: bool) -> Result<(), Error> {
+        unsafe {
+            ffi_try!(ffi::rocksdb_enable_file_deletions(
+                self.base_db,
+                force as c_uchar,
+            ));
+        }
+        Ok(())
+    }
 }
 
 impl TransactionDB {
@@ -524,6 +754,37 @@ impl<'a> GetCF<ReadOptions> for Snapshot<'a> {
 
         self.db.get_cf_full(cf, key, Some(&ro))
     }
+
+    fn multi_get_cf_full<'c, K, I>(
+        &self,
+        keys: I,
+        readopts: Option<&ReadOptions>,
+    ) -> Vec<Result<Option<DBVector>, Error>>
+    where
+        K: AsRef<[u8]>,
+        I: IntoIterator<Item = (Option<&'c ColumnFamily>, K)>,
+    {
+        let mut ro = readopts.cloned().unwrap_or_default();
+        ro.set_snapshot(self);
+        self.db.multi_get_cf_full(keys, Some(&ro))
+    }
+
+    fn batched_multi_get_cf_full<K, I>(
+        &self,
+        cf: &ColumnFamily,
+        keys: I,
+        sorted_input: bool,
+        readopts: Option<&ReadOptions>,
+    ) -> Vec<Result<Option<DBPinnableSlice<'_>>, Error>>
+    where
+        K: AsRef<[u8]>,
+        I: IntoIterator<Item = K>,
+    {
+        let mut ro = readopts.cloned().unwrap_or_default();
+        ro.set_snapshot(self);
+        self.db
+            .batched_multi_get_cf_full(cf, keys, sorted_input, Some(&ro))
+    }
 }
 
 impl<'a> Drop for Snapshot<'a> {
@@ -555,22 +816,15 @@ impl IterateCF for Snapshot<'_> {
 }
 
 impl WriteOps for TransactionDB {
-    fn write_full(
+    fn write_full<B: WriteBatchLike>(
         &self,
-        batch: &WriteBatch,
+        batch: &B,
         writeopts: Option<&WriteOptions>,
     ) -> Result<(), Error> {
         let mut default_writeopts = None;
 
         let wo_handle = WriteOptions::input_or_default(writeopts, &mut default_writeopts)?;
 
-        unsafe {
-            ffi_try!(ffi::rocksdb_transactiondb_write(
-                self.handle(),
-                wo_handle,
-                batch.handle(),
-            ));
-            Ok(())
-        }
+        unsafe { batch.write_to_transaction_db(self.handle(), wo_handle) }
     }
 }
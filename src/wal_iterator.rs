@@ -0,0 +1,111 @@
+// Copyright 2019 Tyler Neely
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+use crate::{Error, WriteBatch};
+use std::marker::PhantomData;
+
+/// An iterator over the write batches in the write-ahead log, created with
+/// ``GetUpdatesSince::get_updates_since``.
+///
+/// Yields the batches as ``(sequence_number, WriteBatch)`` pairs, where the
+/// sequence number is the one of the first write in the batch. Once the end of
+/// the log is reached the iterator stops, a failure of the underlying iterator
+/// is yielded as its last item.
+///
+/// ```
+/// use ckb_rocksdb::prelude::*;
+/// # use ckb_rocksdb::TemporaryDBPath;
+///
+/// let path = "_path_for_rocksdb_storage_wal";
+/// # let path = TemporaryDBPath::new();
+/// # {
+///
+/// let db = DB::open_default(&path).unwrap();
+/// db.put(b"my key", b"my value").unwrap();
+///
+/// for item in db.get_updates_since(0).unwrap() {
+///     let (seq, batch) = item.unwrap();
+///     println!("Saw {} writes at {}", batch.len(), seq);
+/// }
+/// # }
+/// ```
+pub struct WalIterator<'a> {
+    inner: *mut ffi::rocksdb_wal_iterator_t,
+    just_created: bool,
+    done: bool,
+    db: PhantomData<&'a ()>,
+}
+
+unsafe impl<'a> Send for WalIterator<'a> {}
+
+impl<'a> WalIterator<'a> {
+    pub(crate) fn new(inner: *mut ffi::rocksdb_wal_iterator_t) -> WalIterator<'a> {
+        WalIterator {
+            inner,
+            just_created: true,
+            done: false,
+            db: PhantomData,
+        }
+    }
+
+    /// Returns true if the iterator is positioned at a write batch.
+    pub fn valid(&self) -> bool {
+        unsafe { ffi::rocksdb_wal_iter_valid(self.inner) != 0 }
+    }
+
+    /// Returns an error if the iterator has stopped because of a failure
+    /// rather than because it reached the end of the log.
+    pub fn status(&self) -> Result<(), Error> {
+        unsafe {
+            ffi_try!(ffi::rocksdb_wal_iter_status(self.inner));
+        }
+        Ok(())
+    }
+}
+
+impl<'a> Iterator for WalIterator<'a> {
+    type Item = Result<(u64, WriteBatch), Error>;
+
+    fn next(&mut self) -> Option<Result<(u64, WriteBatch), Error>> {
+        if self.done {
+            return None;
+        }
+        // The iterator is already positioned at the first batch when created
+        if self.just_created {
+            self.just_created = false;
+        } else {
+            unsafe {
+                ffi::rocksdb_wal_iter_next(self.inner);
+            }
+        }
+
+        if self.valid() {
+            let mut seq: u64 = 0;
+            let batch = unsafe { ffi::rocksdb_wal_iter_get_batch(self.inner, &mut seq) };
+            Some(Ok((seq, WriteBatch { inner: batch })))
+        } else {
+            self.done = true;
+            self.status().err().map(Err)
+        }
+    }
+}
+
+impl<'a> Drop for WalIterator<'a> {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::rocksdb_wal_iter_destroy(self.inner);
+        }
+    }
+}
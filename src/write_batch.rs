@@ -17,7 +17,10 @@ use crate::ffi;
 
 use crate::{handle::Handle, ColumnFamily, Error};
 
-use libc::{c_char, size_t};
+use libc::{c_char, c_void, size_t};
+use std::any::Any;
+use std::panic::{self, AssertUnwindSafe};
+use std::slice;
 
 /// An atomic batch of write operations.
 ///
@@ -43,10 +46,104 @@ use libc::{c_char, size_t};
 /// # }
 /// ```
 pub struct WriteBatch {
-    inner: *mut ffi::rocksdb_writebatch_t,
+    pub(crate) inner: *mut ffi::rocksdb_writebatch_t,
+}
+
+/// Receives the operations recorded in a `WriteBatch`, see `WriteBatch::iterate`.
+///
+/// Operations on the default column family are passed to `put`, `merge`,
+/// `delete`, `single_delete` and `delete_range`. Operations on other column families are passed
+/// to the `_cf` variants along with the id of the column family, see
+/// `ColumnFamily::id`. Every method is required, so a handler cannot silently
+/// drop a kind of operation.
+pub trait WriteBatchHandler {
+    fn put(&mut self, key: &[u8], value: &[u8]);
+
+    fn delete(&mut self, key: &[u8]);
+
+    fn merge(&mut self, key: &[u8], value: &[u8]);
+
+    fn single_delete(&mut self, key: &[u8]);
+
+    fn delete_range(&mut self, begin_key: &[u8], end_key: &[u8]);
+
+    fn put_cf(&mut self, cf_id: u32, key: &[u8], value: &[u8]);
+
+    fn delete_cf(&mut self, cf_id: u32, key: &[u8]);
+
+    fn merge_cf(&mut self, cf_id: u32, key: &[u8], value: &[u8]);
+
+    fn single_delete_cf(&mut self, cf_id: u32, key: &[u8]);
+
+    fn delete_range_cf(&mut self, cf_id: u32, begin_key: &[u8], end_key: &[u8]);
 }
 
 impl WriteBatch {
+    /// Create a WriteBatch from the serialized representation returned by
+    /// `data`.
+    pub fn from_data(data: &[u8]) -> Result<WriteBatch, Error> {
+        // sequence number (8 bytes) and count (4 bytes)
+        if data.len() < 12 {
+            return Err(Error::from_status(
+                "Corruption: malformed WriteBatch (too small)".to_owned(),
+            ));
+        }
+
+        unsafe {
+            Ok(WriteBatch {
+                inner: ffi::rocksdb_writebatch_create_from(
+                    data.as_ptr() as *const c_char,
+                    data.len() as size_t,
+                ),
+            })
+        }
+    }
+
+    /// Return the serialized representation of the batch, which can be
+    /// turned back into a batch with `from_data`.
+    pub fn data(&self) -> &[u8] {
+        unsafe {
+            let mut batch_size: size_t = 0;
+            let batch_data = ffi::rocksdb_writebatch_data(self.inner, &mut batch_size);
+            slice::from_raw_parts(batch_data as *const u8, batch_size)
+        }
+    }
+
+    /// Pass the operations of the batch to `handler`, in the order they
+    /// were added.
+    ///
+    /// If `handler` panics, the remaining operations are skipped and the panic
+    /// is resumed once RocksDB has returned.
+    pub fn iterate<H: WriteBatchHandler>(&self, handler: &mut H) -> Result<(), Error> {
+        let mut state = IterateState {
+            handler,
+            panic: None,
+        };
+        let result = self.iterate_state(&mut state);
+        if let Some(payload) = state.panic {
+            panic::resume_unwind(payload);
+        }
+        result
+    }
+
+    fn iterate_state<H: WriteBatchHandler>(
+        &self,
+        state: &mut IterateState<'_, H>,
+    ) -> Result<(), Error> {
+        unsafe {
+            ffi_try!(ffi::rocksdb_writebatch_iterate_cf(
+                self.inner,
+                state as *mut IterateState<'_, H> as *mut c_void,
+                Some(writebatch_put_callback::<H>),
+                Some(writebatch_delete_callback::<H>),
+                Some(writebatch_single_delete_callback::<H>),
+                Some(writebatch_merge_callback::<H>),
+                Some(writebatch_delete_range_callback::<H>),
+            ));
+        }
+        Ok(())
+    }
+
     pub fn len(&self) -> usize {
         unsafe { ffi::rocksdb_writebatch_count(self.inner) as usize }
     }
@@ -232,6 +329,118 @@ impl WriteBatch {
     }
 }
 
+/// The state passed to the callbacks of `WriteBatch::iterate`.
+struct IterateState<'a, H> {
+    handler: &'a mut H,
+    panic: Option<Box<dyn Any + Send>>,
+}
+
+impl<H> IterateState<'_, H> {
+    /// Calls `f` with the handler unless it has already panicked.
+    fn call(&mut self, f: impl FnOnce(&mut H)) {
+        if self.panic.is_none() {
+            let handler = &mut *self.handler;
+            if let Err(payload) = panic::catch_unwind(AssertUnwindSafe(|| f(handler))) {
+                self.panic = Some(payload);
+            }
+        }
+    }
+}
+
+unsafe extern "C" fn writebatch_put_callback<H: WriteBatchHandler>(
+    state: *mut c_void,
+    cf_id: u32,
+    k: *const c_char,
+    klen: size_t,
+    v: *const c_char,
+    vlen: size_t,
+) {
+    let state = &mut *(state as *mut IterateState<'_, H>);
+    let key = slice::from_raw_parts(k as *const u8, klen);
+    let value = slice::from_raw_parts(v as *const u8, vlen);
+    state.call(|handler| {
+        if cf_id == 0 {
+            handler.put(key, value);
+        } else {
+            handler.put_cf(cf_id, key, value);
+        }
+    });
+}
+
+unsafe extern "C" fn writebatch_delete_callback<H: WriteBatchHandler>(
+    state: *mut c_void,
+    cf_id: u32,
+    k: *const c_char,
+    klen: size_t,
+) {
+    let state = &mut *(state as *mut IterateState<'_, H>);
+    let key = slice::from_raw_parts(k as *const u8, klen);
+    state.call(|handler| {
+        if cf_id == 0 {
+            handler.delete(key);
+        } else {
+            handler.delete_cf(cf_id, key);
+        }
+    });
+}
+
+unsafe extern "C" fn writebatch_single_delete_callback<H: WriteBatchHandler>(
+    state: *mut c_void,
+    cf_id: u32,
+    k: *const c_char,
+    klen: size_t,
+) {
+    let state = &mut *(state as *mut IterateState<'_, H>);
+    let key = slice::from_raw_parts(k as *const u8, klen);
+    state.call(|handler| {
+        if cf_id == 0 {
+            handler.single_delete(key);
+        } else {
+            handler.single_delete_cf(cf_id, key);
+        }
+    });
+}
+
+unsafe extern "C" fn writebatch_merge_callback<H: WriteBatchHandler>(
+    state: *mut c_void,
+    cf_id: u32,
+    k: *const c_char,
+    klen: size_t,
+    v: *const c_char,
+    vlen: size_t,
+) {
+    let state = &mut *(state as *mut IterateState<'_, H>);
+    let key = slice::from_raw_parts(k as *const u8, klen);
+    let value = slice::from_raw_parts(v as *const u8, vlen);
+    state.call(|handler| {
+        if cf_id == 0 {
+            handler.merge(key, value);
+        } else {
+            handler.merge_cf(cf_id, key, value);
+        }
+    });
+}
+
+unsafe extern "C" fn writebatch_delete_range_callback<H: WriteBatchHandler>(
+    state: *mut c_void,
+    cf_id: u32,
+    begin_k: *const c_char,
+    begin_klen: size_t,
+    end_k: *const c_char,
+    end_klen: size_t,
+) {
+    let state = &mut *(state as *mut IterateState<'_, H>);
+    let begin_key = slice::from_raw_parts(begin_k as *const u8, begin_klen);
+    let end_key = slice::from_raw_parts(end_k as *const u8, end_klen);
+    state.call(|handler| {
+        if cf_id == 0 {
+            handler.delete_range(begin_key, end_key);
+        } else {
+            handler.delete_range_cf(cf_id, begin_key, end_key);
+        }
+    });
+}
+
 impl Default for WriteBatch {
     fn default() -> WriteBatch {
         WriteBatch {
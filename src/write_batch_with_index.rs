@@ -0,0 +1,416 @@
+// Copyright 2019 Tyler Neely
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+use crate::ffi;
+
+use crate::{
+    handle::Handle, ops::Read, ColumnFamily, DBRawIterator, DBVector, Error, Options, ReadOptions,
+};
+
+use libc::{c_char, c_uchar, size_t};
+use std::marker::PhantomData;
+use std::mem;
+use std::slice;
+
+/// A `WriteBatch` which keeps an index of its updates, so that they can be
+/// read back before the batch is written to the database.
+///
+/// ```
+/// use ckb_rocksdb::{prelude::*, WriteBatchWithIndex};
+/// # use ckb_rocksdb::TemporaryDBPath;
+///
+/// let path = "_path_for_rocksdb_storage_wbwi";
+/// # let path = TemporaryDBPath::new();
+/// # {
+///
+/// let db = DB::open_default(&path).unwrap();
+/// db.put(b"key1", b"value1").unwrap();
+///
+/// let mut batch = WriteBatchWithIndex::default();
+/// batch.put(b"key2", b"value2").unwrap();
+///
+/// let value = batch.get_from_batch_and_db(&db, b"key1").unwrap();
+/// assert_eq!(value.as_deref(), Some(&b"value1"[..]));
+/// let value = batch.get_from_batch_and_db(&db, b"key2").unwrap();
+/// assert_eq!(value.as_deref(), Some(&b"value2"[..]));
+///
+/// db.write(&batch).unwrap(); // Atomically commits the batch
+/// # }
+/// ```
+pub struct WriteBatchWithIndex {
+    pub(crate) inner: *mut ffi::rocksdb_writebatch_wi_t,
+}
+
+impl WriteBatchWithIndex {
+    /// Create a batch with `reserved_bytes` preallocated.
+    ///
+    /// If `overwrite_key` is true, a later update of a key replaces the
+    /// earlier one in the index, so iterators only see the latest update.
+    pub fn new(reserved_bytes: usize, overwrite_key: bool) -> WriteBatchWithIndex {
+        WriteBatchWithIndex {
+            inner: unsafe {
+                ffi::rocksdb_writebatch_wi_create(
+                    reserved_bytes as size_t,
+                    overwrite_key as c_uchar,
+                )
+            },
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        unsafe { ffi::rocksdb_writebatch_wi_count(self.inner) as usize }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Return the serialized representation of the batch, which can be
+    /// turned into a `WriteBatch` with `WriteBatch::from_data`.
+    pub fn data(&self) -> &[u8] {
+        unsafe {
+            let mut batch_size: size_t = 0;
+            let batch_data = ffi::rocksdb_writebatch_wi_data(self.inner, &mut batch_size);
+            slice::from_raw_parts(batch_data as *const u8, batch_size)
+        }
+    }
+
+    /// Insert a value into the database under the given key.
+    pub fn put<K, V>(&mut self, key: K, value: V) -> Result<(), Error>
+    where
+        K: AsRef<[u8]>,
+        V: AsRef<[u8]>,
+    {
+        let key = key.as_ref();
+        let value = value.as_ref();
+
+        unsafe {
+            ffi::rocksdb_writebatch_wi_put(
+                self.handle(),
+                key.as_ptr() as *const c_char,
+                key.len() as size_t,
+                value.as_ptr() as *const c_char,
+                value.len() as size_t,
+            );
+            Ok(())
+        }
+    }
+
+    pub fn put_cf<K, V>(&mut self, cf: &ColumnFamily, key: K, value: V) -> Result<(), Error>
+    where
+        K: AsRef<[u8]>,
+        V: AsRef<[u8]>,
+    {
+        let key = key.as_ref();
+        let value = value.as_ref();
+
+        unsafe {
+            ffi::rocksdb_writebatch_wi_put_cf(
+                self.handle(),
+                cf.handle(),
+                key.as_ptr() as *const c_char,
+                key.len() as size_t,
+                value.as_ptr() as *const c_char,
+                value.len() as size_t,
+            );
+            Ok(())
+        }
+    }
+
+    pub fn merge<K, V>(&mut self, key: K, value: V) -> Result<(), Error>
+    where
+        K: AsRef<[u8]>,
+        V: AsRef<[u8]>,
+    {
+        let key = key.as_ref();
+        let value = value.as_ref();
+
+        unsafe {
+            ffi::rocksdb_writebatch_wi_merge(
+                self.handle(),
+                key.as_ptr() as *const c_char,
+                key.len() as size_t,
+                value.as_ptr() as *const c_char,
+                value.len() as size_t,
+            );
+            Ok(())
+        }
+    }
+
+    pub fn merge_cf<K, V>(&mut self, cf: &ColumnFamily, key: K, value: V) -> Result<(), Error>
+    where
+        K: AsRef<[u8]>,
+        V: AsRef<[u8]>,
+    {
+        let key = key.as_ref();
+        let value = value.as_ref();
+
+        unsafe {
+            ffi::rocksdb_writebatch_wi_merge_cf(
+                self.handle(),
+                cf.handle(),
+                key.as_ptr() as *const c_char,
+                key.len() as size_t,
+                value.as_ptr() as *const c_char,
+                value.len() as size_t,
+            );
+            Ok(())
+        }
+    }
+
+    /// Remove the database entry for key.
+    pub fn delete<K: AsRef<[u8]>>(&mut self, key: K) -> Result<(), Error> {
+        let key = key.as_ref();
+
+        unsafe {
+            ffi::rocksdb_writebatch_wi_delete(
+                self.handle(),
+                key.as_ptr() as *const c_char,
+                key.len() as size_t,
+            );
+            Ok(())
+        }
+    }
+
+    pub fn delete_cf<K: AsRef<[u8]>>(&mut self, cf: &ColumnFamily, key: K) -> Result<(), Error> {
+        let key = key.as_ref();
+
+        unsafe {
+            ffi::rocksdb_writebatch_wi_delete_cf(
+                self.handle(),
+                cf.handle(),
+                key.as_ptr() as *const c_char,
+                key.len() as size_t,
+            );
+            Ok(())
+        }
+    }
+
+    /// Clear all updates buffered in this batch.
+    pub fn clear(&mut self) -> Result<(), Error> {
+        unsafe {
+            ffi::rocksdb_writebatch_wi_clear(self.inner);
+        }
+        Ok(())
+    }
+
+    /// Read a key from the batch only, without looking at the database.
+    ///
+    /// Returns an error if the key has pending merges, since resolving them
+    /// needs the merge operator of the database, see `get_from_batch_and_db`.
+    pub fn get_from_batch<K: AsRef<[u8]>>(
+        &self,
+        options: &Options,
+        key: K,
+    ) -> Result<Option<DBVector>, Error> {
+        let key = key.as_ref();
+        let mut val_len: size_t = 0;
+
+        unsafe {
+            let val = ffi_try!(ffi::rocksdb_writebatch_wi_get_from_batch(
+                self.inner,
+                options.handle(),
+                key.as_ptr() as *const c_char,
+                key.len() as size_t,
+                &mut val_len,
+            )) as *mut u8;
+
+            Ok(to_dbvector(val, val_len))
+        }
+    }
+
+    pub fn get_from_batch_cf<K: AsRef<[u8]>>(
+        &self,
+        options: &Options,
+        cf: &ColumnFamily,
+        key: K,
+    ) -> Result<Option<DBVector>, Error> {
+        let key = key.as_ref();
+        let mut val_len: size_t = 0;
+
+        unsafe {
+            let val = ffi_try!(ffi::rocksdb_writebatch_wi_get_from_batch_cf(
+                self.inner,
+                options.handle(),
+                cf.handle(),
+                key.as_ptr() as *const c_char,
+                key.len() as size_t,
+                &mut val_len,
+            )) as *mut u8;
+
+            Ok(to_dbvector(val, val_len))
+        }
+    }
+
+    /// Read a key as if the batch had been written to `db`: updates in the
+    /// batch take precedence over the database.
+    pub fn get_from_batch_and_db<T, K>(&self, db: &T, key: K) -> Result<Option<DBVector>, Error>
+    where
+        T: Handle<ffi::rocksdb_t> + Read,
+        K: AsRef<[u8]>,
+    {
+        self.get_from_batch_and_db_full(db, None, key, None)
+    }
+
+    pub fn get_from_batch_and_db_opt<T, K>(
+        &self,
+        db: &T,
+        key: K,
+        readopts: &ReadOptions,
+    ) -> Result<Option<DBVector>, Error>
+    where
+        T: Handle<ffi::rocksdb_t> + Read,
+        K: AsRef<[u8]>,
+    {
+        self.get_from_batch_and_db_full(db, None, key, Some(readopts))
+    }
+
+    pub fn get_from_batch_and_db_cf<T, K>(
+        &self,
+        db: &T,
+        cf: &ColumnFamily,
+        key: K,
+    ) -> Result<Option<DBVector>, Error>
+    where
+        T: Handle<ffi::rocksdb_t> + Read,
+        K: AsRef<[u8]>,
+    {
+        self.get_from_batch_and_db_full(db, Some(cf), key, None)
+    }
+
+    pub fn get_from_batch_and_db_cf_opt<T, K>(
+        &self,
+        db: &T,
+        cf: &ColumnFamily,
+        key: K,
+        readopts: &ReadOptions,
+    ) -> Result<Option<DBVector>, Error>
+    where
+        T: Handle<ffi::rocksdb_t> + Read,
+        K: AsRef<[u8]>,
+    {
+        self.get_from_batch_and_db_full(db, Some(cf), key, Some(readopts))
+    }
+
+    fn get_from_batch_and_db_full<T, K>(
+        &self,
+        db: &T,
+        cf: Option<&ColumnFamily>,
+        key: K,
+        readopts: Option<&ReadOptions>,
+    ) -> Result<Option<DBVector>, Error>
+    where
+        T: Handle<ffi::rocksdb_t> + Read,
+        K: AsRef<[u8]>,
+    {
+        let mut default_readopts = None;
+
+        let ro_handle = ReadOptions::input_or_default(readopts, &mut default_readopts)?;
+
+        let key = key.as_ref();
+        let mut val_len: size_t = 0;
+
+        unsafe {
+            let val = match cf {
+                Some(cf) => ffi_try!(ffi::rocksdb_writebatch_wi_get_from_batch_and_db_cf(
+                    self.inner,
+                    db.handle(),
+                    ro_handle,
+                    cf.handle(),
+                    key.as_ptr() as *const c_char,
+                    key.len() as size_t,
+                    &mut val_len,
+                )),
+                None => ffi_try!(ffi::rocksdb_writebatch_wi_get_from_batch_and_db(
+                    self.inner,
+                    db.handle(),
+                    ro_handle,
+                    key.as_ptr() as *const c_char,
+                    key.len() as size_t,
+                    &mut val_len,
+                )),
+            } as *mut u8;
+
+            Ok(to_dbvector(val, val_len))
+        }
+    }
+
+    /// Create an iterator which merges the updates in the batch on top of
+    /// `base`, typically an iterator over the database the batch will be
+    /// written to.
+    pub fn iterator_with_base<'a>(&'a self, base: DBRawIterator<'a>) -> DBRawIterator<'a> {
+        unsafe {
+            DBRawIterator {
+                inner: ffi::rocksdb_writebatch_wi_create_iterator_with_base(
+                    self.inner,
+                    into_raw(base),
+                ),
+                db: PhantomData,
+            }
+        }
+    }
+
+    /// Like `iterator_with_base`, for a base iterator over the column family
+    /// `cf`.
+    pub fn iterator_with_base_cf<'a>(
+        &'a self,
+        base: DBRawIterator<'a>,
+        cf: &ColumnFamily,
+    ) -> DBRawIterator<'a> {
+        unsafe {
+            DBRawIterator {
+                inner: ffi::rocksdb_writebatch_wi_create_iterator_with_base_cf(
+                    self.inner,
+                    into_raw(base),
+                    cf.handle(),
+                ),
+                db: PhantomData,
+            }
+        }
+    }
+}
+
+/// The base iterator is owned and freed by the iterator created from it.
+fn into_raw(iter: DBRawIterator<'_>) -> *mut ffi::rocksdb_iterator_t {
+    let inner = iter.inner;
+    mem::forget(iter);
+    inner
+}
+
+unsafe fn to_dbvector(val: *mut u8, val_len: size_t) -> Option<DBVector> {
+    if val.is_null() {
+        None
+    } else {
+        Some(DBVector::from_c(val, val_len))
+    }
+}
+
+impl Default for WriteBatchWithIndex {
+    fn default() -> WriteBatchWithIndex {
+        WriteBatchWithIndex::new(0, true)
+    }
+}
+
+impl Drop for WriteBatchWithIndex {
+    fn drop(&mut self) {
+        unsafe { ffi::rocksdb_writebatch_wi_destroy(self.inner) }
+    }
+}
+
+impl Handle<ffi::rocksdb_writebatch_wi_t> for WriteBatchWithIndex {
+    fn handle(&self) -> *mut ffi::rocksdb_writebatch_wi_t {
+        self.inner
+    }
+}
@@ -0,0 +1,116 @@
+// Copyright 2019 Tyler Neely
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+extern crate ckb_rocksdb as rocksdb;
+
+use crate::rocksdb::{
+    backup::{BackupEngine, BackupEngineOptions, BackupSource, RestoreOptions},
+    prelude::*,
+    DBWithTTL, OptimisticTransactionDB, RateLimiter, TemporaryDBPath, TransactionDB,
+};
+
+/// Back up `db` and restore the backup into a new directory.
+fn backup_and_restore<D: BackupSource>(db: &D) -> TemporaryDBPath {
+    let backup_path = TemporaryDBPath::new();
+    let restore_path = TemporaryDBPath::new();
+
+    let backup_opts = BackupEngineOptions::default();
+    let mut backup_engine = BackupEngine::open(&backup_opts, &backup_path).unwrap();
+    backup_engine.create_new_backup_flush(db, true).unwrap();
+
+    let info = backup_engine.get_backup_info();
+    assert_eq!(info.len(), 1);
+    backup_engine.verify_backup(info[0].backup_id).unwrap();
+
+    backup_engine
+        .restore_from_latest_backup(&restore_path, &restore_path, &RestoreOptions::default())
+        .unwrap();
+
+    restore_path
+}
+
+#[test]
+fn test_backup_db() {
+    let path = TemporaryDBPath::new();
+    let db = DB::open_default(&path).unwrap();
+    db.put(b"k1", b"v1").unwrap();
+    let restore_path = backup_and_restore(&db);
+
+    let restored = DB::open_default(&restore_path).unwrap();
+    let value = restored.get(b"k1").unwrap();
+    assert_eq!(value.as_deref(), Some(&b"v1"[..]));
+}
+
+#[test]
+fn test_backup_transaction_db() {
+    let path = TemporaryDBPath::new();
+    let db = TransactionDB::open_default(&path).unwrap();
+    let txn = db.transaction_default();
+    txn.put(b"k1", b"v1").unwrap();
+    txn.commit().unwrap();
+    let restore_path = backup_and_restore(&db);
+
+    let restored = TransactionDB::open_default(&restore_path).unwrap();
+    let value = restored.get(b"k1").unwrap();
+    assert_eq!(value.as_deref(), Some(&b"v1"[..]));
+}
+
+#[test]
+fn test_backup_optimistic_transaction_db() {
+    let path = TemporaryDBPath::new();
+    let db = OptimisticTransactionDB::open_default(&path).unwrap();
+    let txn = db.transaction_default();
+    txn.put(b"k1", b"v1").unwrap();
+    txn.commit().unwrap();
+    let restore_path = backup_and_restore(&db);
+
+    let restored = OptimisticTransactionDB::open_default(&restore_path).unwrap();
+    let value = restored.get(b"k1").unwrap();
+    assert_eq!(value.as_deref(), Some(&b"v1"[..]));
+}
+
+#[test]
+fn test_backup_db_with_ttl() {
+    let path = TemporaryDBPath::new();
+    let db = DBWithTTL::open_default(&path).unwrap();
+    db.put(b"k1", b"v1").unwrap();
+    let restore_path = backup_and_restore(&db);
+
+    let restored = DBWithTTL::open_default(&restore_path).unwrap();
+    let value = restored.get(b"k1").unwrap();
+    assert_eq!(value.as_deref(), Some(&b"v1"[..]));
+}
+
+#[test]
+fn test_backup_with_rate_limiter() {
+    let path = TemporaryDBPath::new();
+    let db = DB::open_default(&path).unwrap();
+    db.put(b"k1", b"v1").unwrap();
+
+    let limiter = RateLimiter::new(64 * 1024 * 1024, 100 * 1000, 10).unwrap();
+    let backup_path = TemporaryDBPath::new();
+    let mut backup_opts = BackupEngineOptions::default();
+    backup_opts.set_backup_rate_limiter(&limiter);
+    backup_opts.set_restore_rate_limiter(&limiter);
+    let mut backup_engine = BackupEngine::open(&backup_opts, &backup_path).unwrap();
+    backup_engine.create_new_backup_flush(&db, true).unwrap();
+    let through = limiter.total_bytes_through();
+    assert!(through > 0);
+
+    let restore_path = TemporaryDBPath::new();
+    backup_engine
+        .restore_from_latest_backup(&restore_path, &restore_path, &RestoreOptions::default())
+        .unwrap();
+    assert!(limiter.total_bytes_through() > through);
+}
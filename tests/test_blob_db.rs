@@ -0,0 +1,133 @@
+// Copyright 2019 Tyler Neely
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+extern crate ckb_rocksdb as rocksdb;
+
+use crate::rocksdb::{prelude::*, properties, Cache, TemporaryDBPath, Ticker};
+
+const NUM_KEYS: usize = 100;
+
+fn key(i: usize) -> Vec<u8> {
+    format!("key{:03}", i).into_bytes()
+}
+
+fn value(i: usize, version: u8) -> Vec<u8> {
+    let mut value = vec![version; 1024];
+    value[..8].copy_from_slice(&(i as u64).to_be_bytes());
+    value
+}
+
+fn blob_options() -> Options {
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    opts.set_disable_auto_compactions(true);
+    opts.set_enable_blob_files(true);
+    opts.set_min_blob_size(512);
+    opts.set_blob_file_size(1024 * 1024);
+    opts.enable_statistics();
+    opts
+}
+
+fn int_property(db: &DB, name: &str) -> u64 {
+    db.property_int_value(name).unwrap().unwrap()
+}
+
+#[test]
+fn blob_files_hold_large_values() {
+    let path = TemporaryDBPath::new();
+    let mut opts = blob_options();
+    opts.set_blob_cache(&Cache::new_lru_cache(1024 * 1024).unwrap());
+    let db = DB::open(&opts, &path).unwrap();
+
+    for i in 0..NUM_KEYS {
+        db.put(key(i), value(i, 1)).unwrap();
+    }
+    db.put(b"small", b"inline").unwrap();
+    db.flush().unwrap();
+
+    assert_eq!(int_property(&db, properties::NUM_BLOB_FILES), 1);
+    assert!(int_property(&db, properties::LIVE_BLOB_FILE_SIZE) > (NUM_KEYS * 1024) as u64);
+    assert_eq!(
+        int_property(&db, properties::LIVE_BLOB_FILE_GARBAGE_SIZE),
+        0
+    );
+    assert_eq!(
+        int_property(&db, properties::BLOB_CACHE_CAPACITY),
+        1024 * 1024
+    );
+    assert!(db
+        .property_value(properties::BLOB_STATS)
+        .unwrap()
+        .unwrap()
+        .contains("Number of blob files: 1"));
+    assert!(opts.get_ticker_count(Ticker::BlobDbBlobFileBytesWritten) > 0);
+
+    for i in 0..NUM_KEYS {
+        assert_eq!(db.get(key(i)).unwrap().unwrap().to_vec(), value(i, 1));
+    }
+    assert_eq!(&*db.get(b"small").unwrap().unwrap(), b"inline");
+    assert!(opts.get_ticker_count(Ticker::BlobDbCacheAdd) > 0);
+    assert!(int_property(&db, properties::BLOB_CACHE_USAGE) > 0);
+}
+
+#[test]
+fn reads_stay_correct_across_blob_gc() {
+    let path = TemporaryDBPath::new();
+    let mut opts = blob_options();
+    opts.set_enable_blob_gc(true);
+    opts.set_blob_gc_age_cutoff(1.0);
+    opts.set_blob_gc_force_threshold(1.0);
+    let db = DB::open(&opts, &path).unwrap();
+
+    for i in 0..NUM_KEYS {
+        db.put(key(i), value(i, 1)).unwrap();
+    }
+    db.flush().unwrap();
+    // Overwrite half of the values, and delete a few more.
+    for i in 0..NUM_KEYS / 2 {
+        db.put(key(i), value(i, 2)).unwrap();
+    }
+    for i in NUM_KEYS / 2..NUM_KEYS / 2 + 10 {
+        db.delete(key(i)).unwrap();
+    }
+    db.flush().unwrap();
+    assert_eq!(int_property(&db, properties::NUM_BLOB_FILES), 2);
+
+    let check = |db: &DB| {
+        for i in 0..NUM_KEYS {
+            let expected = match i {
+                i if i < NUM_KEYS / 2 => Some(value(i, 2)),
+                i if i < NUM_KEYS / 2 + 10 => None,
+                i => Some(value(i, 1)),
+            };
+            assert_eq!(db.get(key(i)).unwrap().map(|v| v.to_vec()), expected);
+        }
+    };
+    check(&db);
+
+    // All the blob files are old enough to be collected, so the compaction
+    // moves the live values to a new blob file and drops the garbage.
+    db.compact_range(None::<&[u8]>, None::<&[u8]>);
+    check(&db);
+    assert_eq!(int_property(&db, properties::NUM_BLOB_FILES), 1);
+    assert_eq!(
+        int_property(&db, properties::LIVE_BLOB_FILE_GARBAGE_SIZE),
+        0
+    );
+    assert!(opts.get_ticker_count(Ticker::BlobDbGcNumKeysRelocated) > 0);
+
+    drop(db);
+    let db = DB::open(&opts, &path).unwrap();
+    check(&db);
+}
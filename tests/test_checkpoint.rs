@@ -14,7 +14,11 @@
 //
 extern crate ckb_rocksdb as rocksdb;
 
-use crate::rocksdb::{checkpoint::Checkpoint, prelude::*, TemporaryDBPath};
+use crate::rocksdb::{
+    checkpoint::{Checkpoint, ExportImportFilesMetaData},
+    prelude::*,
+    TemporaryDBPath, TransactionDB, TransactionOptions,
+};
 
 #[test]
 pub fn test_single_checkpoint() {
@@ -102,3 +106,101 @@ fn test_checkpoint_outlive_db() {
     let t = trybuild::TestCases::new();
     t.compile_fail("tests/fail/checkpoint_outlive_db.rs");
 }
+
+fn export_cf(tmp_path: &TemporaryDBPath) -> ExportImportFilesMetaData {
+    let db_path = TemporaryDBPath::new();
+
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    let mut db = DB::open(&opts, &db_path).unwrap();
+    db.create_cf("tenant", &Options::default()).unwrap();
+
+    let cf = db.cf_handle("tenant").unwrap();
+    db.put_cf(cf, b"k1", b"v1").unwrap();
+    db.put_cf(cf, b"k2", b"v2").unwrap();
+    db.put(b"other", b"value").unwrap();
+
+    let cp = Checkpoint::new(&db).unwrap();
+    let metadata = cp
+        .export_column_family(cf, tmp_path.join("export"))
+        .unwrap();
+
+    assert_eq!(metadata.db_comparator_name, "leveldb.BytewiseComparator");
+    assert_eq!(metadata.files.len(), 1);
+    let file = &metadata.files[0];
+    assert_eq!(file.directory, tmp_path.join("export"));
+    assert_eq!(file.smallest_key, b"k1");
+    assert_eq!(file.largest_key, b"k2");
+    assert!(file.size > 0);
+    assert!(file.smallest_seqno <= file.largest_seqno);
+
+    metadata
+}
+
+#[test]
+pub fn test_export_import_column_family() {
+    let tmp_path = TemporaryDBPath::new();
+    let metadata = export_cf(&tmp_path);
+
+    let db_path = TemporaryDBPath::new();
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    let mut db = DB::open(&opts, &db_path).unwrap();
+    db.create_cf_with_import("imported", &Options::default(), &metadata, false)
+        .unwrap();
+
+    let cf = db.cf_handle("imported").unwrap();
+    assert_eq!(*db.get_cf(cf, b"k1").unwrap().unwrap(), *b"v1");
+    assert_eq!(*db.get_cf(cf, b"k2").unwrap().unwrap(), *b"v2");
+    assert!(db.get_cf(cf, b"other").unwrap().is_none());
+    db.put_cf(cf, b"k3", b"v3").unwrap();
+    assert_eq!(*db.get_cf(cf, b"k3").unwrap().unwrap(), *b"v3");
+
+    // The export was copied, so it can be imported a second time.
+    db.create_cf_with_import("imported_again", &Options::default(), &metadata, true)
+        .unwrap();
+    let cf = db.cf_handle("imported_again").unwrap();
+    assert_eq!(*db.get_cf(cf, b"k1").unwrap().unwrap(), *b"v1");
+    assert!(!metadata.files[0]
+        .directory
+        .join(metadata.files[0].name.trim_start_matches('/'))
+        .exists());
+}
+
+#[test]
+pub fn test_import_column_family_into_transaction_db() {
+    let tmp_path = TemporaryDBPath::new();
+    let metadata = export_cf(&tmp_path);
+
+    let db_path = TemporaryDBPath::new();
+    let mut db = TransactionDB::open_default(&db_path).unwrap();
+    db.create_cf_with_import("imported", &Options::default(), &metadata, false)
+        .unwrap();
+
+    let cf = db.cf_handle("imported").unwrap();
+    assert_eq!(*db.get_cf(cf, b"k1").unwrap().unwrap(), *b"v1");
+
+    let write_options = WriteOptions::default();
+    let transaction_options = TransactionOptions::new();
+    let txn = db.transaction(&write_options, &transaction_options);
+    assert_eq!(*txn.get_cf(cf, b"k2").unwrap().unwrap(), *b"v2");
+    txn.put_cf(cf, b"k3", b"v3").unwrap();
+    txn.commit().unwrap();
+    assert_eq!(*db.get_cf(cf, b"k3").unwrap().unwrap(), *b"v3");
+}
+
+#[test]
+pub fn test_import_column_family_with_mismatched_comparator() {
+    let tmp_path = TemporaryDBPath::new();
+    let mut metadata = export_cf(&tmp_path);
+    metadata.db_comparator_name = "unknown".to_owned();
+
+    let db_path = TemporaryDBPath::new();
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    let mut db = DB::open(&opts, &db_path).unwrap();
+    assert!(db
+        .create_cf_with_import("imported", &Options::default(), &metadata, false)
+        .is_err());
+    assert!(db.cf_handle("imported").is_none());
+}
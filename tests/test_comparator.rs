@@ -0,0 +1,108 @@
+// Copyright 2019 Tyler Neely
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+extern crate ckb_rocksdb as rocksdb;
+
+use crate::rocksdb::{prelude::*, IteratorMode, TemporaryDBPath};
+use std::cmp::Ordering;
+
+type CompareFn = dyn Fn(&[u8], &[u8]) -> Ordering + Send + Sync;
+
+fn keys(db: &DB) -> Vec<Vec<u8>> {
+    db.iterator(IteratorMode::Start)
+        .map(|(k, _)| k.to_vec())
+        .collect()
+}
+
+#[test]
+fn closure_comparator_test() {
+    let path = TemporaryDBPath::new();
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    // Orders keys by their suffix after a header whose length is only known
+    // at runtime.
+    let header_len = 2;
+    opts.set_comparator("suffix", move |a: &[u8], b: &[u8]| {
+        a[header_len..]
+            .cmp(&b[header_len..])
+            .then_with(|| a[..header_len].cmp(&b[..header_len]))
+    });
+    let db = DB::open(&opts, &path).unwrap();
+    db.put(b"xx3", b"").unwrap();
+    db.put(b"aa2", b"").unwrap();
+    db.put(b"zz1", b"").unwrap();
+    assert_eq!(
+        keys(&db),
+        vec![b"zz1".to_vec(), b"aa2".to_vec(), b"xx3".to_vec()]
+    );
+}
+
+#[test]
+fn boxed_comparator_test() {
+    let path = TemporaryDBPath::new();
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    let compare: Box<CompareFn> =
+        Box::new(|a: &[u8], b: &[u8]| a.len().cmp(&b.len()).then_with(|| a.cmp(b)));
+    opts.set_comparator("length", compare);
+    let db = DB::open(&opts, &path).unwrap();
+    db.put(b"ccc", b"").unwrap();
+    db.put(b"b", b"").unwrap();
+    db.put(b"aa", b"").unwrap();
+    assert_eq!(
+        keys(&db),
+        vec![b"b".to_vec(), b"aa".to_vec(), b"ccc".to_vec()]
+    );
+}
+
+#[test]
+fn reverse_bytewise_comparator_test() {
+    let path = TemporaryDBPath::new();
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    opts.set_reverse_bytewise_comparator();
+    {
+        let db = DB::open(&opts, &path).unwrap();
+        db.put(b"a", b"").unwrap();
+        db.put(b"c", b"").unwrap();
+        db.put(b"b", b"").unwrap();
+        assert_eq!(keys(&db), vec![b"c".to_vec(), b"b".to_vec(), b"a".to_vec()]);
+    }
+
+    // The comparator is recorded in the DB and must match on reopen.
+    let mut opts = Options::default();
+    opts.set_comparator("bytewise", |a: &[u8], b: &[u8]| a.cmp(b));
+    assert!(DB::open(&opts, &path).is_err());
+}
+
+#[test]
+fn big_endian_u64_comparator_test() {
+    let path = TemporaryDBPath::new();
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    opts.set_big_endian_u64_comparator();
+    let db = DB::open(&opts, &path).unwrap();
+    for n in &[256u64, 1, u64::MAX, 0] {
+        db.put(n.to_be_bytes(), b"").unwrap();
+    }
+    let numbers: Vec<u64> = keys(&db)
+        .into_iter()
+        .map(|k| {
+            let mut buf = [0; 8];
+            buf.copy_from_slice(&k);
+            u64::from_be_bytes(buf)
+        })
+        .collect();
+    assert_eq!(numbers, vec![0, 1, 256, u64::MAX]);
+}
@@ -14,7 +14,16 @@
 
 extern crate ckb_rocksdb as rocksdb;
 
-use crate::rocksdb::{prelude::*, CompactionDecision, TemporaryDBPath};
+use crate::rocksdb::{
+    compaction_filter::{CompactionFilter, ValueType},
+    compaction_filter_factory::{
+        CompactionFilterContext, CompactionFilterFactory, TableFileCreationReason,
+    },
+    prelude::*,
+    ColumnFamilyDescriptor, CompactionDecision, MergeOperands, TemporaryDBPath,
+};
+use std::ffi::{CStr, CString};
+use std::sync::{Arc, Mutex};
 
 #[cfg(test)]
 #[allow(unused_variables)]
@@ -46,3 +55,206 @@ fn compaction_filter_test() {
         assert_eq!(&*db.get(b"%k").unwrap().unwrap(), b"secret");
     }
 }
+
+/// Prefixes every value with the number of values seen so far, and drops
+/// the keys in ["skip", "skip_end").
+struct RewriteFilter {
+    name: CString,
+    seen: usize,
+}
+
+impl CompactionFilter for RewriteFilter {
+    fn filter(&mut self, _level: u32, key: &[u8], value: &[u8]) -> CompactionDecision {
+        if key == b"skip" {
+            return CompactionDecision::RemoveAndSkipUntil(b"skip_end".to_vec());
+        }
+        self.seen += 1;
+        let mut new_value = format!("{}:", self.seen).into_bytes();
+        new_value.extend_from_slice(value);
+        CompactionDecision::ChangeOwned(new_value)
+    }
+
+    fn name(&self) -> &CStr {
+        &self.name
+    }
+}
+
+struct RewriteFilterFactory(CString);
+
+impl CompactionFilterFactory for RewriteFilterFactory {
+    type Filter = RewriteFilter;
+
+    fn create(&mut self, _context: CompactionFilterContext) -> Self::Filter {
+        RewriteFilter {
+            name: CString::new("RewriteFilter").unwrap(),
+            seen: 0,
+        }
+    }
+
+    fn name(&self) -> &CStr {
+        &self.0
+    }
+}
+
+#[test]
+fn compaction_filter_factory_owned_values_test() {
+    let path = TemporaryDBPath::new();
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    opts.set_compaction_filter_factory(RewriteFilterFactory(
+        CString::new("RewriteFilterFactory").unwrap(),
+    ));
+    {
+        let db = DB::open(&opts, &path).unwrap();
+        db.put(b"a", b"1").unwrap();
+        db.put(b"b", b"2").unwrap();
+        db.put(b"skip", b"3").unwrap();
+        db.put(b"skip_a", b"4").unwrap();
+        db.put(b"skip_end", b"5").unwrap();
+        db.compact_range(None::<&[u8]>, None::<&[u8]>);
+        assert_eq!(&*db.get(b"a").unwrap().unwrap(), b"1:1");
+        assert_eq!(&*db.get(b"b").unwrap().unwrap(), b"2:2");
+        assert!(db.get(b"skip").unwrap().is_none());
+        assert!(db.get(b"skip_a").unwrap().is_none());
+        assert_eq!(&*db.get(b"skip_end").unwrap().unwrap(), b"3:5");
+    }
+}
+
+/// Records the context of every compaction run and drops the merge operands
+/// equal to "drop".
+struct RecordingFilterFactory {
+    name: CString,
+    contexts: Arc<Mutex<Vec<(u32, String, TableFileCreationReason)>>>,
+}
+
+struct DropOperandFilter(CString);
+
+impl CompactionFilter for DropOperandFilter {
+    fn filter(&mut self, _level: u32, _key: &[u8], _value: &[u8]) -> CompactionDecision {
+        CompactionDecision::Keep
+    }
+
+    fn filter_entry(
+        &mut self,
+        _level: u32,
+        _key: &[u8],
+        value_type: ValueType,
+        value: &[u8],
+    ) -> CompactionDecision {
+        if value_type == ValueType::MergeOperand && value == b"drop" {
+            CompactionDecision::Remove
+        } else {
+            CompactionDecision::Keep
+        }
+    }
+
+    fn name(&self) -> &CStr {
+        &self.0
+    }
+}
+
+impl CompactionFilterFactory for RecordingFilterFactory {
+    type Filter = DropOperandFilter;
+
+    fn create(&mut self, context: CompactionFilterContext) -> Self::Filter {
+        self.contexts.lock().unwrap().push((
+            context.column_family_id,
+            context.column_family_name,
+            context.reason,
+        ));
+        DropOperandFilter(CString::new("DropOperandFilter").unwrap())
+    }
+
+    fn name(&self) -> &CStr {
+        &self.name
+    }
+}
+
+fn concat_merge(
+    _new_key: &[u8],
+    existing_val: Option<&[u8]>,
+    operands: &mut MergeOperands,
+) -> Option<Vec<u8>> {
+    let mut result = existing_val.map(|v| v.to_vec()).unwrap_or_default();
+    for op in operands {
+        result.extend_from_slice(op);
+    }
+    Some(result)
+}
+
+/// Keeps the operands apart when flushing, so that the filter sees each of
+/// them during compaction.
+fn no_partial_merge(
+    _new_key: &[u8],
+    _existing_val: Option<&[u8]>,
+    _operands: &mut MergeOperands,
+) -> Option<Vec<u8>> {
+    None
+}
+
+#[test]
+fn compaction_filter_factory_context_test() {
+    let path = TemporaryDBPath::new();
+    let contexts = Arc::new(Mutex::new(Vec::new()));
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    opts.create_missing_column_families(true);
+    opts.set_merge_operator("concat", concat_merge, no_partial_merge);
+    opts.set_compaction_filter_factory(RecordingFilterFactory {
+        name: CString::new("RecordingFilterFactory").unwrap(),
+        contexts: Arc::clone(&contexts),
+    });
+    {
+        let cfs = vec![
+            ColumnFamilyDescriptor::new("default", opts.clone()),
+            ColumnFamilyDescriptor::new("cf1", opts.clone()),
+        ];
+        let mut db = DB::open_cf_descriptors(&opts, &path, cfs).unwrap();
+        db.create_cf("cf2", &opts).unwrap();
+
+        for name in &["default", "cf1", "cf2"] {
+            let cf = db.cf_handle(name).unwrap();
+            db.merge_cf(cf, b"k", b"a").unwrap();
+            db.merge_cf(cf, b"k", b"drop").unwrap();
+            db.merge_cf(cf, b"k", b"b").unwrap();
+            db.compact_range_cf(cf, None, None);
+            assert_eq!(&*db.get_cf(cf, b"k").unwrap().unwrap(), b"ab");
+        }
+    }
+
+    let contexts = contexts.lock().unwrap();
+    assert_eq!(
+        *contexts,
+        vec![
+            (0, "default".to_owned(), TableFileCreationReason::Compaction),
+            (1, "cf1".to_owned(), TableFileCreationReason::Compaction),
+            (2, "cf2".to_owned(), TableFileCreationReason::Compaction),
+        ]
+    );
+}
+
+#[test]
+fn compaction_filter_factory_context_without_descriptors_test() {
+    let path = TemporaryDBPath::new();
+    let contexts = Arc::new(Mutex::new(Vec::new()));
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    opts.set_merge_operator("concat", concat_merge, no_partial_merge);
+    opts.set_compaction_filter_factory(RecordingFilterFactory {
+        name: CString::new("RecordingFilterFactory").unwrap(),
+        contexts: Arc::clone(&contexts),
+    });
+    {
+        let db = DB::open(&opts, &path).unwrap();
+        db.merge(b"k", b"a").unwrap();
+        db.merge(b"k", b"drop").unwrap();
+        db.compact_range(None::<&[u8]>, None::<&[u8]>);
+        assert_eq!(&*db.get(b"k").unwrap().unwrap(), b"a");
+    }
+
+    let contexts = contexts.lock().unwrap();
+    assert_eq!(
+        *contexts,
+        vec![(0, "default".to_owned(), TableFileCreationReason::Compaction)]
+    );
+}
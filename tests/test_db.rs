@@ -14,8 +14,12 @@
 
 extern crate ckb_rocksdb as rocksdb;
 use libc::size_t;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 
-use crate::rocksdb::{prelude::*, IteratorMode, TemporaryDBPath, WriteBatch};
+use crate::rocksdb::{
+    prelude::*, CompactOptions, ErrorKind, IteratorMode, TemporaryDBPath, WriteBatch,
+};
 
 #[test]
 fn test_db_vector() {
@@ -157,6 +161,110 @@ fn snapshot_test() {
     }
 }
 
+#[test]
+fn multi_get_test() {
+    let path = TemporaryDBPath::new();
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.create_missing_column_families(true);
+        let db = DB::open_cf(&opts, &path, ["cf1", "cf2"]).unwrap();
+        let cf1 = db.cf_handle("cf1").unwrap();
+        let cf2 = db.cf_handle("cf2").unwrap();
+
+        db.put(b"k1", b"v1").unwrap();
+        db.put(b"k2", b"v2").unwrap();
+        db.put_cf(cf1, b"k1", b"cf1_v1").unwrap();
+        db.put_cf(cf2, b"k2", b"cf2_v2").unwrap();
+
+        let values: Vec<_> = db
+            .multi_get([b"k1", b"k3", b"k2"])
+            .into_iter()
+            .map(|v| v.unwrap().map(|v| v.to_vec()))
+            .collect();
+        assert_eq!(
+            values,
+            vec![Some(b"v1".to_vec()), None, Some(b"v2".to_vec())]
+        );
+
+        let values: Vec<_> = db
+            .multi_get_cf(vec![(cf1, b"k1"), (cf2, b"k1"), (cf2, b"k2")])
+            .into_iter()
+            .map(|v| v.unwrap().map(|v| v.to_vec()))
+            .collect();
+        assert_eq!(
+            values,
+            vec![Some(b"cf1_v1".to_vec()), None, Some(b"cf2_v2".to_vec())]
+        );
+
+        // keys of the default column family can be mixed with other ones
+        let values: Vec<_> = db
+            .multi_get_cf_full(vec![(None, b"k2"), (Some(cf1), b"k1"), (None, b"k1")], None)
+            .into_iter()
+            .map(|v| v.unwrap().map(|v| v.to_vec()))
+            .collect();
+        assert_eq!(
+            values,
+            vec![
+                Some(b"v2".to_vec()),
+                Some(b"cf1_v1".to_vec()),
+                Some(b"v1".to_vec())
+            ]
+        );
+
+        let snap = db.snapshot();
+        db.put_cf(cf1, b"k2", b"cf1_v2").unwrap();
+
+        let values = db.batched_multi_get_cf(cf1, [b"k1", b"k2", b"k3"], true);
+        assert_eq!(values.len(), 3);
+        assert_eq!(values[0].as_ref().unwrap().as_deref(), Some(&b"cf1_v1"[..]));
+        assert_eq!(values[1].as_ref().unwrap().as_deref(), Some(&b"cf1_v2"[..]));
+        assert!(values[2].as_ref().unwrap().is_none());
+
+        let values = snap.batched_multi_get_cf(cf1, [b"k1", b"k2"], true);
+        assert_eq!(values[0].as_ref().unwrap().as_deref(), Some(&b"cf1_v1"[..]));
+        assert!(values[1].as_ref().unwrap().is_none());
+
+        let values = snap.multi_get_cf(vec![(cf1, b"k1"), (cf1, b"k2")]);
+        assert!(values[0].as_ref().unwrap().is_some());
+        assert!(values[1].as_ref().unwrap().is_none());
+    }
+}
+
+/// Looks keys up one at a time, relying on the default implementations of
+/// the batched lookups.
+struct PerKeyGet<'a>(&'a DB);
+
+impl GetCF<ReadOptions> for PerKeyGet<'_> {
+    fn get_cf_full<K: AsRef<[u8]>>(
+        &self,
+        cf: Option<&ColumnFamily>,
+        key: K,
+        readopts: Option<&ReadOptions>,
+    ) -> Result<Option<DBVector>, Error> {
+        self.0.get_cf_full(cf, key, readopts)
+    }
+}
+
+#[test]
+fn batched_multi_get_cf_default_impl() {
+    let path = TemporaryDBPath::new();
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.create_missing_column_families(true);
+        let db = DB::open_cf(&opts, &path, ["cf1"]).unwrap();
+        let cf1 = db.cf_handle("cf1").unwrap();
+        db.put_cf(cf1, b"k1", b"v1").unwrap();
+
+        let per_key = PerKeyGet(&db);
+        let values = per_key.batched_multi_get_cf(cf1, [b"k1", b"k2"], true);
+        assert_eq!(values.len(), 2);
+        assert_eq!(values[0].as_ref().unwrap().as_deref(), Some(&b"v1"[..]));
+        assert!(values[1].as_ref().unwrap().is_none());
+    }
+}
+
 #[test]
 fn set_option_test() {
     let path = TemporaryDBPath::new();
@@ -250,3 +358,77 @@ fn test_open_utf8_path() {
         assert!(db.get(b"k1").unwrap().is_none());
     }
 }
+
+#[test]
+fn compact_range_opt_test() {
+    let path = TemporaryDBPath::new();
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    opts.create_missing_column_families(true);
+    let db = DB::open_cf(&opts, &path, ["cf1"]).unwrap();
+    let cf1 = db.cf_handle("cf1").unwrap();
+
+    for i in 0..10u8 {
+        db.put([i], b"value").unwrap();
+        db.put_cf(cf1, [i], b"value").unwrap();
+    }
+    db.flush().unwrap();
+
+    let mut compact_opts = CompactOptions::default();
+    compact_opts.set_exclusive_manual_compaction(true);
+    compact_opts.set_change_level(true);
+    compact_opts.set_target_level(1);
+
+    db.compact_range_opt(None::<&[u8]>, None::<&[u8]>, &compact_opts)
+        .unwrap();
+    assert_eq!(
+        db.property_int_value("rocksdb.num-files-at-level0")
+            .unwrap(),
+        Some(0)
+    );
+    assert_eq!(
+        db.property_int_value("rocksdb.num-files-at-level1")
+            .unwrap(),
+        Some(1)
+    );
+
+    db.compact_range_cf_opt(cf1, Some(&[0u8][..]), None, &compact_opts)
+        .unwrap();
+    assert_eq!(
+        db.property_int_value_cf(cf1, "rocksdb.num-files-at-level1")
+            .unwrap(),
+        Some(1)
+    );
+}
+
+#[test]
+fn compact_range_opt_canceled_test() {
+    let path = TemporaryDBPath::new();
+    let db = DB::open_default(&path).unwrap();
+    db.put(b"k1", b"v1").unwrap();
+    db.flush().unwrap();
+
+    let canceled = Arc::new(AtomicBool::new(false));
+    let mut compact_opts = CompactOptions::default();
+    compact_opts.set_canceled(Arc::clone(&canceled));
+
+    canceled.store(true, Ordering::Release);
+    let err = db
+        .compact_range_opt(None::<&[u8]>, None::<&[u8]>, &compact_opts)
+        .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::Incomplete);
+    assert_eq!(
+        db.property_int_value("rocksdb.num-files-at-level0")
+            .unwrap(),
+        Some(1)
+    );
+
+    canceled.store(false, Ordering::Release);
+    db.compact_range_opt(None::<&[u8]>, None::<&[u8]>, &compact_opts)
+        .unwrap();
+    assert_eq!(
+        db.property_int_value("rocksdb.num-files-at-level0")
+            .unwrap(),
+        Some(0)
+    );
+}
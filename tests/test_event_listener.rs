@@ -0,0 +1,205 @@
+// Copyright 2019 Tyler Neely
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Listeners are called from background threads, so the tests only look at
+// the recorded events once the database has been closed.
+
+extern crate ckb_rocksdb as rocksdb;
+
+use std::sync::{Arc, Mutex};
+
+use crate::rocksdb::{
+    event_listener::{
+        BackgroundErrorReason, CompactionJobInfo, CompactionReason, EventListener, FlushJobInfo,
+        FlushReason, TableFileDeletionInfo, WriteStallCondition, WriteStallInfo,
+    },
+    prelude::*,
+    Env, SstFileManager, TemporaryDBPath,
+};
+
+#[derive(Default)]
+struct Events {
+    flush_begin: Vec<FlushJobInfo>,
+    flush_completed: Vec<FlushJobInfo>,
+    compaction_begin: Vec<CompactionJobInfo>,
+    compaction_completed: Vec<CompactionJobInfo>,
+    table_file_deleted: Vec<TableFileDeletionInfo>,
+    stall_conditions_changed: Vec<WriteStallInfo>,
+    background_errors: Vec<(BackgroundErrorReason, String)>,
+}
+
+struct Recorder(Arc<Mutex<Events>>);
+
+impl EventListener for Recorder {
+    fn on_flush_begin(&self, info: &FlushJobInfo) {
+        self.0.lock().unwrap().flush_begin.push(info.clone());
+    }
+
+    fn on_flush_completed(&self, info: &FlushJobInfo) {
+        self.0.lock().unwrap().flush_completed.push(info.clone());
+    }
+
+    fn on_compaction_begin(&self, info: &CompactionJobInfo) {
+        self.0.lock().unwrap().compaction_begin.push(info.clone());
+    }
+
+    fn on_compaction_completed(&self, info: &CompactionJobInfo) {
+        self.0
+            .lock()
+            .unwrap()
+            .compaction_completed
+            .push(info.clone());
+    }
+
+    fn on_stall_conditions_changed(&self, info: &WriteStallInfo) {
+        self.0
+            .lock()
+            .unwrap()
+            .stall_conditions_changed
+            .push(info.clone());
+    }
+
+    fn on_background_error(&self, reason: BackgroundErrorReason, error: &Error) {
+        self.0
+            .lock()
+            .unwrap()
+            .background_errors
+            .push((reason, error.to_string()));
+    }
+
+    fn on_table_file_deleted(&self, info: &TableFileDeletionInfo) {
+        self.0.lock().unwrap().table_file_deleted.push(info.clone());
+    }
+}
+
+fn open_with_recorder(opts: &mut Options, path: &TemporaryDBPath) -> (DB, Arc<Mutex<Events>>) {
+    let events = Arc::new(Mutex::new(Events::default()));
+    opts.create_if_missing(true);
+    opts.add_event_listener(Recorder(events.clone()));
+    (DB::open(opts, path).unwrap(), events)
+}
+
+#[test]
+fn event_listener_flush() {
+    let path = TemporaryDBPath::new();
+    let mut opts = Options::default();
+    let (db, events) = open_with_recorder(&mut opts, &path);
+
+    db.put(b"k1", b"v1").unwrap();
+    db.put(b"k2", b"v2").unwrap();
+    db.flush().unwrap();
+    drop(db);
+
+    let events = events.lock().unwrap();
+    assert_eq!(events.flush_begin.len(), 1);
+    assert_eq!(events.flush_completed.len(), 1);
+
+    let info = &events.flush_completed[0];
+    assert_eq!(info.cf_name, "default");
+    assert_eq!(info.reason, FlushReason::ManualFlush);
+    assert_eq!(info.num_entries, 2);
+    assert!(info.data_size > 0);
+    assert!(info.smallest_seqno <= info.largest_seqno);
+    assert_eq!(info.file_path.extension(), Some("sst".as_ref()));
+}
+
+#[test]
+fn event_listener_compaction() {
+    let path = TemporaryDBPath::new();
+    let mut opts = Options::default();
+    opts.set_disable_auto_compactions(true);
+    let (db, events) = open_with_recorder(&mut opts, &path);
+
+    db.put(b"k1", b"v1").unwrap();
+    db.flush().unwrap();
+    db.put(b"k1", b"v2").unwrap();
+    db.put(b"k2", b"v2").unwrap();
+    db.flush().unwrap();
+    db.compact_range(None::<&[u8]>, None::<&[u8]>);
+    drop(db);
+
+    let events = events.lock().unwrap();
+    assert_eq!(events.compaction_begin.len(), 1);
+    assert_eq!(events.compaction_completed.len(), 1);
+
+    let info = &events.compaction_completed[0];
+    assert_eq!(info.cf_name, "default");
+    assert_eq!(info.status, Ok(()));
+    assert_eq!(info.reason, CompactionReason::ManualCompaction);
+    assert_eq!(info.base_input_level, 0);
+    assert_eq!(info.input_files.len(), 2);
+    assert_eq!(info.output_files.len(), 1);
+    assert_eq!(info.input_records, 3);
+    assert_eq!(info.output_records, 2);
+
+    // The compaction inputs are obsolete afterwards.
+    for input in &info.input_files {
+        assert!(events
+            .table_file_deleted
+            .iter()
+            .any(|deleted| &deleted.file_path == input && deleted.status.is_ok()));
+    }
+}
+
+#[test]
+fn event_listener_stall_conditions() {
+    let path = TemporaryDBPath::new();
+    let mut opts = Options::default();
+    // Writes are delayed as soon as two table files wait in L0, until the
+    // compaction they trigger has run.
+    opts.set_level_zero_file_num_compaction_trigger(2);
+    opts.set_level_zero_slowdown_writes_trigger(2);
+    opts.set_level_zero_stop_writes_trigger(10);
+    let (db, events) = open_with_recorder(&mut opts, &path);
+
+    db.put(b"k1", b"v1").unwrap();
+    db.flush().unwrap();
+    db.put(b"k2", b"v2").unwrap();
+    db.flush().unwrap();
+    drop(db);
+
+    let events = events.lock().unwrap();
+    let info = events
+        .stall_conditions_changed
+        .iter()
+        .find(|info| info.current == WriteStallCondition::Delayed)
+        .unwrap();
+    assert_eq!(info.cf_name, "default");
+    assert_eq!(info.previous, WriteStallCondition::Normal);
+}
+
+#[test]
+fn event_listener_background_error() {
+    let path = TemporaryDBPath::new();
+    let env = Env::default().unwrap();
+    let manager = SstFileManager::new(&env).unwrap();
+    let mut opts = Options::default();
+    opts.set_env(&env);
+    opts.set_sst_file_manager(&manager);
+    let (db, events) = open_with_recorder(&mut opts, &path);
+
+    // Flushes fail once the space limit is reached.
+    manager.set_max_allowed_space_usage(1);
+    db.put(b"k1", b"v1").unwrap();
+    let _ = db.flush();
+    drop(db);
+
+    let events = events.lock().unwrap();
+    let (reason, message) = &events.background_errors[0];
+    assert!(matches!(
+        reason,
+        BackgroundErrorReason::Flush | BackgroundErrorReason::FlushNoWal
+    ));
+    assert!(message.contains("Max allowed space was reached"));
+}
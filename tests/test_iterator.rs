@@ -14,7 +14,11 @@
 //
 extern crate ckb_rocksdb as rocksdb;
 
-use crate::rocksdb::{prelude::*, Direction, IteratorMode, MemtableFactory, TemporaryDBPath};
+use crate::rocksdb::{
+    prelude::*, Direction, ErrorKind, IteratorMode, MemtableFactory, TemporaryDBPath,
+};
+use std::fs::{self, OpenOptions};
+use std::io::{Seek, SeekFrom, Write};
 
 fn cba(input: &[u8]) -> Box<[u8]> {
     input.to_vec().into_boxed_slice()
@@ -296,6 +300,68 @@ fn test_full_iterator() {
     }
 }
 
+#[test]
+fn test_iterator_status() {
+    let n = TemporaryDBPath::new();
+    {
+        let db = DB::open_default(&n).unwrap();
+        db.put(b"k1", b"v1").unwrap();
+        db.put(b"k2", b"v2").unwrap();
+
+        let mut iter = db.raw_iterator();
+        iter.seek_to_first();
+        while iter.valid() {
+            iter.next();
+        }
+        assert!(iter.status().is_ok());
+
+        let items: Result<Vec<_>, _> = db.iterator(IteratorMode::Start).fallible().collect();
+        assert_eq!(
+            items.unwrap(),
+            vec![(cba(b"k1"), cba(b"v1")), (cba(b"k2"), cba(b"v2"))]
+        );
+    }
+}
+
+#[test]
+fn test_iterator_status_corruption() {
+    let n = TemporaryDBPath::new();
+    {
+        let db = DB::open_default(&n).unwrap();
+        for i in 0..100u32 {
+            db.put(i.to_be_bytes(), vec![i as u8; 1024]).unwrap();
+        }
+        db.flush().unwrap();
+    }
+
+    // clobber the data blocks at the beginning of the table file
+    for entry in fs::read_dir(&n).unwrap() {
+        let path = entry.unwrap().path();
+        if path.extension().and_then(|ext| ext.to_str()) == Some("sst") {
+            let mut file = OpenOptions::new().write(true).open(path).unwrap();
+            file.seek(SeekFrom::Start(128)).unwrap();
+            file.write_all(&[0xff; 4096]).unwrap();
+        }
+    }
+
+    {
+        let db = DB::open_default(&n).unwrap();
+
+        let mut iter = db.raw_iterator();
+        iter.seek_to_first();
+        while iter.valid() {
+            iter.next();
+        }
+        assert_eq!(iter.status().unwrap_err().kind(), ErrorKind::Corruption);
+
+        // a plain iterator just ends, the fallible one reports why
+        let items: Vec<_> = db.iterator(IteratorMode::Start).fallible().collect();
+        let err = items.last().unwrap().as_ref().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Corruption);
+        assert!(items[..items.len() - 1].iter().all(Result::is_ok));
+    }
+}
+
 // FIXME: windows
 #[cfg(not(target_os = "windows"))]
 #[test]
@@ -0,0 +1,74 @@
+// Copyright 2019 Tyler Neely
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+extern crate ckb_rocksdb as rocksdb;
+
+use std::fs;
+use std::sync::{Arc, Mutex};
+
+use crate::rocksdb::{prelude::*, LogLevel, TemporaryDBPath};
+
+type Messages = Arc<Mutex<Vec<(LogLevel, String)>>>;
+
+fn open_with_logger(level: LogLevel, path: &TemporaryDBPath) -> (DB, Messages) {
+    let messages = Messages::default();
+    let recorded = messages.clone();
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    opts.set_logger(level, move |level: LogLevel, message: &str| {
+        recorded.lock().unwrap().push((level, message.to_owned()));
+    });
+    (DB::open(&opts, path).unwrap(), messages)
+}
+
+#[test]
+fn logger_receives_info_log() {
+    let path = TemporaryDBPath::new();
+    let (db, messages) = open_with_logger(LogLevel::Info, &path);
+    db.put(b"k1", b"v1").unwrap();
+    db.flush().unwrap();
+    drop(db);
+
+    let messages = messages.lock().unwrap();
+    assert!(messages
+        .iter()
+        .any(|(_, message)| message.contains("RocksDB version")));
+    assert!(messages
+        .iter()
+        .all(|(level, message)| { *level != LogLevel::Debug && !message.ends_with('\n') }));
+
+    // Nothing is written to LOG files.
+    let log_files = fs::read_dir(&path)
+        .unwrap()
+        .filter(|entry| {
+            let name = entry.as_ref().unwrap().file_name();
+            name.to_string_lossy().starts_with("LOG")
+        })
+        .count();
+    assert_eq!(log_files, 0);
+}
+
+#[test]
+fn logger_drops_messages_below_level() {
+    let path = TemporaryDBPath::new();
+    let (db, messages) = open_with_logger(LogLevel::Error, &path);
+    db.put(b"k1", b"v1").unwrap();
+    db.flush().unwrap();
+    drop(db);
+
+    let messages = messages.lock().unwrap();
+    assert!(messages
+        .iter()
+        .all(|(level, _)| matches!(level, LogLevel::Error | LogLevel::Fatal | LogLevel::Header)));
+}
@@ -0,0 +1,187 @@
+// Copyright 2019 Tyler Neely
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+extern crate ckb_rocksdb as rocksdb;
+
+use crate::rocksdb::{prelude::*, TemporaryDBPath, TransactionDB};
+
+fn open_with_cf(path: &TemporaryDBPath) -> DB {
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    opts.create_missing_column_families(true);
+    opts.set_disable_auto_compactions(true);
+    DB::open_cf(&opts, path, ["cf1"]).unwrap()
+}
+
+#[test]
+fn test_live_files_metadata() {
+    let path = TemporaryDBPath::new();
+    let db = open_with_cf(&path);
+    let cf1 = db.cf_handle("cf1").unwrap();
+
+    db.put(b"k1", b"v1").unwrap();
+    db.put(b"k2", b"v2").unwrap();
+    db.delete(b"k3").unwrap();
+    db.flush().unwrap();
+    db.put_cf(cf1, b"a", b"1").unwrap();
+    db.compact_range_cf(cf1, None, None);
+
+    let mut files = db.get_live_files_metadata();
+    files.sort_by(|a, b| a.column_family_name.cmp(&b.column_family_name));
+    assert_eq!(files.len(), 2);
+
+    assert_eq!(files[0].column_family_name, "cf1");
+    assert_eq!(files[0].num_entries, 1);
+    assert!(files[0].smallest_key.starts_with(b"a"));
+
+    let file = &files[1];
+    assert_eq!(file.column_family_name, "default");
+    assert_eq!(file.level, 0);
+    assert!(file.smallest_key.starts_with(b"k1"));
+    assert!(file.largest_key.starts_with(b"k3"));
+    assert_eq!(file.num_entries, 3);
+    assert_eq!(file.num_deletions, 1);
+    assert!(file.smallest_seqno < file.largest_seqno);
+    assert!(file.size > 0);
+    assert!(file
+        .directory
+        .join(file.name.trim_start_matches('/'))
+        .exists());
+}
+
+#[test]
+fn test_column_family_metadata() {
+    let path = TemporaryDBPath::new();
+    let db = open_with_cf(&path);
+    let default = db.cf_handle("default").unwrap();
+
+    db.put(b"k1", b"v1").unwrap();
+    db.flush().unwrap();
+    db.put(b"k2", b"v2").unwrap();
+    db.flush().unwrap();
+
+    let metadata = db.get_column_family_metadata(default);
+    assert_eq!(metadata.name, "default");
+    assert_eq!(metadata.file_count, 2);
+    assert_eq!(metadata.levels.len(), 7);
+    assert_eq!(metadata.levels[0].level, 0);
+    assert_eq!(metadata.levels[0].files.len(), 2);
+    assert_eq!(
+        metadata.size,
+        metadata.levels[0].files.iter().map(|f| f.size).sum::<u64>()
+    );
+    for file in &metadata.levels[0].files {
+        assert_eq!(file.num_entries, 1);
+        assert_eq!(file.num_deletions, 0);
+        assert_eq!(file.smallest_seqno, file.largest_seqno);
+        assert!(file
+            .directory
+            .join(file.name.trim_start_matches('/'))
+            .exists());
+    }
+
+    db.compact_range(None::<&[u8]>, None::<&[u8]>);
+    let metadata = db.get_column_family_metadata(default);
+    assert_eq!(metadata.file_count, 1);
+    assert!(metadata.levels[0].files.is_empty());
+    let level = metadata
+        .levels
+        .iter()
+        .find(|l| !l.files.is_empty())
+        .unwrap();
+    assert_eq!(level.size, metadata.size);
+    assert!(level.files[0].smallest_key.starts_with(b"k1"));
+    assert!(level.files[0].largest_key.starts_with(b"k2"));
+
+    let metadata = db.get_column_family_metadata(db.cf_handle("cf1").unwrap());
+    assert_eq!(metadata.name, "cf1");
+    assert_eq!(metadata.file_count, 0);
+    assert_eq!(metadata.size, 0);
+}
+
+#[test]
+fn test_disable_file_deletions() {
+    let path = TemporaryDBPath::new();
+    let db = open_with_cf(&path);
+
+    db.put(b"k1", b"v1").unwrap();
+    db.flush().unwrap();
+    db.put(b"k2", b"v2").unwrap();
+    db.flush().unwrap();
+    let flushed: Vec<_> = db
+        .get_live_files_metadata()
+        .into_iter()
+        .map(|f| f.directory.join(f.name.trim_start_matches('/')))
+        .collect();
+    assert_eq!(flushed.len(), 2);
+
+    let guard = db.disable_file_deletions().unwrap();
+    db.disable_file_deletions_raw().unwrap();
+    db.compact_range(None::<&[u8]>, None::<&[u8]>);
+    assert!(flushed.iter().all(|f| f.exists()));
+
+    drop(guard);
+    assert!(flushed.iter().all(|f| f.exists()));
+
+    db.enable_file_deletions(false).unwrap();
+    assert!(flushed.iter().all(|f| !f.exists()));
+}
+
+#[test]
+fn test_file_deletions_guard() {
+    let path = TemporaryDBPath::new();
+    let db = open_with_cf(&path);
+
+    db.put(b"k1", b"v1").unwrap();
+    db.flush().unwrap();
+    db.put(b"k2", b"v2").unwrap();
+    db.flush().unwrap();
+    let flushed: Vec<_> = db
+        .get_live_files_metadata()
+        .into_iter()
+        .map(|f| f.directory.join(f.name.trim_start_matches('/')))
+        .collect();
+
+    {
+        let _outer = db.disable_file_deletions().unwrap();
+        {
+            let _inner = db.disable_file_deletions().unwrap();
+            db.compact_range(None::<&[u8]>, None::<&[u8]>);
+        }
+        assert!(flushed.iter().all(|f| f.exists()));
+    }
+    assert!(flushed.iter().all(|f| !f.exists()));
+}
+
+#[test]
+fn test_transaction_db_metadata() {
+    let path = TemporaryDBPath::new();
+    {
+        let db = open_with_cf(&path);
+        db.put(b"k1", b"v1").unwrap();
+        db.flush().unwrap();
+    }
+
+    let db = TransactionDB::open_cf(&Options::default(), &path, ["cf1"]).unwrap();
+    let files = db.get_live_files_metadata();
+    assert_eq!(files.len(), 1);
+    assert_eq!(files[0].num_entries, 1);
+
+    let metadata = db.get_column_family_metadata(db.cf_handle("default").unwrap());
+    assert_eq!(metadata.file_count, 1);
+    assert_eq!(metadata.levels[0].files[0].size, files[0].size);
+
+    let _guard = db.disable_file_deletions().unwrap();
+    db.enable_file_deletions(true).unwrap();
+}
@@ -0,0 +1,83 @@
+// Copyright 2019 Tyler Neely
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// The panic policy is process wide, so these tests live in their own binary
+// and all of them use `PanicPolicy::Fail`.
+
+extern crate ckb_rocksdb as rocksdb;
+
+use crate::rocksdb::{
+    prelude::*, set_panic_policy, CompactionDecision, ErrorKind, MergeOperands, PanicPolicy,
+    TemporaryDBPath,
+};
+
+fn panicking_merge(
+    _new_key: &[u8],
+    existing_val: Option<&[u8]>,
+    operands: &mut MergeOperands,
+) -> Option<Vec<u8>> {
+    let mut result = existing_val.map(|v| v.to_vec()).unwrap_or_default();
+    for op in operands {
+        if op == b"panic" {
+            panic!("merge failed");
+        }
+        result.extend_from_slice(op);
+    }
+    Some(result)
+}
+
+fn panicking_filter(_level: u32, key: &[u8], _value: &[u8]) -> CompactionDecision {
+    if key == b"panic" {
+        panic!("filter failed");
+    }
+    CompactionDecision::Remove
+}
+
+#[test]
+fn merge_panic_fails_merge() {
+    set_panic_policy(PanicPolicy::Fail);
+
+    let path = TemporaryDBPath::new();
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    opts.set_merge_operator_associative("panicking", panicking_merge);
+    let db = DB::open(&opts, &path).unwrap();
+
+    db.put(b"k1", b"a").unwrap();
+    db.merge(b"k1", b"b").unwrap();
+    assert_eq!(&*db.get(b"k1").unwrap().unwrap(), b"ab");
+
+    db.merge(b"k2", b"panic").unwrap();
+    match db.get(b"k2") {
+        Err(e) => assert_eq!(e.kind(), ErrorKind::Corruption),
+        Ok(_) => panic!("should fail"),
+    }
+}
+
+#[test]
+fn compaction_filter_panic_keeps_entry() {
+    set_panic_policy(PanicPolicy::Fail);
+
+    let path = TemporaryDBPath::new();
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    opts.set_compaction_filter("panicking", panicking_filter);
+    let db = DB::open(&opts, &path).unwrap();
+
+    db.put(b"k1", b"a").unwrap();
+    db.put(b"panic", b"b").unwrap();
+    db.compact_range(None::<&[u8]>, None::<&[u8]>);
+    assert!(db.get(b"k1").unwrap().is_none());
+    assert_eq!(&*db.get(b"panic").unwrap().unwrap(), b"b");
+}
@@ -14,7 +14,10 @@
 //
 extern crate ckb_rocksdb as rocksdb;
 
-use crate::rocksdb::{prelude::*, TemporaryDBPath};
+use crate::rocksdb::{
+    prelude::*, Cache, Env, ErrorKind, RateLimiter, SstFileManager, TemporaryDBPath, TransactionDB,
+    WriteBufferManager,
+};
 
 #[test]
 fn test_set_num_levels() {
@@ -37,3 +40,171 @@ fn test_increase_parallelism() {
         let _db = DB::open(&opts, &n).unwrap();
     }
 }
+
+#[test]
+fn test_shared_rate_limiter() {
+    let limiter = RateLimiter::new(64 * 1024 * 1024, 100 * 1000, 10).unwrap();
+    let n1 = TemporaryDBPath::new();
+    let n2 = TemporaryDBPath::new();
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.set_rate_limiter(&limiter);
+        let db1 = DB::open(&opts, &n1).unwrap();
+        let db2 = DB::open(&opts, &n2).unwrap();
+
+        db1.put(b"k1", b"v1").unwrap();
+        db1.flush().unwrap();
+        let through = limiter.total_bytes_through();
+        assert!(through > 0);
+
+        db2.put(b"k1", b"v1").unwrap();
+        db2.flush().unwrap();
+        assert!(limiter.total_bytes_through() > through);
+
+        limiter.set_bytes_per_second(1024 * 1024);
+        assert_eq!(limiter.get_bytes_per_second(), 1024 * 1024);
+        db1.put(b"k2", b"v2").unwrap();
+        db1.flush().unwrap();
+    }
+}
+
+#[test]
+fn test_auto_tuned_rate_limiter() {
+    let limiter = RateLimiter::new_auto_tuned(64 * 1024 * 1024, 100 * 1000, 10).unwrap();
+    assert!(limiter.get_bytes_per_second() <= 64 * 1024 * 1024);
+
+    let n = TemporaryDBPath::new();
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.set_rate_limiter(&limiter);
+        let db = DB::open(&opts, &n).unwrap();
+        db.put(b"k1", b"v1").unwrap();
+        db.flush().unwrap();
+        assert!(limiter.total_bytes_through() > 0);
+    }
+}
+
+#[test]
+fn test_rate_limiter_invalid_arguments() {
+    for (rate, refill_period_us, fairness) in
+        &[(0, 100 * 1000, 10), (1024, 0, 10), (1024, 100 * 1000, 0)]
+    {
+        let err = RateLimiter::new(*rate, *refill_period_us, *fairness)
+            .err()
+            .unwrap();
+        assert_eq!(err.kind(), ErrorKind::InvalidArgument);
+        assert!(err.as_ref().contains("must be positive"));
+        let err = RateLimiter::new_auto_tuned(*rate, *refill_period_us, *fairness)
+            .err()
+            .unwrap();
+        assert_eq!(err.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+#[should_panic(expected = "must be positive")]
+fn test_rate_limiter_set_zero_rate() {
+    let limiter = RateLimiter::new(1024, 100 * 1000, 10).unwrap();
+    limiter.set_bytes_per_second(0);
+}
+
+#[test]
+fn test_shared_write_buffer_manager() {
+    let cache = Cache::new_lru_cache(64 * 1024 * 1024).unwrap();
+    let manager = WriteBufferManager::new_with_cache(16 * 1024 * 1024, &cache, true);
+    assert!(manager.enabled());
+    assert!(manager.cost_to_cache());
+    assert_eq!(manager.get_buffer_size(), 16 * 1024 * 1024);
+
+    let n1 = TemporaryDBPath::new();
+    let n2 = TemporaryDBPath::new();
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.set_write_buffer_manager(&manager);
+        let db = DB::open(&opts, &n1).unwrap();
+        let txn_db = TransactionDB::open(&opts, &n2).unwrap();
+
+        db.put(b"k1", b"v1").unwrap();
+        txn_db.put(b"k1", b"v1").unwrap();
+        assert!(manager.get_usage() > 0);
+        assert!(manager.get_mutable_memtable_usage() > 0);
+        assert!(manager.get_cache_usage() >= manager.get_usage());
+        assert!(cache.get_usage() >= manager.get_cache_usage());
+
+        manager.set_buffer_size(32 * 1024 * 1024);
+        assert_eq!(manager.get_buffer_size(), 32 * 1024 * 1024);
+    }
+    assert_eq!(manager.get_usage(), 0);
+}
+
+#[test]
+#[should_panic(expected = "cannot be changed to or from 0")]
+fn test_write_buffer_manager_set_zero_buffer_size() {
+    let manager = WriteBufferManager::new(16 * 1024 * 1024, false);
+    manager.set_buffer_size(0);
+}
+
+#[test]
+#[should_panic(expected = "cannot be changed to or from 0")]
+fn test_write_buffer_manager_set_buffer_size_without_limit() {
+    let manager = WriteBufferManager::new(0, false);
+    assert!(!manager.enabled());
+    manager.set_buffer_size(16 * 1024 * 1024);
+}
+
+#[test]
+fn test_shared_sst_file_manager() {
+    let env = Env::default().unwrap();
+    let manager = SstFileManager::new(&env).unwrap();
+    manager.set_delete_rate_bytes_per_second(1024 * 1024);
+    assert_eq!(manager.get_delete_rate_bytes_per_second(), 1024 * 1024);
+    manager.set_max_trash_db_ratio(0.5);
+    assert!((manager.get_max_trash_db_ratio() - 0.5).abs() < f64::EPSILON);
+
+    let n1 = TemporaryDBPath::new();
+    let n2 = TemporaryDBPath::new();
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.set_env(&env);
+        opts.set_sst_file_manager(&manager);
+        let db1 = DB::open(&opts, &n1).unwrap();
+        let db2 = DB::open(&opts, &n2).unwrap();
+
+        db1.put(b"k1", b"v1").unwrap();
+        db1.flush().unwrap();
+        let size = manager.get_total_size();
+        assert!(size > 0);
+        db2.put(b"k1", b"v1").unwrap();
+        db2.flush().unwrap();
+        assert!(manager.get_total_size() > size);
+
+        manager.set_max_allowed_space_usage(1);
+        manager.set_compaction_buffer_size(1);
+        assert!(manager.is_max_allowed_space_reached());
+        assert!(manager.is_max_allowed_space_reached_including_compactions());
+        // The next flush stops the database.
+        db1.put(b"k2", b"v2").unwrap();
+        let _ = db1.flush();
+        assert!(db1.put(b"k3", b"v3").is_err());
+    }
+}
+
+#[test]
+fn test_sst_file_manager_outlives_handle() {
+    let n = TemporaryDBPath::new();
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    {
+        let env = Env::default().unwrap();
+        let manager = SstFileManager::new(&env).unwrap();
+        opts.set_env(&env);
+        opts.set_sst_file_manager(&manager);
+    }
+    let db = DB::open(&opts, &n).unwrap();
+    db.put(b"k1", b"v1").unwrap();
+    db.flush().unwrap();
+}
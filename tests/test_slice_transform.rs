@@ -81,3 +81,31 @@ fn test_no_in_domain() {
         );
     }
 }
+
+#[test]
+fn test_closure_slice_transform() {
+    let db_path = TemporaryDBPath::new();
+    {
+        let prefix_len = 3;
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.set_prefix_extractor(SliceTransform::create(
+            "runtime prefix",
+            move |key: &[u8]| &key[..prefix_len],
+            Some(Box::new(move |key: &[u8]| key.len() >= prefix_len)),
+        ));
+
+        let db = DB::open(&opts, &db_path).unwrap();
+        db.put(b"aaa1", b"1").unwrap();
+        db.put(b"aaa2", b"2").unwrap();
+        db.put(b"bbb1", b"3").unwrap();
+        db.put(b"a", b"4").unwrap();
+
+        let keys: Vec<_> = db
+            .prefix_iterator(b"aaa")
+            .map(|(k, _)| k.to_vec())
+            .collect();
+        assert_eq!(keys, vec![b"aaa1".to_vec(), b"aaa2".to_vec()]);
+        assert_eq!(&*db.get(b"a").unwrap().unwrap(), b"4");
+    }
+}
@@ -0,0 +1,124 @@
+extern crate ckb_rocksdb as rocksdb;
+
+use std::fs;
+use std::path::Path;
+
+use crate::rocksdb::{prelude::*, IteratorMode, SstFileReader, SstFileWriter, TemporaryDBPath};
+
+fn write_sst(path: &Path) {
+    let opts = Options::default();
+    let mut writer = SstFileWriter::create(&opts);
+    writer.open(path).unwrap();
+    writer.put(b"k1", b"v1").unwrap();
+    writer.put(b"k2", b"value2").unwrap();
+    writer.delete(b"k3").unwrap();
+    writer.finish().unwrap();
+}
+
+#[test]
+fn sst_file_reader_reads_writer_output() {
+    let dir = TemporaryDBPath::new();
+    let path = dir.join("data.sst");
+    write_sst(&path);
+
+    let reader = SstFileReader::open(&Options::default(), &path).unwrap();
+    reader.verify_checksum().unwrap();
+
+    let entries: Vec<_> = reader
+        .iterator(IteratorMode::Start)
+        .map(|(k, v)| (k.to_vec(), v.to_vec()))
+        .collect();
+    assert_eq!(
+        entries,
+        vec![
+            (b"k1".to_vec(), b"v1".to_vec()),
+            (b"k2".to_vec(), b"value2".to_vec())
+        ]
+    );
+
+    let props = reader.table_properties();
+    assert_eq!(props.num_entries, 3);
+    assert_eq!(props.num_deletions, 1);
+    assert_eq!(props.comparator_name, "leveldb.BytewiseComparator");
+    assert_eq!(props.raw_value_size, 8);
+    assert!(props.raw_key_size > 0);
+    assert!(props.data_size > 0);
+    assert_eq!(props.creation_time, 0);
+    assert_eq!(props.file_creation_time, 0);
+    // The deletion of k3 counts, as it does for ingestion.
+    assert_eq!(reader.smallest_key().unwrap(), Some(b"k1".to_vec()));
+    assert_eq!(reader.largest_key().unwrap(), Some(b"k3".to_vec()));
+}
+
+#[test]
+fn sst_file_reader_range_tombstone_bounds() {
+    let dir = TemporaryDBPath::new();
+    let path = dir.join("data.sst");
+    let opts = Options::default();
+    let mut writer = SstFileWriter::create(&opts);
+    writer.open(&path).unwrap();
+    writer.delete_range(b"k2", b"k5").unwrap();
+    writer.finish().unwrap();
+
+    let reader = SstFileReader::open(&opts, &path).unwrap();
+    assert_eq!(reader.iterator(IteratorMode::Start).count(), 0);
+    assert_eq!(reader.smallest_key().unwrap(), Some(b"k2".to_vec()));
+    assert_eq!(reader.largest_key().unwrap(), Some(b"k5".to_vec()));
+
+    let path = dir.join("mixed.sst");
+    let mut writer = SstFileWriter::create(&opts);
+    writer.open(&path).unwrap();
+    writer.put(b"k3", b"v3").unwrap();
+    writer.delete_range(b"k1", b"k2").unwrap();
+    writer.finish().unwrap();
+
+    let reader = SstFileReader::open(&opts, &path).unwrap();
+    assert_eq!(reader.smallest_key().unwrap(), Some(b"k1".to_vec()));
+    assert_eq!(reader.largest_key().unwrap(), Some(b"k3".to_vec()));
+}
+
+#[test]
+fn sst_file_reader_reads_db_files() {
+    let path = TemporaryDBPath::new();
+    let db = DB::open_default(&path).unwrap();
+    db.put(b"a", b"1").unwrap();
+    db.put(b"b", b"2").unwrap();
+    db.flush().unwrap();
+
+    let file = &db.get_live_files_metadata()[0];
+    let file_path = file.directory.join(file.name.trim_start_matches('/'));
+    let reader = SstFileReader::open(&Options::default(), &file_path).unwrap();
+    reader.verify_checksum().unwrap();
+
+    let props = reader.table_properties();
+    assert_eq!(props.num_entries, 2);
+    assert!(props.file_creation_time > 0);
+    assert_eq!(reader.smallest_key().unwrap(), Some(b"a".to_vec()));
+    assert_eq!(reader.largest_key().unwrap(), Some(b"b".to_vec()));
+
+    let mut iter = reader.raw_iterator();
+    iter.seek(b"b");
+    assert_eq!(iter.value(), Some(&b"2"[..]));
+}
+
+#[test]
+fn sst_file_reader_detects_corruption() {
+    let dir = TemporaryDBPath::new();
+    let path = dir.join("data.sst");
+    write_sst(&path);
+
+    // The first data block starts at the beginning of the file.
+    let mut contents = fs::read(&path).unwrap();
+    contents[0] ^= 0xff;
+    fs::write(&path, contents).unwrap();
+
+    let reader = SstFileReader::open(&Options::default(), &path).unwrap();
+    assert!(reader.verify_checksum().is_err());
+    assert!(reader.smallest_key().is_err());
+}
+
+#[test]
+fn sst_file_reader_missing_file() {
+    let dir = TemporaryDBPath::new();
+    assert!(SstFileReader::open(&Options::default(), dir.join("missing.sst")).is_err());
+}
@@ -1,6 +1,6 @@
 extern crate ckb_rocksdb as rocksdb;
 
-use crate::rocksdb::{prelude::*, TemporaryDBPath};
+use crate::rocksdb::{prelude::*, ColumnFamilyDescriptor, TemporaryDBPath, TransactionDB};
 use rocksdb::SstFileWriter;
 
 #[test]
@@ -35,3 +35,134 @@ fn sst_file_writer_works() {
         assert!(db.get(b"k3").unwrap().is_none());
     }
 }
+
+#[test]
+fn sst_file_writer_finish_returns_file_info() {
+    let dir = TemporaryDBPath::new();
+    let writer_path = dir.join("filewriter");
+
+    let opts = Options::default();
+    let mut writer = SstFileWriter::create(&opts);
+    writer.open(&writer_path).unwrap();
+    writer.put(b"k1", b"v1").unwrap();
+    writer.put(b"k2", b"v2").unwrap();
+    writer.delete(b"k3").unwrap();
+    writer.delete_range(b"k7", b"k9").unwrap();
+    writer.delete_range(b"k5", b"k6").unwrap();
+    let info = writer.finish().unwrap();
+
+    assert_eq!(info.file_path, writer_path);
+    assert_eq!(info.smallest_key, b"k1");
+    assert_eq!(info.largest_key, b"k3");
+    assert_eq!(info.smallest_range_del_key, b"k5");
+    assert_eq!(info.largest_range_del_key, b"k9");
+    assert_eq!(info.num_entries, 3);
+    assert_eq!(info.num_range_del_entries, 2);
+    assert_eq!(info.sequence_number, 0);
+    assert_eq!(
+        info.file_size,
+        std::fs::metadata(&writer_path).unwrap().len()
+    );
+}
+
+#[test]
+fn sst_file_writer_delete_range() {
+    let path = TemporaryDBPath::new();
+    let writer_path = path.join("filewriter");
+
+    let db = DB::open_default(path.join("db")).unwrap();
+    for key in &[b"k1", b"k2", b"k3", b"k4"] {
+        db.put(key, b"v").unwrap();
+    }
+
+    let opts = Options::default();
+    let mut writer = SstFileWriter::create(&opts);
+    writer.open(&writer_path).unwrap();
+    writer.put(b"k1", b"new").unwrap();
+    writer.delete_range(b"k2", b"k4").unwrap();
+    writer.finish().unwrap();
+
+    db.ingest_external_file(vec![&writer_path]).unwrap();
+    assert_eq!(&*db.get(b"k1").unwrap().unwrap(), b"new");
+    assert!(db.get(b"k2").unwrap().is_none());
+    assert!(db.get(b"k3").unwrap().is_none());
+    assert_eq!(&*db.get(b"k4").unwrap().unwrap(), b"v");
+}
+
+#[test]
+fn sst_file_writer_create_cf() {
+    let path = TemporaryDBPath::new();
+    let writer_path = path.join("filewriter");
+
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    opts.create_missing_column_families(true);
+    let mut cf_opts = Options::default();
+    cf_opts.set_comparator("reverse", |a: &[u8], b: &[u8]| b.cmp(a));
+    let cfs = vec![ColumnFamilyDescriptor::new("reverse", cf_opts)];
+    let db = DB::open_cf_descriptors(&opts, path.join("db"), cfs).unwrap();
+    let cf = db.cf_handle("reverse").unwrap();
+
+    {
+        // Keys must be added in the order of the column family's comparator.
+        let mut writer = SstFileWriter::create_cf(&db, cf);
+        writer.open(&writer_path).unwrap();
+        writer.put(b"k2", b"v2").unwrap();
+        writer.put(b"k1", b"v1").unwrap();
+        let info = writer.finish().unwrap();
+        assert_eq!(info.smallest_key, b"k2");
+        assert_eq!(info.largest_key, b"k1");
+    }
+
+    db.ingest_external_file_cf(cf, vec![&writer_path]).unwrap();
+    assert_eq!(&*db.get_cf(cf, b"k1").unwrap().unwrap(), b"v1");
+    assert_eq!(&*db.get_cf(cf, b"k2").unwrap().unwrap(), b"v2");
+
+    // The default column family uses the bytewise comparator.
+    let mut writer = SstFileWriter::create_cf(&db, db.cf_handle("default").unwrap());
+    writer.open(path.join("default")).unwrap();
+    writer.put(b"k2", b"v2").unwrap();
+    assert!(writer.put(b"k1", b"v1").is_err());
+}
+
+#[test]
+fn sst_file_writer_create_with_cf_options() {
+    let path = TemporaryDBPath::new();
+    let writer_path = path.join("filewriter");
+
+    let mut cf_opts = Options::default();
+    cf_opts.set_comparator("reverse", |a: &[u8], b: &[u8]| b.cmp(a));
+    {
+        let mut writer = SstFileWriter::create_with_cf_options(&cf_opts);
+        writer.open(&writer_path).unwrap();
+        writer.put(b"k2", b"v2").unwrap();
+        writer.put(b"k1", b"v1").unwrap();
+        writer.finish().unwrap();
+    }
+
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    opts.create_missing_column_families(true);
+    let cfs = vec![ColumnFamilyDescriptor::new("reverse", cf_opts)];
+    let db = DB::open_cf_descriptors(&opts, path.join("db"), cfs).unwrap();
+    let cf = db.cf_handle("reverse").unwrap();
+    db.ingest_external_file_cf(cf, vec![&writer_path]).unwrap();
+    assert_eq!(&*db.get_cf(cf, b"k1").unwrap().unwrap(), b"v1");
+}
+
+#[test]
+fn sst_file_writer_create_cf_transaction_db() {
+    let path = TemporaryDBPath::new();
+    let writer_path = path.join("filewriter");
+
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    let db = TransactionDB::open(&opts, path.join("db")).unwrap();
+    let mut writer = SstFileWriter::create_cf(&db, db.cf_handle("default").unwrap());
+    writer.open(&writer_path).unwrap();
+    writer.put(b"k1", b"v1").unwrap();
+    assert!(writer.put(b"k0", b"v0").is_err());
+    let info = writer.finish().unwrap();
+    assert_eq!(info.num_entries, 1);
+    assert_eq!(info.smallest_key, b"k1");
+}
@@ -0,0 +1,99 @@
+// Copyright 2019 Tyler Neely
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+extern crate ckb_rocksdb as rocksdb;
+
+use crate::rocksdb::{ffi, prelude::*, Histogram, StatsLevel, TemporaryDBPath, Ticker};
+use std::ffi::CStr;
+
+#[test]
+fn ticker_and_histogram_names() {
+    assert_eq!(Ticker::ALL[0], Ticker::BlockCacheMiss);
+    assert_eq!(Ticker::BlockCacheMiss.name(), "rocksdb.block.cache.miss");
+    assert_eq!(
+        Ticker::BloomFilterUseful.name(),
+        "rocksdb.bloom.filter.useful"
+    );
+    assert_eq!(Ticker::StallMicros.name(), "rocksdb.stall.micros");
+    assert_eq!(Histogram::ALL[0], Histogram::DbGet);
+    assert_eq!(Histogram::DbGet.name(), "rocksdb.db.get.micros");
+}
+
+fn rocksdb_name(name: *const std::os::raw::c_char) -> Option<&'static str> {
+    if name.is_null() {
+        None
+    } else {
+        Some(unsafe { CStr::from_ptr(name) }.to_str().unwrap())
+    }
+}
+
+#[test]
+fn names_match_rocksdb() {
+    for (i, ticker) in Ticker::ALL.iter().enumerate() {
+        assert_eq!(*ticker as usize, i);
+        let name = rocksdb_name(unsafe { ffi::rocksdb_statistics_ticker_name(i as u32) });
+        assert_eq!(name, Some(ticker.name()));
+    }
+    let count = Ticker::ALL.len() as u32;
+    assert!(unsafe { ffi::rocksdb_statistics_ticker_name(count) }.is_null());
+
+    for (i, histogram) in Histogram::ALL.iter().enumerate() {
+        assert_eq!(*histogram as usize, i);
+        let name = rocksdb_name(unsafe { ffi::rocksdb_statistics_histogram_name(i as u32) });
+        assert_eq!(name, Some(histogram.name()));
+    }
+    let count = Histogram::ALL.len() as u32;
+    assert!(unsafe { ffi::rocksdb_statistics_histogram_name(count) }.is_null());
+}
+
+#[test]
+fn statistics_test() {
+    let path = TemporaryDBPath::new();
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    opts.enable_statistics();
+    opts.set_statistics_level(StatsLevel::All);
+    {
+        let db = DB::open(&opts, &path).unwrap();
+        db.put(b"k1", b"v1").unwrap();
+        db.put(b"k2", b"v2").unwrap();
+        assert!(db.get(b"k1").unwrap().is_some());
+        assert!(db.get(b"k3").unwrap().is_none());
+
+        assert_eq!(opts.get_ticker_count(Ticker::NumberKeysWritten), 2);
+        assert_eq!(opts.get_ticker_count(Ticker::NumberKeysRead), 2);
+        assert_eq!(opts.get_ticker_count(Ticker::MemtableHit), 1);
+        assert_eq!(opts.get_ticker_count(Ticker::MemtableMiss), 1);
+
+        let get = opts.get_histogram_data(Histogram::DbGet);
+        assert_eq!(get.count, 2);
+        assert!(get.max >= get.min);
+
+        // The text dump reports the same values.
+        let dump = opts.get_statistics().unwrap();
+        assert!(dump.contains("rocksdb.number.keys.written COUNT : 2"));
+
+        opts.reset_statistics().unwrap();
+        assert_eq!(opts.get_ticker_count(Ticker::NumberKeysWritten), 0);
+        assert_eq!(opts.get_histogram_data(Histogram::DbGet).count, 0);
+    }
+}
+
+#[test]
+fn statistics_disabled_test() {
+    let opts = Options::default();
+    assert_eq!(opts.get_ticker_count(Ticker::BlockCacheHit), 0);
+    assert_eq!(opts.get_histogram_data(Histogram::DbGet).count, 0);
+    assert!(opts.reset_statistics().is_ok());
+}
@@ -1,8 +1,8 @@
 extern crate ckb_rocksdb as rocksdb;
 
 use crate::rocksdb::{
-    prelude::*, MergeOperands, Options, TemporaryDBPath, TransactionDB, TransactionDBOptions,
-    TransactionOptions, WriteOptions,
+    prelude::*, ErrorKind, MergeOperands, Options, SubCode, TemporaryDBPath, TransactionDB,
+    TransactionDBOptions, TransactionOptions, TxnDBWritePolicy, WriteOptions,
 };
 
 #[test]
@@ -55,6 +55,26 @@ pub fn test_transaction() {
     }
 }
 
+#[test]
+pub fn test_transaction_lock_timeout_error_kind() {
+    let path = TemporaryDBPath::new();
+    {
+        let db = TransactionDB::open_default(&path).unwrap();
+        let write_options = WriteOptions::default();
+        let transaction_options = TransactionOptions::new();
+        transaction_options.set_lock_timeout(10);
+
+        let trans1 = db.transaction(&write_options, &transaction_options);
+        let trans2 = db.transaction(&write_options, &transaction_options);
+
+        trans1.put(b"k1", b"v1").unwrap();
+        let err = trans2.put(b"k1", b"v2").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::TimedOut);
+        assert_eq!(err.subcode(), SubCode::LockTimeout);
+        assert!(err.as_ref().contains("Timeout waiting to lock key"));
+    }
+}
+
 #[test]
 pub fn test_transaction_rollback_savepoint() {
     let path = TemporaryDBPath::new();
@@ -250,6 +270,215 @@ pub fn get_for_update_cf() {
     }
 }
 
+#[test]
+pub fn test_transaction_multi_get() {
+    let path = TemporaryDBPath::new();
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        let topts = TransactionDBOptions::default();
+
+        let mut db = TransactionDB::open_with_descriptor(&opts, &path, topts).unwrap();
+
+        db.create_cf("cf1", &opts)
+            .expect("failed to create new column family cf1");
+        let cf1 = db.cf_handle("cf1").expect("column family not exists.");
+
+        db.put(b"k1", b"v1").unwrap();
+        db.put_cf(cf1, b"k2", b"v2").unwrap();
+
+        let values = db.multi_get([b"k1", b"k2"]);
+        assert_eq!(values[0].as_ref().unwrap().as_deref(), Some(&b"v1"[..]));
+        assert!(values[1].as_ref().unwrap().is_none());
+
+        let values = db.batched_multi_get_cf(cf1, [b"k1", b"k2"], true);
+        assert!(values[0].as_ref().unwrap().is_none());
+        assert_eq!(values[1].as_ref().unwrap().as_deref(), Some(&b"v2"[..]));
+
+        let snapshot = db.snapshot();
+        db.put(b"k3", b"v3").unwrap();
+        let values = snapshot.multi_get([b"k1", b"k3"]);
+        assert!(values[0].as_ref().unwrap().is_some());
+        assert!(values[1].as_ref().unwrap().is_none());
+
+        let trans = db.transaction_default();
+        trans.put(b"k4", b"v4").unwrap();
+        trans.put_cf(cf1, b"k1", b"v1").unwrap();
+
+        let values = trans.multi_get([b"k1", b"k3", b"k4", b"k5"]);
+        let values: Vec<_> = values
+            .into_iter()
+            .map(|v| v.unwrap().map(|v| v.to_vec()))
+            .collect();
+        assert_eq!(
+            values,
+            vec![
+                Some(b"v1".to_vec()),
+                Some(b"v3".to_vec()),
+                Some(b"v4".to_vec()),
+                None
+            ]
+        );
+
+        let values = trans.multi_get_cf(vec![(cf1, b"k1"), (cf1, b"k2")]);
+        assert_eq!(values[0].as_ref().unwrap().as_deref(), Some(&b"v1"[..]));
+        assert_eq!(values[1].as_ref().unwrap().as_deref(), Some(&b"v2"[..]));
+
+        let values = trans.batched_multi_get_cf(cf1, [b"k1", b"k2", b"k3"], true);
+        assert_eq!(values[0].as_ref().unwrap().as_deref(), Some(&b"v1"[..]));
+        assert_eq!(values[1].as_ref().unwrap().as_deref(), Some(&b"v2"[..]));
+        assert!(values[2].as_ref().unwrap().is_none());
+
+        trans.commit().unwrap();
+    }
+}
+
+#[test]
+pub fn multi_get_for_update() {
+    let path = TemporaryDBPath::new();
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        let topts = TransactionDBOptions::default();
+
+        let mut db = TransactionDB::open_with_descriptor(&opts, &path, topts).unwrap();
+
+        db.create_cf("cf1", &opts)
+            .expect("failed to create new column family cf1");
+        let cf1 = db.cf_handle("cf1").expect("column family not exists.");
+
+        db.put("k1", "v1").expect("failed to put k1 v1");
+        db.put_cf(cf1, "k2", "v2").expect("failed to put k2 v2");
+
+        let tran1 = db.transaction_default();
+        let values = tran1.multi_get_for_update(["k1", "k3"]);
+        assert_eq!(values[0].as_ref().unwrap().as_deref(), Some(&b"v1"[..]));
+        assert!(values[1].as_ref().unwrap().is_none());
+
+        let values = tran1.multi_get_for_update_cf(vec![(cf1, "k2")]);
+        assert_eq!(values[0].as_ref().unwrap().as_deref(), Some(&b"v2"[..]));
+
+        // every key read for update is locked, even the missing ones
+        assert!(db.put("k1", "v2").is_err());
+        assert!(db.put("k3", "v3").is_err());
+        assert!(db.put_cf(cf1, "k2", "v3").is_err());
+
+        tran1.commit().unwrap();
+        db.put("k1", "v2").expect("failed to put k1 v2");
+    }
+}
+
+#[test]
+pub fn test_transaction_two_phase_commit() {
+    let path = TemporaryDBPath::new();
+    {
+        let db = TransactionDB::open_default(&path).unwrap();
+
+        let trans = db.transaction_default();
+        trans.put(b"k1", b"v1").unwrap();
+        // only named transactions can be prepared
+        let err = trans.prepare().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidArgument);
+
+        trans.set_name(b"txn1").unwrap();
+        assert_eq!(trans.name(), b"txn1");
+        trans.prepare().unwrap();
+        assert!(db.get(b"k1").unwrap().is_none());
+        trans.commit().unwrap();
+        assert_eq!(&*db.get(b"k1").unwrap().unwrap(), b"v1");
+
+        // names must be unique among live transactions
+        let trans1 = db.transaction_default();
+        trans1.set_name(b"txn2").unwrap();
+        let trans2 = db.transaction_default();
+        assert!(trans2.set_name(b"txn2").is_err());
+        assert!(trans2.name().is_empty());
+    }
+}
+
+#[test]
+fn prepared_transactions_excludes_live_transactions() {
+    let path = TemporaryDBPath::new();
+    {
+        let db = TransactionDB::open_default(&path).unwrap();
+        let trans = db.transaction_default();
+        trans.put(b"k1", b"v1").unwrap();
+        trans.set_name(b"txn1").unwrap();
+        trans.prepare().unwrap();
+
+        // the transaction is owned by `trans` only
+        let prepared = db.prepared_transactions();
+        assert!(prepared.is_empty());
+        drop(prepared);
+        drop(trans);
+        assert!(db.get(b"k1").unwrap().is_none());
+    }
+}
+
+fn crash_between_prepare_and_commit(write_policy: TxnDBWritePolicy) {
+    let path = TemporaryDBPath::new();
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+
+    let open = || {
+        let topts = TransactionDBOptions::default();
+        topts.set_write_policy(write_policy);
+        TransactionDB::open_with_descriptor(&opts, &path, topts).unwrap()
+    };
+
+    {
+        let db = open();
+        assert!(db.prepared_transactions().is_empty());
+
+        for (name, key) in &[("txn1", b"k1"), ("txn2", b"k2")] {
+            let trans = db.transaction_default();
+            trans.put(key, b"v").unwrap();
+            trans.set_name(name).unwrap();
+            trans.prepare().unwrap();
+            // neither committed nor rolled back, as if the process died here
+            std::mem::forget(trans);
+        }
+    }
+
+    {
+        let db = open();
+        assert!(db.get(b"k1").unwrap().is_none());
+        assert!(db.get(b"k2").unwrap().is_none());
+
+        let mut prepared = db.prepared_transactions();
+        prepared.sort_by_key(|trans| trans.name());
+        assert_eq!(prepared.len(), 2);
+        // they are handed out once
+        assert!(db.prepared_transactions().is_empty());
+        assert_eq!(prepared[0].name(), b"txn1");
+        assert_eq!(prepared[1].name(), b"txn2");
+
+        prepared[0].commit().unwrap();
+        prepared[1].rollback().unwrap();
+        drop(prepared);
+
+        assert_eq!(&*db.get(b"k1").unwrap().unwrap(), b"v");
+        assert!(db.get(b"k2").unwrap().is_none());
+    }
+
+    {
+        let db = open();
+        assert!(db.prepared_transactions().is_empty());
+        assert_eq!(&*db.get(b"k1").unwrap().unwrap(), b"v");
+        assert!(db.get(b"k2").unwrap().is_none());
+    }
+}
+
+#[test]
+pub fn test_transaction_recover_prepared() {
+    crash_between_prepare_and_commit(TxnDBWritePolicy::WriteCommitted);
+}
+
+#[test]
+pub fn test_transaction_recover_prepared_write_prepared() {
+    crash_between_prepare_and_commit(TxnDBWritePolicy::WritePrepared);
+}
+
 #[test]
 pub fn test_transaction_merge() {
     #[allow(clippy::unnecessary_wraps)]
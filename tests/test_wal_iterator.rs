@@ -0,0 +1,88 @@
+// Copyright 2019 Tyler Neely
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+extern crate ckb_rocksdb as rocksdb;
+
+use crate::rocksdb::{prelude::*, TemporaryDBPath, TransactionDB, WriteBatch};
+
+#[test]
+fn test_get_updates_since() {
+    let n = TemporaryDBPath::new();
+    {
+        let db = DB::open_default(&n).unwrap();
+        assert_eq!(db.latest_sequence_number(), 0);
+
+        db.put(b"k1", b"v1").unwrap();
+        db.put(b"k2", b"v2").unwrap();
+
+        let mut batch = WriteBatch::default();
+        batch.put(b"k3", b"v3").unwrap();
+        batch.put(b"k4", b"v4").unwrap();
+        batch.delete(b"k1").unwrap();
+        db.write(&batch).unwrap();
+
+        assert_eq!(db.latest_sequence_number(), 5);
+
+        let updates: Vec<_> = db
+            .get_updates_since(0)
+            .unwrap()
+            .map(|item| {
+                let (seq, batch) = item.unwrap();
+                (seq, batch.len())
+            })
+            .collect();
+        assert_eq!(updates, vec![(1, 1), (2, 1), (3, 3)]);
+
+        // starts with the batch containing the requested sequence number
+        let updates: Vec<_> = db
+            .get_updates_since(4)
+            .unwrap()
+            .map(|item| item.unwrap().0)
+            .collect();
+        assert_eq!(updates, vec![3]);
+
+        // tailing picks up later writes with a new iterator
+        db.put(b"k5", b"v5").unwrap();
+        let mut iter = db.get_updates_since(6).unwrap();
+        let (seq, batch) = iter.next().unwrap().unwrap();
+        assert_eq!(seq, 6);
+        assert_eq!(batch.len(), 1);
+        assert!(iter.next().is_none());
+        assert!(iter.status().is_ok());
+    }
+}
+
+#[test]
+fn test_transaction_db_get_updates_since() {
+    let n = TemporaryDBPath::new();
+    {
+        let db = TransactionDB::open_default(&n).unwrap();
+
+        db.put(b"k1", b"v1").unwrap();
+
+        let trans = db.transaction_default();
+        trans.put(b"k2", b"v2").unwrap();
+        trans.put(b"k3", b"v3").unwrap();
+        trans.commit().unwrap();
+
+        assert_eq!(db.latest_sequence_number(), 3);
+
+        let writes: usize = db
+            .get_updates_since(0)
+            .unwrap()
+            .map(|item| item.unwrap().1.len())
+            .sum();
+        assert_eq!(writes, 3);
+    }
+}
@@ -14,7 +14,66 @@
 //
 extern crate ckb_rocksdb as rocksdb;
 
-use crate::rocksdb::WriteBatch;
+use crate::rocksdb::{prelude::*, ErrorKind, TemporaryDBPath, WriteBatch, WriteBatchHandler};
+use std::panic;
+
+#[derive(Debug, PartialEq)]
+enum Op {
+    Put(u32, Vec<u8>, Vec<u8>),
+    Delete(u32, Vec<u8>),
+    SingleDelete(u32, Vec<u8>),
+    Merge(u32, Vec<u8>, Vec<u8>),
+    DeleteRange(u32, Vec<u8>, Vec<u8>),
+}
+
+#[derive(Default)]
+struct Recorder {
+    ops: Vec<Op>,
+}
+
+impl WriteBatchHandler for Recorder {
+    fn put(&mut self, key: &[u8], value: &[u8]) {
+        self.put_cf(0, key, value);
+    }
+
+    fn delete(&mut self, key: &[u8]) {
+        self.delete_cf(0, key);
+    }
+
+    fn merge(&mut self, key: &[u8], value: &[u8]) {
+        self.merge_cf(0, key, value);
+    }
+
+    fn single_delete(&mut self, key: &[u8]) {
+        self.single_delete_cf(0, key);
+    }
+
+    fn delete_range(&mut self, begin_key: &[u8], end_key: &[u8]) {
+        self.delete_range_cf(0, begin_key, end_key);
+    }
+
+    fn put_cf(&mut self, cf_id: u32, key: &[u8], value: &[u8]) {
+        self.ops.push(Op::Put(cf_id, key.to_vec(), value.to_vec()));
+    }
+
+    fn delete_cf(&mut self, cf_id: u32, key: &[u8]) {
+        self.ops.push(Op::Delete(cf_id, key.to_vec()));
+    }
+
+    fn merge_cf(&mut self, cf_id: u32, key: &[u8], value: &[u8]) {
+        self.ops
+            .push(Op::Merge(cf_id, key.to_vec(), value.to_vec()));
+    }
+
+    fn single_delete_cf(&mut self, cf_id: u32, key: &[u8]) {
+        self.ops.push(Op::SingleDelete(cf_id, key.to_vec()));
+    }
+
+    fn delete_range_cf(&mut self, cf_id: u32, begin_key: &[u8], end_key: &[u8]) {
+        self.ops
+            .push(Op::DeleteRange(cf_id, begin_key.to_vec(), end_key.to_vec()));
+    }
+}
 
 #[test]
 fn test_write_batch_clear() {
@@ -25,3 +84,233 @@ fn test_write_batch_clear() {
     assert_eq!(batch.len(), 0);
     assert!(batch.is_empty());
 }
+
+#[test]
+fn test_write_batch_iterate() {
+    let path = TemporaryDBPath::new();
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.create_missing_column_families(true);
+        let db = DB::open_cf(&opts, &path, ["cf1"]).unwrap();
+        let cf1 = db.cf_handle("cf1").unwrap();
+        assert_ne!(cf1.id(), 0);
+
+        let mut batch = WriteBatch::default();
+        batch.put(b"k1", b"v1").unwrap();
+        batch.merge(b"k2", b"v2").unwrap();
+        batch.delete(b"k3").unwrap();
+        batch.delete_range(b"k4", b"k5").unwrap();
+        batch.put_cf(cf1, b"k6", b"v6").unwrap();
+        batch.delete_cf(cf1, b"k7").unwrap();
+
+        let mut recorder = Recorder::default();
+        batch.iterate(&mut recorder).unwrap();
+        assert_eq!(
+            recorder.ops,
+            vec![
+                Op::Put(0, b"k1".to_vec(), b"v1".to_vec()),
+                Op::Merge(0, b"k2".to_vec(), b"v2".to_vec()),
+                Op::Delete(0, b"k3".to_vec()),
+                Op::DeleteRange(0, b"k4".to_vec(), b"k5".to_vec()),
+                Op::Put(cf1.id(), b"k6".to_vec(), b"v6".to_vec()),
+                Op::Delete(cf1.id(), b"k7".to_vec()),
+            ]
+        );
+    }
+}
+
+/// Replays the operations it receives into another batch. `WriteBatch` has
+/// no single deletes, so they are replayed as deletes.
+struct Replayer<'a> {
+    batch: WriteBatch,
+    cf1: &'a ColumnFamily,
+}
+
+impl<'a> Replayer<'a> {
+    fn cf(&self, cf_id: u32) -> &'a ColumnFamily {
+        assert_eq!(cf_id, self.cf1.id());
+        self.cf1
+    }
+}
+
+impl WriteBatchHandler for Replayer<'_> {
+    fn put(&mut self, key: &[u8], value: &[u8]) {
+        self.batch.put(key, value).unwrap();
+    }
+
+    fn delete(&mut self, key: &[u8]) {
+        self.batch.delete(key).unwrap();
+    }
+
+    fn merge(&mut self, key: &[u8], value: &[u8]) {
+        self.batch.merge(key, value).unwrap();
+    }
+
+    fn single_delete(&mut self, key: &[u8]) {
+        self.batch.delete(key).unwrap();
+    }
+
+    fn delete_range(&mut self, begin_key: &[u8], end_key: &[u8]) {
+        self.batch.delete_range(begin_key, end_key).unwrap();
+    }
+
+    fn put_cf(&mut self, cf_id: u32, key: &[u8], value: &[u8]) {
+        let cf = self.cf(cf_id);
+        self.batch.put_cf(cf, key, value).unwrap();
+    }
+
+    fn delete_cf(&mut self, cf_id: u32, key: &[u8]) {
+        let cf = self.cf(cf_id);
+        self.batch.delete_cf(cf, key).unwrap();
+    }
+
+    fn merge_cf(&mut self, cf_id: u32, key: &[u8], value: &[u8]) {
+        let cf = self.cf(cf_id);
+        self.batch.merge_cf(cf, key, value).unwrap();
+    }
+
+    fn single_delete_cf(&mut self, cf_id: u32, key: &[u8]) {
+        let cf = self.cf(cf_id);
+        self.batch.delete_cf(cf, key).unwrap();
+    }
+
+    fn delete_range_cf(&mut self, cf_id: u32, begin_key: &[u8], end_key: &[u8]) {
+        let cf = self.cf(cf_id);
+        self.batch.delete_range_cf(cf, begin_key, end_key).unwrap();
+    }
+}
+
+#[test]
+fn test_write_batch_iterate_single_delete() {
+    let path = TemporaryDBPath::new();
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.create_missing_column_families(true);
+        let db = DB::open_cf(&opts, &path, ["cf1"]).unwrap();
+        let cf1 = db.cf_handle("cf1").unwrap();
+        db.put(b"k1", b"v1").unwrap();
+        db.put_cf(cf1, b"k2", b"v2").unwrap();
+
+        // Single deletes can only be added to a batch through its serialized
+        // form: sequence number, count, then tagged records.
+        assert!(cf1.id() < 0x80);
+        let cf_id = cf1.id() as u8;
+        let mut data = vec![0; 8];
+        data.extend_from_slice(&3u32.to_le_bytes());
+        data.extend_from_slice(&[0x7, 2, b'k', b'1']);
+        data.extend_from_slice(&[0x5, cf_id, 2, b'k', b'3', 2, b'v', b'3']);
+        data.extend_from_slice(&[0x8, cf_id, 2, b'k', b'2']);
+        let batch = WriteBatch::from_data(&data).unwrap();
+
+        let mut recorder = Recorder::default();
+        batch.iterate(&mut recorder).unwrap();
+        assert_eq!(
+            recorder.ops,
+            vec![
+                Op::SingleDelete(0, b"k1".to_vec()),
+                Op::Put(cf1.id(), b"k3".to_vec(), b"v3".to_vec()),
+                Op::SingleDelete(cf1.id(), b"k2".to_vec()),
+            ]
+        );
+
+        let mut replayer = Replayer {
+            batch: WriteBatch::default(),
+            cf1,
+        };
+        batch.iterate(&mut replayer).unwrap();
+        assert_eq!(replayer.batch.len(), 3);
+        db.write(&replayer.batch).unwrap();
+        assert!(db.get(b"k1").unwrap().is_none());
+        assert!(db.get_cf(cf1, b"k2").unwrap().is_none());
+        assert_eq!(&*db.get_cf(cf1, b"k3").unwrap().unwrap(), b"v3");
+    }
+}
+
+#[test]
+fn test_write_batch_data() {
+    let path = TemporaryDBPath::new();
+    {
+        let db = DB::open_default(&path).unwrap();
+
+        let mut batch = WriteBatch::default();
+        batch.put(b"k1", b"v1").unwrap();
+        batch.put(b"k2", b"v2").unwrap();
+        batch.delete(b"k3").unwrap();
+        assert_eq!(batch.data().len(), batch.size_in_bytes());
+
+        let copy = WriteBatch::from_data(batch.data()).unwrap();
+        assert_eq!(copy.len(), 3);
+        assert_eq!(copy.data(), batch.data());
+
+        db.put(b"k3", b"v3").unwrap();
+        db.write(&copy).unwrap();
+        assert_eq!(&*db.get(b"k1").unwrap().unwrap(), b"v1");
+        assert_eq!(&*db.get(b"k2").unwrap().unwrap(), b"v2");
+        assert!(db.get(b"k3").unwrap().is_none());
+
+        let err = WriteBatch::from_data(b"short").err().unwrap();
+        assert_eq!(err.kind(), ErrorKind::Corruption);
+    }
+}
+
+struct PanickingHandler {
+    calls: usize,
+}
+
+impl WriteBatchHandler for PanickingHandler {
+    fn put(&mut self, _key: &[u8], _value: &[u8]) {
+        self.calls += 1;
+        panic!("handler failed");
+    }
+
+    fn delete(&mut self, _key: &[u8]) {
+        self.calls += 1;
+    }
+
+    fn merge(&mut self, _key: &[u8], _value: &[u8]) {
+        self.calls += 1;
+    }
+
+    fn single_delete(&mut self, _key: &[u8]) {
+        self.calls += 1;
+    }
+
+    fn delete_range(&mut self, _begin_key: &[u8], _end_key: &[u8]) {
+        self.calls += 1;
+    }
+
+    fn put_cf(&mut self, _cf_id: u32, _key: &[u8], _value: &[u8]) {
+        self.calls += 1;
+    }
+
+    fn delete_cf(&mut self, _cf_id: u32, _key: &[u8]) {
+        self.calls += 1;
+    }
+
+    fn merge_cf(&mut self, _cf_id: u32, _key: &[u8], _value: &[u8]) {
+        self.calls += 1;
+    }
+
+    fn single_delete_cf(&mut self, _cf_id: u32, _key: &[u8]) {
+        self.calls += 1;
+    }
+
+    fn delete_range_cf(&mut self, _cf_id: u32, _begin_key: &[u8], _end_key: &[u8]) {
+        self.calls += 1;
+    }
+}
+
+#[test]
+fn test_write_batch_iterate_panic() {
+    let mut batch = WriteBatch::default();
+    batch.put(b"k1", b"v1").unwrap();
+    batch.delete(b"k2").unwrap();
+
+    let mut handler = PanickingHandler { calls: 0 };
+    let result = panic::catch_unwind(panic::AssertUnwindSafe(|| batch.iterate(&mut handler)));
+    let payload = result.unwrap_err();
+    assert_eq!(payload.downcast_ref::<&str>(), Some(&"handler failed"));
+    assert_eq!(handler.calls, 1);
+}
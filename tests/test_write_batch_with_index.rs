@@ -0,0 +1,180 @@
+// Copyright 2019 Tyler Neely
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+extern crate ckb_rocksdb as rocksdb;
+
+use crate::rocksdb::{
+    prelude::*, MergeOperands, TemporaryDBPath, TransactionDB, WriteBatchWithIndex,
+};
+
+fn concat_merge(
+    _new_key: &[u8],
+    existing_val: Option<&[u8]>,
+    operands: &mut MergeOperands,
+) -> Option<Vec<u8>> {
+    let mut result = existing_val.map(|v| v.to_vec()).unwrap_or_default();
+    for op in operands {
+        result.extend_from_slice(op);
+    }
+    Some(result)
+}
+
+#[test]
+fn test_write_batch_with_index_get_from_batch() {
+    let opts = Options::default();
+
+    let mut batch = WriteBatchWithIndex::default();
+    assert!(batch.is_empty());
+    batch.put(b"k1", b"v1").unwrap();
+    batch.put(b"k2", b"v2").unwrap();
+    batch.delete(b"k2").unwrap();
+    batch.merge(b"k3", b"v3").unwrap();
+    assert_eq!(batch.len(), 4);
+
+    let value = batch.get_from_batch(&opts, b"k1").unwrap();
+    assert_eq!(value.as_deref(), Some(&b"v1"[..]));
+    assert!(batch.get_from_batch(&opts, b"k2").unwrap().is_none());
+    assert!(batch.get_from_batch(&opts, b"k3").is_err());
+    assert!(batch.get_from_batch(&opts, b"k4").unwrap().is_none());
+
+    batch.clear().unwrap();
+    assert!(batch.is_empty());
+    assert!(batch.get_from_batch(&opts, b"k1").unwrap().is_none());
+}
+
+#[test]
+fn test_write_batch_with_index_get_from_batch_and_db() {
+    let path = TemporaryDBPath::new();
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    opts.set_merge_operator_associative("concat", concat_merge);
+    let db = DB::open(&opts, &path).unwrap();
+    db.put(b"k1", b"db1").unwrap();
+    db.put(b"k2", b"db2").unwrap();
+    db.put(b"k4", b"a").unwrap();
+
+    let mut batch = WriteBatchWithIndex::default();
+    batch.put(b"k1", b"batch1").unwrap();
+    batch.delete(b"k2").unwrap();
+    batch.put(b"k3", b"batch3").unwrap();
+    batch.merge(b"k4", b"b").unwrap();
+
+    let value = batch.get_from_batch_and_db(&db, b"k1").unwrap();
+    assert_eq!(value.as_deref(), Some(&b"batch1"[..]));
+    assert!(batch.get_from_batch_and_db(&db, b"k2").unwrap().is_none());
+    let value = batch.get_from_batch_and_db(&db, b"k3").unwrap();
+    assert_eq!(value.as_deref(), Some(&b"batch3"[..]));
+    let value = batch.get_from_batch_and_db(&db, b"k4").unwrap();
+    assert_eq!(value.as_deref(), Some(&b"ab"[..]));
+
+    // nothing is visible in the database until the batch is written
+    assert_eq!(db.get(b"k1").unwrap().as_deref(), Some(&b"db1"[..]));
+    assert!(db.get(b"k3").unwrap().is_none());
+
+    db.write(&batch).unwrap();
+    assert_eq!(db.get(b"k1").unwrap().as_deref(), Some(&b"batch1"[..]));
+    assert!(db.get(b"k2").unwrap().is_none());
+    assert_eq!(db.get(b"k3").unwrap().as_deref(), Some(&b"batch3"[..]));
+    assert_eq!(db.get(b"k4").unwrap().as_deref(), Some(&b"ab"[..]));
+}
+
+#[test]
+fn test_write_batch_with_index_iterator_with_base() {
+    let path = TemporaryDBPath::new();
+    let db = DB::open_default(&path).unwrap();
+    db.put(b"k1", b"db1").unwrap();
+    db.put(b"k2", b"db2").unwrap();
+    db.put(b"k4", b"db4").unwrap();
+
+    let mut batch = WriteBatchWithIndex::default();
+    batch.put(b"k2", b"batch2").unwrap();
+    batch.put(b"k3", b"batch3").unwrap();
+    batch.delete(b"k4").unwrap();
+
+    let mut iter = batch.iterator_with_base(db.raw_iterator());
+    iter.seek_to_first();
+    let mut items = Vec::new();
+    while iter.valid() {
+        items.push((iter.key().unwrap().to_vec(), iter.value().unwrap().to_vec()));
+        iter.next();
+    }
+    assert!(iter.status().is_ok());
+    assert_eq!(
+        items,
+        vec![
+            (b"k1".to_vec(), b"db1".to_vec()),
+            (b"k2".to_vec(), b"batch2".to_vec()),
+            (b"k3".to_vec(), b"batch3".to_vec()),
+        ]
+    );
+
+    iter.seek_to_last();
+    assert_eq!(iter.key(), Some(&b"k3"[..]));
+}
+
+#[test]
+fn test_write_batch_with_index_cf() {
+    let path = TemporaryDBPath::new();
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    opts.create_missing_column_families(true);
+    let db = DB::open_cf(&opts, &path, ["cf1"]).unwrap();
+    let cf1 = db.cf_handle("cf1").unwrap();
+    db.put_cf(cf1, b"k1", b"db1").unwrap();
+
+    let mut batch = WriteBatchWithIndex::default();
+    batch.put_cf(cf1, b"k2", b"batch2").unwrap();
+    batch.put(b"k3", b"default3").unwrap();
+
+    assert!(batch
+        .get_from_batch_cf(&opts, cf1, b"k3")
+        .unwrap()
+        .is_none());
+    let value = batch.get_from_batch_and_db_cf(&db, cf1, b"k1").unwrap();
+    assert_eq!(value.as_deref(), Some(&b"db1"[..]));
+    let value = batch.get_from_batch_and_db_cf(&db, cf1, b"k2").unwrap();
+    assert_eq!(value.as_deref(), Some(&b"batch2"[..]));
+
+    {
+        let mut iter = batch.iterator_with_base_cf(db.raw_iterator_cf(cf1).unwrap(), cf1);
+        iter.seek_to_first();
+        assert_eq!(iter.key(), Some(&b"k1"[..]));
+        iter.next();
+        assert_eq!(iter.key(), Some(&b"k2"[..]));
+        iter.next();
+        assert!(!iter.valid());
+    }
+
+    db.write(&batch).unwrap();
+    assert_eq!(
+        db.get_cf(cf1, b"k2").unwrap().as_deref(),
+        Some(&b"batch2"[..])
+    );
+    assert_eq!(db.get(b"k3").unwrap().as_deref(), Some(&b"default3"[..]));
+}
+
+#[test]
+fn test_write_batch_with_index_transaction_db() {
+    let path = TemporaryDBPath::new();
+    let db = TransactionDB::open_default(&path).unwrap();
+
+    let mut batch = WriteBatchWithIndex::default();
+    batch.put(b"k1", b"v1").unwrap();
+    batch.put(b"k2", b"v2").unwrap();
+    batch.delete(b"k1").unwrap();
+
+    db.write(&batch).unwrap();
+    assert!(db.get(b"k1").unwrap().is_none());
+    assert_eq!(db.get(b"k2").unwrap().as_deref(), Some(&b"v2"[..]));
+}